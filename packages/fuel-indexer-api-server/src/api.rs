@@ -1,8 +1,10 @@
 use crate::{
+    cache::GraphqlCache,
     middleware::AuthenticationMiddleware,
     uses::{
-        get_nonce, graphql_playground, health_check, indexer_status, query_graph,
-        register_indexer_assets, remove_indexer, sql_query, verify_signature,
+        get_nonce, graphql_playground, health_check, indexer_status,
+        query_federated_graph, query_graph, register_indexer_assets, remove_indexer,
+        sql_query, subscribe_graph, verify_signature,
     },
 };
 
@@ -20,7 +22,11 @@ use axum::{
 };
 use fuel_indexer_database::{IndexerConnectionPool, IndexerDatabaseError};
 use fuel_indexer_graphql::graphql::GraphqlError;
-use fuel_indexer_lib::{config::IndexerConfig, defaults, utils::ServiceRequest};
+use fuel_indexer_lib::{
+    config::IndexerConfig,
+    defaults,
+    utils::{subscribe_to_entity_changes, subscribe_to_schema_changes, ServiceRequest},
+};
 use fuel_indexer_schema::db::{manager::SchemaManager, IndexerSchemaDbError};
 use hyper::Method;
 use serde_json::json;
@@ -30,7 +36,10 @@ use std::{
     time::{Duration, Instant},
 };
 use thiserror::Error;
-use tokio::sync::mpsc::{error::SendError, Sender};
+use tokio::sync::{
+    broadcast::error::RecvError,
+    mpsc::{error::SendError, Sender},
+};
 use tower::ServiceBuilder;
 use tower::{buffer::BufferLayer, limit::RateLimitLayer};
 use tower_http::{
@@ -60,6 +69,8 @@ pub enum HttpError {
     NotFound(String),
     #[error("Error.")]
     InternalServer,
+    #[error("Service unavailable. {0:#?}")]
+    ServiceUnavailable(String),
     #[error("HTTP error: {0:?}")]
     Http(http::Error),
 }
@@ -125,6 +136,23 @@ impl IntoResponse for ApiError {
 
         error!("ApiError: {self:?}");
 
+        // Queries that exceed the limits of the web API are rejected with a GraphQL
+        // error, whose code tells clients which limit was exceeded.
+        if let Self::Graphql(e) = &self {
+            if let Some(code) = e.code() {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({
+                        "errors": [{
+                            "message": e.to_string(),
+                            "extensions": { "code": code },
+                        }],
+                    })),
+                )
+                    .into_response();
+            }
+        }
+
         let (status, details) = match self {
             Self::JsonWebToken(e) => (
                 StatusCode::BAD_REQUEST,
@@ -139,6 +167,10 @@ impl IntoResponse for ApiError {
             Self::Http(HttpError::NotFound(e)) => {
                 (StatusCode::NOT_FOUND, format!("Not found: {e}."))
             }
+            Self::Http(HttpError::ServiceUnavailable(e)) => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                format!("Service unavailable: {e}."),
+            ),
             Self::Sqlx(e) => (
                 StatusCode::INTERNAL_SERVER_ERROR,
                 format!("Database error: {e}."),
@@ -196,6 +228,53 @@ impl WebApi {
     ) -> ApiResult<Router> {
         let sm = SchemaManager::new(pool.clone());
         let schema_manager = Arc::new(RwLock::new(sm));
+        // Without an indexer service receiving requests, no indexer runs in this process.
+        let in_service = !tx.is_closed();
+        let cache = Arc::new(GraphqlCache::new(
+            config.web_api.response_cache_size,
+            in_service,
+        ));
+
+        // Schemas replaced by the indexer service running in this process, e.g. by
+        // promoting a shadow indexer, have to be reloaded.
+        tokio::spawn({
+            let cache = cache.clone();
+            let mut changes = subscribe_to_schema_changes();
+            async move {
+                loop {
+                    match changes.recv().await {
+                        Ok(change) => {
+                            cache
+                                .invalidate(&change.namespace, &change.identifier)
+                                .await
+                        }
+                        // Changes that were missed could be to any indexer.
+                        Err(RecvError::Lagged(_)) => cache.clear().await,
+                        Err(RecvError::Closed) => break,
+                    }
+                }
+            }
+        });
+
+        // Commits of the indexers running in this process tell which cached
+        // responses are out of date.
+        if in_service {
+            tokio::spawn({
+                let cache = cache.clone();
+                let mut changes = subscribe_to_entity_changes();
+                async move {
+                    loop {
+                        match changes.recv().await {
+                            Ok(changes) => cache.record_commit(&changes).await,
+                            Err(RecvError::Lagged(_)) => {
+                                cache.clear_block_heights().await
+                            }
+                            Err(RecvError::Closed) => break,
+                        }
+                    }
+                }
+            });
+        }
         let max_body_size = config.web_api.max_body_size;
         let start_time = Arc::new(Instant::now());
 
@@ -205,9 +284,16 @@ impl WebApi {
             Level::from_str(config.log_level.as_ref()).expect("Invalid log level.");
 
         let mut graph_routes = Router::new()
-            .route("/:namespace/:identifier", post(query_graph))
+            .route("/", post(query_federated_graph))
+            .route(
+                "/:namespace/:identifier",
+                post(query_graph).get(subscribe_graph),
+            )
+            .layer(Extension(tx.clone()))
             .layer(Extension(schema_manager.clone()))
+            .layer(Extension(cache.clone()))
             .layer(Extension(pool.clone()))
+            .layer(Extension(config.clone()))
             .layer(RequestBodyLimitLayer::new(max_body_size));
 
         let mut sql_routes = Router::new();
@@ -256,6 +342,7 @@ impl WebApi {
             .route("/:namespace/:identifier", delete(remove_indexer))
             .layer(AuthenticationMiddleware::from(&config))
             .layer(Extension(tx))
+            .layer(Extension(cache))
             .layer(Extension(pool.clone()))
             .layer(Extension(config.clone()))
             .layer(RequestBodyLimitLayer::new(max_body_size));
@@ -0,0 +1,514 @@
+use crate::api::{ApiError, ApiResult, HttpError};
+use async_graphql::{dynamic::Schema as DynamicSchema, Request};
+use async_std::sync::{Arc, Mutex, RwLock};
+use fuel_indexer_database::{queries, IndexerConnectionPool};
+use fuel_indexer_graphql::{
+    dynamic::{build_dynamic_schema, build_federated_dynamic_schema},
+    federation::federated_field_names,
+    graphql::META_FIELD,
+};
+use fuel_indexer_lib::{defaults, utils::EntityChanges};
+use fuel_indexer_schema::db::{manager::SchemaManager, tables::IndexerSchema};
+use serde_json::Value;
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+/// The schemas used to serve the GraphQL queries of one version of an indexer,
+/// along with the responses cached for that version.
+pub(crate) struct CachedSchema {
+    /// Version of the indexer's GraphQL schema.
+    version: String,
+
+    /// When `version` was last checked against the latest version of the schema.
+    checked_at: Mutex<Instant>,
+
+    /// Schema used to build the SQL queries.
+    pub schema: IndexerSchema,
+
+    /// Dynamic schema used to answer introspection queries.
+    pub dynamic_schema: DynamicSchema,
+
+    /// Responses cached at the indexer's last committed block height.
+    responses: Mutex<CachedResponses>,
+}
+
+/// The dynamic schema used to validate and introspect federated queries, along with
+/// the schemas of the indexers that it serves.
+pub(crate) struct FederatedSchema {
+    /// Schemas of each indexer, by the name of its root field.
+    pub indexers: BTreeMap<String, Arc<CachedSchema>>,
+
+    /// Namespace and identifier of each indexer, by the name of its root field.
+    names: BTreeMap<String, (String, String)>,
+
+    /// Dynamic schema made of the root fields of every indexer.
+    pub dynamic_schema: DynamicSchema,
+}
+
+#[derive(Default)]
+struct CachedResponses {
+    block_height: u32,
+    responses: HashMap<String, Value>,
+}
+
+/// Identifies a cached response: the request it answers, and the last block height
+/// committed by the indexer when it was built.
+pub(crate) struct ResponseKey {
+    block_height: u32,
+    request: String,
+}
+
+impl CachedSchema {
+    /// Whether the version of the schema is due to be checked again, in which case
+    /// the check is recorded as done.
+    async fn check_due(&self) -> bool {
+        let mut checked_at = self.checked_at.lock().await;
+        if checked_at.elapsed() < Duration::from_secs(defaults::SCHEMA_VERSION_CHECK_SECS)
+        {
+            return false;
+        }
+        *checked_at = Instant::now();
+        true
+    }
+
+    /// Return the cached response for the given key, if any.
+    pub async fn response(&self, key: &ResponseKey) -> Option<Value> {
+        let cached = self.responses.lock().await;
+        if cached.block_height != key.block_height {
+            return None;
+        }
+        cached.responses.get(&key.request).cloned()
+    }
+
+    /// Cache a response, dropping every response that was cached at another block height.
+    ///
+    /// Once `capacity` responses are cached for a block height, further responses
+    /// aren't cached until the indexer commits a new block.
+    async fn insert_response(&self, key: ResponseKey, response: Value, capacity: usize) {
+        let mut cached = self.responses.lock().await;
+        if cached.block_height != key.block_height {
+            cached.block_height = key.block_height;
+            cached.responses.clear();
+        }
+        if cached.responses.len() < capacity {
+            cached.responses.insert(key.request, response);
+        }
+    }
+}
+
+/// Cache of the schemas, and optionally of the query responses, of each indexer
+/// served by the web API.
+pub(crate) struct GraphqlCache {
+    schemas: RwLock<HashMap<(String, String), Arc<CachedSchema>>>,
+    federated: RwLock<Option<Arc<FederatedSchema>>>,
+    response_cache_size: Option<usize>,
+
+    /// Last block height at which each indexer committed changes, if the indexers
+    /// run in this process.
+    block_heights: Option<RwLock<HashMap<(String, String), u32>>>,
+}
+
+impl GraphqlCache {
+    /// Create a new `GraphqlCache`, caching up to `response_cache_size` responses per
+    /// indexer, or no responses at all if `response_cache_size` is `None`.
+    ///
+    /// If `in_service` is set, the web API runs within the indexer service, and the
+    /// commits of its indexers are tracked with `record_commit`. Otherwise, commits
+    /// are looked up in the database.
+    pub fn new(response_cache_size: Option<usize>, in_service: bool) -> Self {
+        Self {
+            schemas: RwLock::new(HashMap::new()),
+            federated: RwLock::new(None),
+            response_cache_size,
+            block_heights: in_service.then(|| RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Return the schemas for the latest version of the given indexer, loading and
+    /// caching them if they aren't cached yet.
+    ///
+    /// Cached schemas are dropped when the indexer is registered again, removed, or its
+    /// schema is replaced by the indexer service running in this process. Schemas
+    /// replaced by another process are noticed by checking their version every
+    /// `SCHEMA_VERSION_CHECK_SECS`.
+    pub async fn schema(
+        &self,
+        pool: &IndexerConnectionPool,
+        manager: &Arc<RwLock<SchemaManager>>,
+        namespace: &str,
+        identifier: &str,
+    ) -> ApiResult<Arc<CachedSchema>> {
+        let not_found = || {
+            ApiError::Http(HttpError::NotFound(format!(
+                "The graph '{namespace}.{identifier}' was not found."
+            )))
+        };
+
+        let key = (namespace.to_string(), identifier.to_string());
+        let cached = self.schemas.read().await.get(&key).cloned();
+        if let Some(cached) = &cached {
+            if !cached.check_due().await {
+                return Ok(cached.clone());
+            }
+        }
+
+        let version = {
+            let mut conn = pool.acquire().await?;
+            queries::graph_root_latest_version(&mut conn, namespace, identifier).await
+        };
+
+        if let Some(cached) = cached {
+            if version.as_ref().ok() == Some(&cached.version) {
+                return Ok(cached);
+            }
+            self.invalidate(namespace, identifier).await;
+        }
+
+        let version = version.map_err(|_e| not_found())?;
+        let schema = manager
+            .read()
+            .await
+            .load_schema(namespace, identifier)
+            .await
+            .map_err(|_e| not_found())?;
+        let dynamic_schema = build_dynamic_schema(&schema)?;
+
+        let cached = Arc::new(CachedSchema {
+            version,
+            checked_at: Mutex::new(Instant::now()),
+            schema,
+            dynamic_schema,
+            responses: Mutex::new(CachedResponses::default()),
+        });
+        self.schemas.write().await.insert(key, cached.clone());
+
+        Ok(cached)
+    }
+
+    /// Return the federated schema of every registered indexer, building and caching
+    /// it if it isn't cached yet.
+    ///
+    /// The cached schema is dropped whenever the schemas of any indexer are.
+    pub async fn federated_schema(
+        &self,
+        pool: &IndexerConnectionPool,
+        manager: &Arc<RwLock<SchemaManager>>,
+    ) -> ApiResult<Arc<FederatedSchema>> {
+        let cached = self.federated.read().await.clone();
+        if let Some(cached) = cached {
+            // The schema of each indexer is checked as when it's queried on its own.
+            // An indexer that can't be found anymore was removed.
+            let mut fresh = true;
+            for (name, (namespace, identifier)) in cached.names.iter() {
+                fresh &= match self.schema(pool, manager, namespace, identifier).await {
+                    Ok(schema) => Arc::ptr_eq(&schema, &cached.indexers[name]),
+                    Err(_) => false,
+                };
+            }
+            if fresh {
+                return Ok(cached);
+            }
+        }
+
+        let indexers = {
+            let mut conn = pool.acquire().await?;
+            queries::all_registered_indexers(&mut conn)
+                .await?
+                .into_iter()
+                .map(|indexer| (indexer.namespace, indexer.identifier))
+                .collect::<Vec<_>>()
+        };
+
+        let names = federated_field_names(indexers)
+            .into_iter()
+            .collect::<BTreeMap<_, _>>();
+
+        let mut schemas = BTreeMap::new();
+        for (name, (namespace, identifier)) in names.iter() {
+            schemas.insert(
+                name.clone(),
+                self.schema(pool, manager, namespace, identifier).await?,
+            );
+        }
+
+        // A schema can't be built without any root field to query.
+        if schemas.is_empty() {
+            return Err(ApiError::Http(HttpError::NotFound(
+                "No indexers are registered.".to_string(),
+            )));
+        }
+
+        let dynamic_schema = build_federated_dynamic_schema(
+            schemas
+                .iter()
+                .map(|(name, cached)| (name.as_str(), &cached.schema)),
+        )?;
+
+        let federated = Arc::new(FederatedSchema {
+            indexers: schemas,
+            names,
+            dynamic_schema,
+        });
+        *self.federated.write().await = Some(federated.clone());
+
+        Ok(federated)
+    }
+
+    /// Drop everything cached for the given indexer.
+    pub async fn invalidate(&self, namespace: &str, identifier: &str) {
+        let key = (namespace.to_string(), identifier.to_string());
+        self.schemas.write().await.remove(&key);
+        *self.federated.write().await = None;
+        if let Some(block_heights) = &self.block_heights {
+            block_heights.write().await.remove(&key);
+        }
+    }
+
+    /// Drop everything cached for every indexer.
+    pub async fn clear(&self) {
+        self.schemas.write().await.clear();
+        *self.federated.write().await = None;
+        self.clear_block_heights().await;
+    }
+
+    /// Record the changes committed by an indexer running in this process.
+    pub async fn record_commit(&self, changes: &EntityChanges) {
+        let Some(block_heights) = &self.block_heights else {
+            return;
+        };
+
+        let key = (changes.namespace.clone(), changes.identifier.clone());
+        let mut block_heights = block_heights.write().await;
+        match changes.block_height {
+            Some(height) => {
+                let last = block_heights.entry(key).or_default();
+                *last = (*last).max(height);
+            }
+            // The height is looked up in the database again.
+            None => {
+                block_heights.remove(&key);
+            }
+        }
+    }
+
+    /// Forget the commits recorded for every indexer, e.g. after missing some of them.
+    pub async fn clear_block_heights(&self) {
+        if let Some(block_heights) = &self.block_heights {
+            block_heights.write().await.clear();
+        }
+    }
+
+    /// Return the last block height at which the given indexer committed changes.
+    async fn block_height(
+        &self,
+        pool: &IndexerConnectionPool,
+        namespace: &str,
+        identifier: &str,
+    ) -> ApiResult<u32> {
+        let key = (namespace.to_string(), identifier.to_string());
+        if let Some(block_heights) = &self.block_heights {
+            if let Some(height) = block_heights.read().await.get(&key) {
+                return Ok(*height);
+            }
+        }
+
+        let height = {
+            let mut conn = pool.acquire().await?;
+            queries::last_block_height_for_indexer(&mut conn, namespace, identifier)
+                .await?
+        };
+
+        // A commit that was recorded while the height was looked up is more recent.
+        match &self.block_heights {
+            Some(block_heights) => {
+                Ok(*block_heights.write().await.entry(key).or_insert(height))
+            }
+            None => Ok(height),
+        }
+    }
+
+    /// Return the key under which the response to the given request is cached, or
+    /// `None` if responses aren't cached.
+    pub async fn response_key(
+        &self,
+        pool: &IndexerConnectionPool,
+        namespace: &str,
+        identifier: &str,
+        request: &Request,
+    ) -> ApiResult<Option<ResponseKey>> {
+        if self.response_cache_size.is_none() {
+            return Ok(None);
+        }
+
+        // The status reported by `_meta` can change without a new block being committed.
+        let query = normalize_query(&request.query);
+        if query
+            .split(|c: char| !c.is_alphanumeric() && c != '_')
+            .any(|token| token == META_FIELD)
+        {
+            return Ok(None);
+        }
+
+        let block_height = self.block_height(pool, namespace, identifier).await?;
+
+        let request = format!(
+            "{}\n{}\n{}",
+            query,
+            request.operation_name.as_deref().unwrap_or_default(),
+            serde_json::to_string(&request.variables)?
+        );
+
+        Ok(Some(ResponseKey {
+            block_height,
+            request,
+        }))
+    }
+
+    /// Cache the response for the given key, if responses are cached.
+    pub async fn insert_response(
+        &self,
+        cached: &CachedSchema,
+        key: ResponseKey,
+        response: Value,
+    ) {
+        if let Some(capacity) = self.response_cache_size {
+            cached.insert_response(key, response, capacity).await;
+        }
+    }
+}
+
+/// Collapse the whitespace, commas and comments outside of strings in a GraphQL query,
+/// so that queries that only differ in formatting share the same cached response.
+fn normalize_query(query: &str) -> String {
+    let mut normalized = String::with_capacity(query.len());
+    let mut chars = query.chars().peekable();
+    let mut separated = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() || c == ',' => separated = true,
+            '#' => {
+                separated = true;
+                for c in chars.by_ref() {
+                    if c == '\n' || c == '\r' {
+                        break;
+                    }
+                }
+            }
+            _ => {
+                if separated && !normalized.is_empty() {
+                    normalized.push(' ');
+                }
+                separated = false;
+                normalized.push(c);
+
+                if c == '"' {
+                    let block = chars.peek() == Some(&'"') && {
+                        let mut ahead = chars.clone();
+                        ahead.next();
+                        ahead.peek() == Some(&'"')
+                    };
+
+                    if block {
+                        normalized.push(chars.next().unwrap_or_default());
+                        normalized.push(chars.next().unwrap_or_default());
+                        let mut quotes = 0;
+                        for c in chars.by_ref() {
+                            normalized.push(c);
+                            quotes = if c == '"' { quotes + 1 } else { 0 };
+                            if quotes == 3 {
+                                break;
+                            }
+                        }
+                    } else {
+                        let mut escaped = false;
+                        for c in chars.by_ref() {
+                            normalized.push(c);
+                            match c {
+                                '\\' if !escaped => escaped = true,
+                                '"' if !escaped => break,
+                                _ => escaped = false,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    normalized
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_record_commit() {
+        async fn recorded(cache: &GraphqlCache) -> Option<u32> {
+            let block_heights = cache.block_heights.as_ref().unwrap();
+            let key = ("fuel".to_string(), "test".to_string());
+            block_heights.read().await.get(&key).copied()
+        }
+
+        let cache = GraphqlCache::new(Some(10), true);
+        let commit = |block_height| EntityChanges {
+            namespace: "fuel".to_string(),
+            identifier: "test".to_string(),
+            block_height,
+            ..EntityChanges::default()
+        };
+
+        cache.record_commit(&commit(Some(5))).await;
+        assert_eq!(recorded(&cache).await, Some(5));
+
+        // Commits received out of order don't go back in time.
+        cache.record_commit(&commit(Some(3))).await;
+        assert_eq!(recorded(&cache).await, Some(5));
+
+        // Commits without a height have to be looked up in the database.
+        cache.record_commit(&commit(None)).await;
+        assert_eq!(recorded(&cache).await, None);
+
+        cache.record_commit(&commit(Some(7))).await;
+        cache.invalidate("fuel", "test").await;
+        assert_eq!(recorded(&cache).await, None);
+
+        // Without an indexer service, nothing is recorded.
+        let standalone = GraphqlCache::new(Some(10), false);
+        standalone.record_commit(&commit(Some(5))).await;
+        assert!(standalone.block_heights.is_none());
+    }
+
+    #[test]
+    fn test_normalize_query() {
+        let query = r#"
+            query {
+                # Every block, by height.
+                block(order: { height: asc }, filter: { hash: { equals: "a,  b" } }) {
+                    id,
+                    height
+                }
+            }
+        "#;
+
+        assert_eq!(
+            normalize_query(query),
+            r#"query { block(order: { height: asc } filter: { hash: { equals: "a,  b" } }) { id height } }"#
+        );
+        assert_eq!(
+            normalize_query("query {\n  block { id, height }\n}"),
+            normalize_query("query { block { id height } }")
+        );
+        assert_ne!(
+            normalize_query(r#"{ block(filter: { hash: { equals: "a b" } }) { id } }"#),
+            normalize_query(r#"{ block(filter: { hash: { equals: "a  b" } }) { id } }"#)
+        );
+        assert_eq!(
+            normalize_query("{ block { id, \"\"\"a,  \"b\"\n\"\"\" } }"),
+            "{ block { id \"\"\"a,  \"b\"\n\"\"\" } }"
+        );
+    }
+}
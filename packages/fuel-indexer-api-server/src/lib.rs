@@ -1,6 +1,7 @@
 #![deny(unused_crate_dependencies)]
 
 pub mod api;
+pub(crate) mod cache;
 pub mod cli;
 pub(crate) mod commands;
 pub(crate) mod middleware;
@@ -1,14 +1,17 @@
 use crate::{
     api::{ApiError, ApiResult, HttpError},
+    cache::GraphqlCache,
     models::{Claims, SqlQuery, VerifySignatureRequest},
     sql::SqlQueryValidator,
 };
-use async_graphql::http::{playground_source, GraphQLPlaygroundConfig};
-use async_graphql_axum::GraphQLRequest;
+use async_graphql::http::{
+    playground_source, GraphQLPlaygroundConfig, ALL_WEBSOCKET_PROTOCOLS,
+};
+use async_graphql_axum::{GraphQLProtocol, GraphQLRequest, GraphQLWebSocket};
 use async_std::sync::{Arc, RwLock};
 use axum::{
     body::Body,
-    extract::{multipart::Multipart, Extension, Json, Path},
+    extract::{multipart::Multipart, Extension, Json, Path, WebSocketUpgrade},
     http::StatusCode,
     response::{IntoResponse, Response},
 };
@@ -18,18 +21,21 @@ use fuel_indexer_database::{
     types::{IndexerAsset, IndexerAssetType},
     IndexerConnectionPool,
 };
-use fuel_indexer_graphql::dynamic::{build_dynamic_schema, execute_query};
+use fuel_indexer_graphql::{
+    dynamic::execute_query, federation::execute_federated_query, graphql::QueryLimits,
+    subscriptions::SubscriptionExecutor,
+};
 use fuel_indexer_lib::{
     config::{auth::AuthenticationStrategy, IndexerConfig},
     defaults,
     graphql::GraphQLSchema,
     utils::{
         FuelClientHealthResponse, ReloadRequest, ServiceRequest, ServiceStatus,
-        StopRequest,
+        ShadowRequest, StopRequest,
     },
     ExecutionSource,
 };
-use fuel_indexer_schema::db::manager::SchemaManager;
+use fuel_indexer_schema::db::{manager::SchemaManager, IndexerSchemaDbError};
 use hyper::Client;
 use hyper_rustls::HttpsConnectorBuilder;
 use jsonwebtoken::{encode, EncodingKey, Header};
@@ -50,8 +56,98 @@ pub(crate) async fn query_graph(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(pool): Extension<IndexerConnectionPool>,
     Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(cache): Extension<Arc<GraphqlCache>>,
+    Extension(config): Extension<IndexerConfig>,
     req: GraphQLRequest,
 ) -> ApiResult<axum::Json<Value>> {
+    let cached = cache
+        .schema(&pool, &manager, &namespace, &identifier)
+        .await?;
+
+    let req = req.into_inner();
+    let key = cache
+        .response_key(&pool, &namespace, &identifier, &req)
+        .await?;
+    if let Some(data) = match &key {
+        Some(key) => cached.response(key).await,
+        None => None,
+    } {
+        return Ok(axum::Json(data));
+    }
+
+    let user_query = req.query.clone();
+    let response = execute_query(
+        req,
+        cached.dynamic_schema.clone(),
+        user_query,
+        pool,
+        &cached.schema,
+        QueryLimits::from(&config.web_api),
+    )
+    .await?;
+    let data = serde_json::json!({ "data": response });
+
+    if let Some(key) = key {
+        cache.insert_response(&cached, key, data.clone()).await;
+    }
+
+    Ok(axum::Json(data))
+}
+
+/// Return the results for the given `GraphQLRequest`, whose root fields each select
+/// from one of the registered indexers.
+///
+/// Requests are validated against the federated schema of the registered indexers,
+/// which can also be introspected.
+pub(crate) async fn query_federated_graph(
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(cache): Extension<Arc<GraphqlCache>>,
+    Extension(config): Extension<IndexerConfig>,
+    req: GraphQLRequest,
+) -> ApiResult<axum::Json<Value>> {
+    let federated = cache.federated_schema(&pool, &manager).await?;
+    let schemas = federated
+        .indexers
+        .iter()
+        .map(|(name, cached)| (name.clone(), &cached.schema))
+        .collect();
+
+    let response = execute_federated_query(
+        req.into_inner(),
+        &federated.dynamic_schema,
+        &schemas,
+        &pool,
+        QueryLimits::from(&config.web_api),
+    )
+    .await?;
+
+    Ok(axum::Json(json!({ "data": response })))
+}
+
+/// Given an indexer namespace and identifier, serve GraphQL queries and subscriptions
+/// over a WebSocket, using the `graphql-ws` protocol.
+///
+/// Subscribers are told about the changes committed by the indexers running in this
+/// process, so subscriptions are only served alongside an indexer service.
+pub(crate) async fn subscribe_graph(
+    Path((namespace, identifier)): Path<(String, String)>,
+    Extension(tx): Extension<Sender<ServiceRequest>>,
+    Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(config): Extension<IndexerConfig>,
+    protocol: GraphQLProtocol,
+    upgrade: WebSocketUpgrade,
+) -> ApiResult<Response> {
+    // Without an indexer service receiving requests, no changes are ever committed
+    // in this process.
+    if tx.is_closed() {
+        return Err(ApiError::Http(HttpError::ServiceUnavailable(
+            "Subscriptions are only served by a web API running within the indexer service"
+                .to_string(),
+        )));
+    }
+
     match manager
         .read()
         .await
@@ -59,13 +155,14 @@ pub(crate) async fn query_graph(
         .await
     {
         Ok(schema) => {
-            let dynamic_schema = build_dynamic_schema(&schema)?;
-            let user_query = req.0.query.clone();
-            let response =
-                execute_query(req.into_inner(), dynamic_schema, user_query, pool, schema)
-                    .await?;
-            let data = serde_json::json!({ "data": response });
-            Ok(axum::Json(data))
+            let executor = SubscriptionExecutor::new(schema, pool)
+                .with_limits(QueryLimits::from(&config.web_api));
+            Ok(upgrade
+                .protocols(ALL_WEBSOCKET_PROTOCOLS)
+                .on_upgrade(move |stream| {
+                    GraphQLWebSocket::new(stream, executor, protocol).serve()
+                })
+                .into_response())
         }
         Err(_e) => Err(ApiError::Http(HttpError::NotFound(format!(
             "The graph '{namespace}.{identifier}' was not found."
@@ -156,6 +253,7 @@ pub(crate) async fn remove_indexer(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(tx): Extension<Sender<ServiceRequest>>,
     Extension(pool): Extension<IndexerConnectionPool>,
+    Extension(cache): Extension<Arc<GraphqlCache>>,
     Extension(claims): Extension<Claims>,
     Extension(config): Extension<IndexerConfig>,
 ) -> ApiResult<axum::Json<Value>> {
@@ -197,6 +295,8 @@ pub(crate) async fn remove_indexer(
 
     queries::commit_transaction(&mut conn).await?;
 
+    cache.invalidate(&namespace, &identifier).await;
+
     Ok(Json(json!({
         "success": "true"
     })))
@@ -204,10 +304,16 @@ pub(crate) async fn remove_indexer(
 
 /// Given an indexer namespace and identifier, register the indexer in the database, and
 /// send a `ServiceRequest::Reload` to the service for this indexer.
+///
+/// If the indexer already exists and its new schema can't be applied to the existing
+/// data, the new version is registered as a shadow indexer instead, and a
+/// `ServiceRequest::Shadow` is sent to the service.
+#[allow(clippy::too_many_arguments)]
 pub(crate) async fn register_indexer_assets(
     Path((namespace, identifier)): Path<(String, String)>,
     Extension(tx): Extension<Sender<ServiceRequest>>,
     Extension(schema_manager): Extension<Arc<RwLock<SchemaManager>>>,
+    Extension(cache): Extension<Arc<GraphqlCache>>,
     Extension(claims): Extension<Claims>,
     Extension(pool): Extension<IndexerConnectionPool>,
     Extension(config): Extension<IndexerConfig>,
@@ -239,9 +345,32 @@ pub(crate) async fn register_indexer_assets(
     .await;
 
     match result {
-        Ok(assets) => {
+        Ok(Deployment::Shadow) => {
+            queries::commit_transaction(&mut conn).await?;
+
+            cache.invalidate(&namespace, &identifier).await;
+
+            if let Err(e) = tx
+                .send(ServiceRequest::Shadow(ShadowRequest {
+                    namespace,
+                    identifier,
+                }))
+                .await
+            {
+                error!("Failed to send ServiceRequest::Shadow: {e:?}");
+                return Err(e.into());
+            }
+
+            Ok(Json(json!({
+                "success": "true",
+                "shadow": "true",
+            })))
+        }
+        Ok(Deployment::Live(assets)) => {
             queries::commit_transaction(&mut conn).await?;
 
+            cache.invalidate(&namespace, &identifier).await;
+
             if let Err(e) = tx
                 .send(ServiceRequest::Reload(ReloadRequest {
                     namespace,
@@ -265,6 +394,15 @@ pub(crate) async fn register_indexer_assets(
     }
 }
 
+/// How a deployed indexer was registered.
+enum Deployment {
+    /// The assets were registered, and replace the running version of the indexer.
+    Live(Vec<IndexerAsset>),
+
+    /// The assets were registered as a shadow indexer, to be synced in the background.
+    Shadow,
+}
+
 #[allow(clippy::too_many_arguments)]
 async fn register_indexer_assets_transaction(
     conn: &mut fuel_indexer_database::IndexerConnection,
@@ -275,41 +413,17 @@ async fn register_indexer_assets_transaction(
     pubkey: &str,
     replace_indexer: bool,
     asset_bytes: Vec<(IndexerAssetType, Vec<u8>)>,
-) -> ApiResult<Vec<IndexerAsset>> {
+) -> ApiResult<Deployment> {
     let mut assets: Vec<IndexerAsset> = Vec::new();
 
     let indexer_id = queries::get_indexer_id(conn, namespace, identifier).await;
 
-    // If the indexer already exists, check that replacing is enabled and that
-    // the schema has not changed.
-    if let Ok(indexer_id) = indexer_id {
-        if !replace_indexer {
-            return Err(ApiError::Http(HttpError::Conflict(format!(
-                "Indexer({namespace}.{identifier}) already exists. Use --replace-indexer to replace it."
-            ))));
-        }
-
-        for (asset_type, data) in asset_bytes.iter() {
-            if *asset_type == IndexerAssetType::Schema {
-                // The schema must be the same. This query returns an asset
-                // if the bytes match. If it returns None (and the indexer
-                // exists), it means that its schema is different.
-                let schema = GraphQLSchema::from(data.to_vec());
-                if queries::asset_already_exists(
-                    conn,
-                    &IndexerAssetType::Schema,
-                    &(&schema).into(),
-                    &indexer_id,
-                )
-                .await?
-                .is_none()
-                {
-                    return Err(ApiError::Http(HttpError::Conflict(format!(
-                            "Indexer({namespace}.{identifier})'s schema has changed. Use --replace-indexer --remove-data to replace the indexer and the indexed data."
-                        ))));
-                }
-            }
-        }
+    // If the indexer already exists, check that replacing is enabled. Changes to
+    // its schema are checked when the schema is registered below.
+    if indexer_id.is_ok() && !replace_indexer {
+        return Err(ApiError::Http(HttpError::Conflict(format!(
+            "Indexer({namespace}.{identifier}) already exists. Use --replace-indexer to replace it."
+        ))));
     }
 
     if !config.replace_indexer && replace_indexer {
@@ -319,6 +433,58 @@ async fn register_indexer_assets_transaction(
         ))));
     }
 
+    if indexer_id.is_ok() {
+        let asset = |t: IndexerAssetType| {
+            asset_bytes
+                .iter()
+                .find(|(asset_type, _)| *asset_type == t)
+                .map(|(_, data)| data.to_vec())
+        };
+
+        if let (Some(manifest), Some(schema), Some(wasm)) = (
+            asset(IndexerAssetType::Manifest),
+            asset(IndexerAssetType::Schema),
+            asset(IndexerAssetType::Wasm),
+        ) {
+            let schema = GraphQLSchema::from(schema);
+            let manager = schema_manager.read().await;
+
+            let diff = manager
+                .schema_diff(namespace, identifier, &schema, ExecutionSource::Wasm, conn)
+                .await?;
+
+            // Breaking changes are indexed into a shadow schema, which replaces the
+            // live schema once it has caught up.
+            if diff.map(|d| d.is_breaking()).unwrap_or(false) {
+                manager
+                    .new_shadow_schema(
+                        namespace,
+                        identifier,
+                        &schema,
+                        ExecutionSource::Wasm,
+                        conn,
+                    )
+                    .await?;
+
+                queries::register_shadow_indexer(
+                    conn,
+                    namespace,
+                    identifier,
+                    manifest,
+                    (&schema).into(),
+                    wasm,
+                    Some(pubkey),
+                )
+                .await?;
+
+                return Ok(Deployment::Shadow);
+            }
+        }
+
+        // A live deployment supersedes any shadow version still being synced.
+        queries::remove_shadow_indexer(conn, namespace, identifier).await?;
+    }
+
     for (asset_type, data) in asset_bytes.iter() {
         match asset_type {
             IndexerAssetType::Wasm | IndexerAssetType::Manifest => {
@@ -358,14 +524,22 @@ async fn register_indexer_assets_transaction(
                         ExecutionSource::Wasm,
                         conn,
                     )
-                    .await?;
+                    .await
+                    .map_err(|e| match e {
+                        IndexerSchemaDbError::BreakingSchemaChange(report) => {
+                            ApiError::Http(HttpError::Conflict(format!(
+                                "Indexer({namespace}.{identifier})'s schema has changed in ways that cannot be applied to its existing data:\n{report}\nUse --replace-indexer --remove-data to replace the indexer and the indexed data."
+                            )))
+                        }
+                        e => e.into(),
+                    })?;
 
                 assets.push(asset);
             }
         }
     }
 
-    Ok(assets)
+    Ok(Deployment::Live(assets))
 }
 
 // This function parses the `Multipart` struct set to the deploy indexer
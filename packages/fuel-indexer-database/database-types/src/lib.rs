@@ -7,16 +7,17 @@ use async_graphql_parser::{
     types::{FieldDefinition, ObjectType, TypeDefinition, TypeKind},
     Pos, Positioned,
 };
-use async_graphql_value::Name;
+use async_graphql_value::{ConstValue, Name};
 use chrono::{
     serde::ts_microseconds,
     {DateTime, Utc},
 };
 use fuel_indexer_lib::{
     graphql::{
-        extract_foreign_key_info, field_id, is_list_type,
-        types::{IdCol, ObjectCol},
-        JoinTableMeta, ParsedGraphQLSchema,
+        computed_field_sql, extract_foreign_key_info, field_default_value, field_id,
+        field_index_type, field_type_name, is_list_type,
+        types::{BlockHeightCol, BlockRangeCol, IdCol, ObjectCol, SearchCol},
+        JoinTableMeta, OnDuplicate, ParsedGraphQLSchema, SearchMeta,
     },
     MAX_ARRAY_LENGTH,
 };
@@ -26,6 +27,7 @@ use std::{
     collections::HashSet,
     fmt,
     fmt::Write,
+    str::FromStr,
     string::ToString,
     time::{SystemTime, UNIX_EPOCH},
 };
@@ -42,6 +44,18 @@ pub enum IndexMethod {
     /// SQL Hash index.
     #[strum(serialize = "hash")]
     Hash,
+
+    /// SQL GIN index.
+    #[strum(serialize = "gin")]
+    Gin,
+
+    /// SQL BRIN index.
+    #[strum(serialize = "brin")]
+    Brin,
+
+    /// SQL GIN index over the trigrams of a text column.
+    #[strum(serialize = "trigram")]
+    Trigram,
 }
 
 /// SQL database types used by indexers.
@@ -88,6 +102,8 @@ pub enum ColumnType {
     // primary key columns, so we need a version of `ID` that does not include
     // a primary key constraint.
     UID = 36,
+    UInt32 = 37,
+    BigDecimal = 38,
 }
 
 impl From<ColumnType> for i32 {
@@ -130,6 +146,8 @@ impl From<ColumnType> for i32 {
             ColumnType::BlockId => 34,
             ColumnType::Array => 35,
             ColumnType::UID => 36,
+            ColumnType::UInt32 => 37,
+            ColumnType::BigDecimal => 38,
         }
     }
 }
@@ -187,6 +205,8 @@ impl From<i32> for ColumnType {
             34 => ColumnType::BlockId,
             35 => ColumnType::Array,
             36 => ColumnType::UID,
+            37 => ColumnType::UInt32,
+            38 => ColumnType::BigDecimal,
             _ => unimplemented!("Invalid ColumnType: {num}."),
         }
     }
@@ -232,6 +252,8 @@ impl From<&str> for ColumnType {
             "BlockId" => ColumnType::BlockId,
             "Array" => ColumnType::Array,
             "UID" => ColumnType::UID,
+            "UInt32" => ColumnType::UInt32,
+            "BigDecimal" => ColumnType::BigDecimal,
             _ => unimplemented!("Invalid ColumnType: '{name}'."),
         }
     }
@@ -316,6 +338,12 @@ pub struct Column {
     ///
     /// Only if this is a `ColumnType::Array`
     pub array_coltype: Option<ColumnType>,
+
+    /// SQL literal of the column's default value, from a `@default` directive.
+    pub default: Option<String>,
+
+    /// SQL expression the column is generated from, if this is a `@computed` column.
+    pub computed: Option<String>,
 }
 
 impl SqlNamed for Column {
@@ -354,6 +382,9 @@ impl Column {
                     .iter()
                     .any(|d| d.node.name.to_string() == "unique");
 
+                let default = field_default_value(f)
+                    .map(|value| default_sql_literal(&value, &field_type_name(f)));
+
                 Self {
                     type_id,
                     name: f.name.to_string(),
@@ -363,6 +394,8 @@ impl Column {
                     unique,
                     nullable: f.ty.node.nullable,
                     persistence,
+                    default,
+                    computed: computed_field_sql(f),
                     ..Self::default()
                 }
             }
@@ -380,6 +413,7 @@ impl Column {
         match self.coltype {
             ColumnType::Address => "varchar(64)".to_string(),
             ColumnType::AssetId => "varchar(64)".to_string(),
+            ColumnType::BigDecimal => "numeric".to_string(),
             ColumnType::Blob => "varchar(10485760)".to_string(),
             ColumnType::BlockHeight => "integer".to_string(),
             ColumnType::BlockId => "varchar(64)".to_string(),
@@ -410,6 +444,7 @@ impl Column {
             ColumnType::TxId => "varchar(64)".to_string(),
             ColumnType::UInt1 => "integer".to_string(),
             ColumnType::UInt16 => "numeric(39, 0)".to_string(),
+            ColumnType::UInt32 => "numeric(78, 0)".to_string(),
             ColumnType::UInt4 => "integer".to_string(),
             ColumnType::UInt8 => "numeric(20, 0)".to_string(),
             ColumnType::Virtual => "json".to_string(),
@@ -427,6 +462,8 @@ impl Column {
                     ColumnType::Int8 => "bigint",
                     ColumnType::UInt8 => "numeric(20, 0)",
                     ColumnType::UInt16 | ColumnType::Int16 => "numeric(39, 0)",
+                    ColumnType::UInt32 => "numeric(78, 0)",
+                    ColumnType::BigDecimal => "numeric",
                     ColumnType::Address
                     | ColumnType::Bytes4
                     | ColumnType::Bytes8
@@ -461,16 +498,33 @@ impl SqlFragment for Column {
     fn create(&self) -> String {
         let null_frag = if self.nullable { "" } else { "not null" };
         let unique_frag = if self.unique { "unique" } else { "" };
-        format!(
-            "{} {} {} {}",
-            self.name,
+        let value_frag = match (&self.computed, &self.default) {
+            (Some(sql), _) => format!("GENERATED ALWAYS AS ({sql}) STORED"),
+            (None, Some(value)) => format!("default {value}"),
+            (None, None) => "".to_string(),
+        };
+        [
+            self.name.clone(),
             // Will only panic if given an array type
             self.sql_type(),
-            null_frag,
-            unique_frag
-        )
-        .trim()
-        .to_string()
+            value_frag,
+            null_frag.to_string(),
+            unique_frag.to_string(),
+        ]
+        .into_iter()
+        .filter(|frag| !frag.is_empty())
+        .collect::<Vec<String>>()
+        .join(" ")
+    }
+}
+
+/// Return the SQL literal for the `@default` value of a field of type `field_type`.
+fn default_sql_literal(value: &ConstValue, field_type: &str) -> String {
+    match value {
+        ConstValue::String(s) => format!("'{}'", s.replace('\'', "''")),
+        // Enum values are stored as `Enum::VALUE` strings.
+        ConstValue::Enum(v) => format!("'{field_type}::{v}'"),
+        other => other.to_string(),
     }
 }
 
@@ -632,6 +686,36 @@ impl RegisteredIndexer {
     }
 }
 
+/// A new version of an indexer that is syncing into a shadow schema, while the
+/// previous version of the indexer keeps serving queries.
+#[derive(Debug)]
+pub struct ShadowIndexer {
+    /// Namespace of the indexer.
+    pub namespace: String,
+
+    /// Identifier of the indexer.
+    pub identifier: String,
+
+    /// Manifest of the new version.
+    pub manifest: Vec<u8>,
+
+    /// GraphQL schema of the new version.
+    pub schema: Vec<u8>,
+
+    /// WASM module of the new version.
+    pub wasm: Vec<u8>,
+
+    /// Public key of the user that deployed the new version.
+    pub pubkey: Option<String>,
+}
+
+impl ShadowIndexer {
+    /// Return the unique identifier (UID) of the indexer.
+    pub fn uid(&self) -> String {
+        format!("{}.{}", self.namespace, self.identifier)
+    }
+}
+
 /// SQL database types used by indexers.
 #[derive(Eq, PartialEq, Debug, Clone, Default)]
 pub enum DbType {
@@ -709,14 +793,14 @@ pub struct SqlIndex {
     /// Whether this index is unique.
     pub unique: bool,
 
-    /// Name of column to which index is applied.
-    pub column_name: String,
+    /// Names of columns to which index is applied, in index order.
+    pub column_names: Vec<String>,
 }
 
 impl SqlNamed for SqlIndex {
     /// Return the SQL name of the index.
     fn sql_name(&self) -> String {
-        format!("{}_{}_idx", &self.table_name, &self.column_name)
+        format!("{}_{}_idx", &self.table_name, self.column_names.join("_"))
     }
 }
 
@@ -730,14 +814,24 @@ impl SqlFragment for SqlIndex {
 
         match self.db_type {
             DbType::Postgres => {
+                let (method, columns) = match self.method {
+                    IndexMethod::Trigram => (
+                        IndexMethod::Gin.as_ref(),
+                        self.column_names
+                            .iter()
+                            .map(|c| format!("{c} gin_trgm_ops"))
+                            .collect::<Vec<String>>(),
+                    ),
+                    _ => (self.method.as_ref(), self.column_names.clone()),
+                };
                 let _ = write!(
                     frag,
                     "INDEX {} ON {}.{} USING {} ({});",
                     self.sql_name(),
                     self.namespace,
                     self.table_name,
-                    self.method.as_ref(),
-                    self.column_name
+                    method,
+                    columns.join(", ")
                 );
             }
         }
@@ -746,6 +840,47 @@ impl SqlFragment for SqlIndex {
     }
 }
 
+/// SQL unique index on the `ID` column of the current version of each record
+/// in a versioned table.
+///
+/// Versioned tables keep a row per version of a record, so the `ID` column
+/// can't be a primary key. Instead, only one row per `ID` may have an unbounded
+/// block range.
+#[derive(Debug, Default, Eq, PartialEq)]
+pub struct BlockRangeIndex {
+    /// The type of database.
+    pub db_type: DbType,
+
+    /// Name of table on which constraint is applied.
+    pub table_name: String,
+
+    /// Fully qualified namespace of the indexer.
+    pub namespace: String,
+}
+
+impl SqlNamed for BlockRangeIndex {
+    /// Return the SQL name of the index.
+    fn sql_name(&self) -> String {
+        format!("{}__current_idx", &self.table_name)
+    }
+}
+
+impl SqlFragment for BlockRangeIndex {
+    /// Return the SQL create statement for a `BlockRangeIndex`.
+    fn create(&self) -> String {
+        match self.db_type {
+            DbType::Postgres => format!(
+                "CREATE UNIQUE INDEX {} ON {}.{} ({}) WHERE upper_inf({});",
+                self.sql_name(),
+                self.namespace,
+                self.table_name,
+                IdCol::to_lowercase_str(),
+                BlockRangeCol::to_lowercase_str(),
+            ),
+        }
+    }
+}
+
 /// On delete action for a FK constraint.
 #[derive(Debug, Clone, Copy, Default, EnumString, AsRefStr, Eq, PartialEq)]
 pub enum OnDelete {
@@ -782,6 +917,22 @@ pub enum Constraint {
 
     /// SQL primary key constraint.
     Pk(PrimaryKey),
+
+    /// SQL index on the current version of each record in a versioned table.
+    BlockRange(BlockRangeIndex),
+}
+
+impl Constraint {
+    /// Return the name of the database extension that the `Constraint` requires, if any.
+    pub fn extension(&self) -> Option<&'static str> {
+        match self {
+            Constraint::Index(SqlIndex {
+                method: IndexMethod::Trigram,
+                ..
+            }) => Some("pg_trgm"),
+            _ => None,
+        }
+    }
 }
 
 impl SqlFragment for Constraint {
@@ -791,6 +942,7 @@ impl SqlFragment for Constraint {
             Constraint::Index(idx) => idx.create(),
             Constraint::Fk(fk) => fk.create(),
             Constraint::Pk(pk) => pk.create(),
+            Constraint::BlockRange(idx) => idx.create(),
         }
     }
 }
@@ -911,6 +1063,20 @@ pub struct Table {
     /// How this typedef is persisted to the database.
     persistence: Persistence,
 
+    /// Whether this table keeps a row for each version of a record.
+    versioned: bool,
+
+    /// What happens when a duplicate record is saved, if this table is insert-only.
+    on_duplicate: Option<OnDuplicate>,
+
+    /// Full-text search configuration of this table, if any.
+    search: Option<SearchMeta>,
+
+    /// Columns generated by the database from `@computed` fields.
+    ///
+    /// These are never written by the indexer, so they're kept apart from `columns`.
+    computed_columns: Vec<Column>,
+
     /// The type of table.
     #[allow(unused)]
     table_type: TableType,
@@ -934,6 +1100,31 @@ impl Table {
         &self.columns
     }
 
+    /// Whether this table keeps a row for each version of a record.
+    pub fn is_versioned(&self) -> bool {
+        self.versioned
+    }
+
+    /// Whether this table is insert-only.
+    pub fn is_immutable(&self) -> bool {
+        self.on_duplicate.is_some()
+    }
+
+    /// What happens when a duplicate record is saved, if this table is insert-only.
+    pub fn on_duplicate(&self) -> Option<OnDuplicate> {
+        self.on_duplicate
+    }
+
+    /// Full-text search configuration of this table, if any.
+    pub fn search(&self) -> Option<&SearchMeta> {
+        self.search.as_ref()
+    }
+
+    /// Columns generated by the database from `@computed` fields.
+    pub fn computed_columns(&self) -> &Vec<Column> {
+        &self.computed_columns
+    }
+
     /// Create a new `Table` from a given `TypeDefinition`.
     pub fn from_typedef(typ: &TypeDefinition, parsed: &ParsedGraphQLSchema) -> Self {
         let ty_id = type_id(&parsed.fully_qualified_namespace(), &typ.name.to_string());
@@ -945,6 +1136,9 @@ impl Table {
                     Persistence::Scalar
                 };
 
+                let versioned = parsed.is_versioned_typedef(&typ.name.to_string());
+                let on_duplicate = parsed.on_duplicate(&typ.name.to_string());
+
                 let mut columns = o
                     .fields
                     .iter()
@@ -989,15 +1183,19 @@ impl Table {
                                 db_type: DbType::Postgres,
                                 table_name: typ.name.to_string().to_lowercase(),
                                 namespace: parsed.fully_qualified_namespace(),
+                                method: field_index_type(&f.node)
+                                    .and_then(|t| IndexMethod::from_str(&t.to_lowercase()).ok())
+                                    .unwrap_or_default(),
                                 unique: has_unique,
-                                column_name: f.node.name.to_string(),
-                                ..SqlIndex::default()
+                                column_names: vec![f.node.name.to_string()],
                             }));
                         }
 
 
                         let field_typ = f.node.ty.node.to_string().replace(['[', ']', '!'], "");
-                        if parsed.is_possible_foreign_key(&field_typ) {
+
+                        // Versioned tables have no primary key that could be referenced.
+                        if parsed.is_possible_foreign_key(&field_typ) && !parsed.is_versioned_typedef(&field_typ) {
                             let (ref_coltype, ref_colname, ref_tablename) =
                                 extract_foreign_key_info(
                                     &f.node,
@@ -1031,6 +1229,73 @@ impl Table {
                     ..Column::default()
                 });
 
+                if versioned {
+                    constraints.push(Constraint::BlockRange(BlockRangeIndex {
+                        db_type: DbType::Postgres,
+                        table_name: typ.name.to_string().to_lowercase(),
+                        namespace: parsed.fully_qualified_namespace(),
+                    }));
+                }
+
+                // Insert-only tables are written in block order, so a BRIN index on
+                // the block height stays small while still narrowing block range scans.
+                if on_duplicate.is_some() {
+                    constraints.push(Constraint::Index(SqlIndex {
+                        db_type: DbType::Postgres,
+                        table_name: typ.name.to_string().to_lowercase(),
+                        namespace: parsed.fully_qualified_namespace(),
+                        method: IndexMethod::Brin,
+                        unique: false,
+                        column_names: vec![BlockHeightCol::to_lowercase_string()],
+                    }));
+                }
+
+                constraints.extend(
+                    parsed
+                        .composite_indexes(&typ.name.to_string())
+                        .iter()
+                        .map(|meta| {
+                            Constraint::Index(SqlIndex {
+                                db_type: DbType::Postgres,
+                                table_name: typ.name.to_string().to_lowercase(),
+                                namespace: parsed.fully_qualified_namespace(),
+                                method: IndexMethod::from_str(&meta.method.to_lowercase())
+                                    .unwrap_or_default(),
+                                unique: meta.unique,
+                                column_names: meta.fields.clone(),
+                            })
+                        }),
+                );
+
+                let search = parsed.search_meta(&typ.name.to_string()).cloned();
+
+                let computed_columns = parsed
+                    .computed_fields(&typ.name.to_string())
+                    .iter()
+                    .enumerate()
+                    .map(|(i, name)| {
+                        let (f, _) = &parsed.field_defs()[&field_id(&typ.name.to_string(), name)];
+                        Column::from_field_def(
+                            f,
+                            parsed,
+                            ty_id,
+                            (columns.len() + i) as i32,
+                            persistence,
+                        )
+                    })
+                    .collect::<Vec<Column>>();
+
+                if search.is_some() {
+                    constraints.push(Constraint::Index(SqlIndex {
+                        db_type: DbType::Postgres,
+                        table_name: typ.name.to_string().to_lowercase(),
+                        namespace: parsed.fully_qualified_namespace(),
+                        method: IndexMethod::Gin,
+                        unique: false,
+                        column_names: vec![SearchCol::to_lowercase_string()],
+                    }));
+                }
+
                 Self {
                     // TODO: https://github.com/FuelLabs/fuel-indexer/issues/960
                     name: typ.name.to_string().to_lowercase(),
@@ -1039,6 +1304,10 @@ impl Table {
                     columns,
                     constraints,
                     persistence,
+                    versioned,
+                    on_duplicate,
+                    search,
+                    computed_columns,
                     table_type: TableType::Regular
                 }
             }
@@ -1151,7 +1420,7 @@ impl Table {
             },
         ];
 
-        let constraints = vec![
+        let mut constraints = vec![
             Constraint::Fk(ForeignKey {
                 db_type: DbType::Postgres,
                 namespace: parsed.fully_qualified_namespace(),
@@ -1198,11 +1467,11 @@ impl Table {
                 table_name: item.table_name(),
                 namespace: parsed.fully_qualified_namespace(),
                 unique: false,
-                column_name: format!(
+                column_names: vec![format!(
                     "{}_{}",
                     item.parent_table_name(),
                     item.parent_column_name()
-                ),
+                )],
                 ..SqlIndex::default()
             }),
             Constraint::Index(SqlIndex {
@@ -1210,15 +1479,24 @@ impl Table {
                 table_name: item.table_name(),
                 namespace: parsed.fully_qualified_namespace(),
                 unique: false,
-                column_name: format!(
+                column_names: vec![format!(
                     "{}_{}",
                     item.child_table_name(),
                     item.child_column_name()
-                ),
+                )],
                 ..SqlIndex::default()
             }),
         ];
 
+        // Versioned tables have no primary key that could be referenced.
+        constraints.retain(|c| match c {
+            Constraint::Fk(fk) => ![item.parent(), item.child()].iter().any(|r| {
+                parsed.is_versioned_typedef(&r.typedef_name)
+                    && r.typedef_name.to_lowercase() == fk.ref_tablename
+            }),
+            _ => true,
+        });
+
         Self {
             name: item.table_name(),
             namespace: parsed.namespace().to_string(),
@@ -1226,6 +1504,10 @@ impl Table {
             columns,
             constraints,
             persistence: Persistence::Scalar,
+            versioned: false,
+            on_duplicate: None,
+            search: None,
+            computed_columns: vec![],
             table_type: TableType::Join,
         }
     }
@@ -1240,12 +1522,48 @@ impl SqlFragment for Table {
                     "CREATE TABLE {}_{}.{} (\n",
                     self.namespace, self.identifier, self.name
                 );
-                let cols = self
+                let mut cols = self
                     .columns
                     .iter()
-                    .map(|c| c.create())
-                    .collect::<Vec<String>>()
-                    .join(",\n");
+                    .map(|c| {
+                        // The `ID` column of a versioned table is repeated for each version
+                        // of a record, so it can't be a primary key.
+                        if self.versioned && c.coltype == ColumnType::ID {
+                            format!("{} varchar(64) not null", c.name)
+                        } else {
+                            c.create()
+                        }
+                    })
+                    .collect::<Vec<String>>();
+                if self.versioned {
+                    cols.push(format!(
+                        "{} int8range not null",
+                        BlockRangeCol::to_lowercase_str()
+                    ));
+                }
+                if self.on_duplicate.is_some() {
+                    cols.push(format!(
+                        "{} integer not null",
+                        BlockHeightCol::to_lowercase_str()
+                    ));
+                }
+                if let Some(search) = &self.search {
+                    // The search document is kept up to date by Postgres, so it
+                    // never has to be written by the indexer.
+                    let document = search
+                        .fields
+                        .iter()
+                        .map(|f| format!("coalesce({f}, '')"))
+                        .collect::<Vec<String>>()
+                        .join(" || ' ' || ");
+                    cols.push(format!(
+                        "{} tsvector GENERATED ALWAYS AS (to_tsvector('{}'::regconfig, {document})) STORED",
+                        SearchCol::to_lowercase_str(),
+                        search.language
+                    ));
+                }
+                cols.extend(self.computed_columns.iter().map(|c| c.create()));
+                let cols = cols.join(",\n");
                 s.push_str(&cols);
                 // Remove last ',\n' from last column to avoid syntax error
                 let chars = s.chars();
@@ -1466,4 +1784,178 @@ type Wallet @entity {
             })
         );
     }
+
+    #[test]
+    fn test_can_create_searchable_table_from_typedef_with_search_directive() {
+        let schema = r#"
+type Post @entity @search(fields: ["title", "body"]) {
+    id: ID!
+    title: Charfield!
+    body: Blob
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Post").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+
+        assert!(table
+            .create()
+            .contains("_search tsvector GENERATED ALWAYS AS (to_tsvector('english'::regconfig, coalesce(title, '') || ' ' || coalesce(body, ''))) STORED"));
+        assert_eq!(
+            table.constraints()[0].create(),
+            "CREATE INDEX post__search_idx ON test_test.post USING gin (_search);"
+        );
+    }
+
+    #[test]
+    fn test_can_create_table_with_default_and_computed_columns_from_typedef() {
+        let schema = r#"
+enum Tier {
+    Basic
+    Premium
+}
+
+type Person @entity {
+    id: ID!
+    name: Charfield! @default(value: "O'Neil")
+    age: UInt1! @default(value: 18)
+    tier: Tier! @default(value: Basic)
+    initials: Charfield! @computed(sql: "left(name, 1)")
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Person").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+        let create = table.create();
+
+        assert!(create.contains("name varchar(255) default 'O''Neil' not null"));
+        assert!(create.contains("age integer default 18 not null"));
+        assert!(create.contains("tier varchar(255) default 'Tier::Basic' not null"));
+        assert!(create.contains(
+            "initials varchar(255) GENERATED ALWAYS AS (left(name, 1)) STORED not null"
+        ));
+
+        // Computed columns are never written by the indexer.
+        assert!(!table.columns().iter().any(|c| c.name == "initials"));
+        assert_eq!(table.computed_columns().len(), 1);
+    }
+
+    #[test]
+    fn test_can_create_immutable_table_from_typedef() {
+        let schema = r#"
+type Swap @entity @immutable(onDuplicate: Ignore) {
+    id: ID!
+    amount: UInt8!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Swap").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+
+        assert!(table.is_immutable());
+        assert_eq!(table.on_duplicate(), Some(OnDuplicate::Ignore));
+        assert!(table.create().contains("_block_height integer not null"));
+        assert!(table.constraints().iter().any(|c| c.create()
+            == "CREATE INDEX swap__block_height_idx ON test_test.swap USING brin (_block_height);"));
+    }
+
+    #[test]
+    fn test_can_create_composite_indexes_from_typedef_with_index_directives() {
+        let schema = r#"
+type Position @entity @index(fields: ["owner", "pool"]) @unique(fields: ["pool", "nonce"]) {
+    id: ID!
+    owner: Address!
+    pool: ContractId!
+    nonce: UInt8!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Position").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+
+        assert_eq!(
+            table
+                .constraints()
+                .iter()
+                .map(|c| c.create())
+                .collect::<Vec<String>>(),
+            vec![
+                "CREATE INDEX position_owner_pool_idx ON test_test.position USING btree (owner, pool);",
+                "CREATE UNIQUE INDEX position_pool_nonce_idx ON test_test.position USING btree (pool, nonce);",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_can_create_trigram_index_from_typedef_with_indexed_directive() {
+        let schema = r#"
+type Token @entity {
+    id: ID!
+    symbol: Charfield! @indexed(type: Trigram)
+    name: Charfield! @indexed(type: Hash)
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typ = schema.type_defs().get("Token").unwrap();
+        let table = Table::from_typedef(typ, &schema);
+
+        assert_eq!(
+            table
+                .constraints()
+                .iter()
+                .map(|c| (c.create(), c.extension()))
+                .collect::<Vec<(String, Option<&str>)>>(),
+            vec![
+                (
+                    "CREATE INDEX token_symbol_idx ON test_test.token USING gin (symbol gin_trgm_ops);".to_string(),
+                    Some("pg_trgm")
+                ),
+                (
+                    "CREATE INDEX token_name_idx ON test_test.token USING hash (name);"
+                        .to_string(),
+                    None
+                ),
+            ]
+        );
+    }
 }
@@ -0,0 +1,2 @@
+DROP TABLE IF EXISTS index_shadow_registry;
+DROP TABLE IF EXISTS index_retired_schemas;
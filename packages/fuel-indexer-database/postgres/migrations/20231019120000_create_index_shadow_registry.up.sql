@@ -0,0 +1,20 @@
+CREATE TABLE IF NOT EXISTS index_shadow_registry (
+   id bigserial primary key,
+   index_id bigserial,
+   manifest bytea not null,
+   schema bytea not null,
+   wasm bytea not null,
+   pubkey varchar(255),
+   UNIQUE(index_id),
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+	        deferrable initially deferred
+);
+
+CREATE TABLE IF NOT EXISTS index_retired_schemas (
+   id bigserial primary key,
+   schema_name varchar(255) not null unique,
+   drop_after timestamp not null
+);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS index_errors (
+   id bigserial primary key,
+   index_id bigserial,
+   error text not null,
+   created_at timestamp not null default now(),
+    constraint fk_index_registry_id
+        foreign key(index_id)
+            references index_registry(id)
+            on delete cascade
+	        deferrable initially deferred
+);
@@ -2,7 +2,10 @@
 
 use bigdecimal::ToPrimitive;
 use fuel_indexer_database_types::*;
-use fuel_indexer_lib::utils::sha256_digest;
+use fuel_indexer_lib::{
+    fully_qualified_namespace, graphql::types::BlockRangeCol, shadow_identifier,
+    utils::sha256_digest,
+};
 use sqlx::{pool::PoolConnection, postgres::PgRow, types::JsonValue, Postgres, Row};
 use std::str::FromStr;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -36,6 +39,24 @@ pub async fn put_object(
     Ok(result.rows_affected() as usize)
 }
 
+/// Insert several blobs of serialized `FtColumns` into the database with a single query.
+///
+/// Each blob is bound to the query's parameters in order.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn put_objects(
+    conn: &mut PoolConnection<Postgres>,
+    query: String,
+    objects: Vec<Vec<u8>>,
+) -> sqlx::Result<usize> {
+    let mut builder = sqlx::QueryBuilder::new(query);
+    let mut query = builder.build();
+    for bytes in objects {
+        query = query.bind(bytes);
+    }
+    let result = query.execute(conn).await?;
+    Ok(result.rows_affected() as usize)
+}
+
 /// Fetch a blob of serialized `FtColumn`s from the database.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn get_object(
@@ -192,6 +213,26 @@ pub async fn graph_root_latest(
     })
 }
 
+/// Return the version of the latest `GraphRoot` for a given indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn graph_root_latest_version(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<String> {
+    let row = sqlx::query(
+        "SELECT version FROM graph_registry_graph_root
+        WHERE schema_name = $1 AND schema_identifier = $2
+        ORDER BY id DESC LIMIT 1",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(row.get(0))
+}
+
 /// Return the set of `TypeIds` associated with the given indexer.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn type_id_list_by_name(
@@ -277,6 +318,28 @@ pub async fn type_id_insert(
     Ok(result.rows_affected() as usize)
 }
 
+/// Move the `TypeIds` of a given indexer to a new schema version.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn type_id_update_version(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    version: &str,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "UPDATE graph_registry_type_ids SET schema_version = $3
+        WHERE schema_name = $1
+        AND schema_identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(version)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
 /// Indicate whether or not a given schema has been persisted to the database.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn schema_exists(
@@ -328,6 +391,21 @@ pub async fn new_column_insert(
     Ok(result.rows_affected() as usize)
 }
 
+/// Remove the `Columns` associated with a set of `TypeIds`.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn remove_columns_by_type_id(
+    conn: &mut PoolConnection<Postgres>,
+    type_ids: Vec<i64>,
+) -> sqlx::Result<usize> {
+    let result =
+        sqlx::query("DELETE FROM graph_registry_columns WHERE type_id = ANY($1)")
+            .bind(type_ids)
+            .execute(conn)
+            .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
 /// Return the set of `Columns` associated with a given `TypeId`.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn list_column_by_id(
@@ -364,6 +442,7 @@ pub async fn list_column_by_id(
                     persistence: Persistence::from_str(persistence.as_str())
                         .expect("Bad persistence."),
                     array_coltype: array_coltype.map(|t| ColumnType::from(t.as_str())),
+                    ..Column::default()
                 }
             })
             .collect::<Vec<Column>>(),
@@ -652,6 +731,42 @@ pub async fn last_block_height_for_indexer(
         .unwrap_or_else(|_e| 1))
 }
 
+/// Return the last block height that the given indexer has committed, if any.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn last_committed_block_height(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<u32>> {
+    let query = format!(
+        "SELECT MAX(block_height) FROM {namespace}_{identifier}.indexmetadataentity"
+    );
+
+    let row = sqlx::query(&query).fetch_one(conn).await?;
+
+    Ok(row
+        .try_get::<Option<i32>, usize>(0)?
+        .map(|height| height.to_u32().expect("Bad block height.")))
+}
+
+/// Return the names of the tables in a schema that keep a row for each version of a record.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn versioned_table_names(
+    conn: &mut PoolConnection<Postgres>,
+    schema_name: &str,
+) -> sqlx::Result<Vec<String>> {
+    let rows = sqlx::query(
+        "SELECT table_name FROM information_schema.columns
+        WHERE table_schema = $1 AND column_name = $2",
+    )
+    .bind(schema_name)
+    .bind(BlockRangeCol::to_lowercase_str())
+    .fetch_all(conn)
+    .await?;
+
+    Ok(rows.iter().map(|row| row.get(0)).collect())
+}
+
 // TODO: https://github.com/FuelLabs/fuel-indexer/issues/251
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn asset_already_exists(
@@ -743,6 +858,8 @@ pub async fn remove_indexer(
     identifier: &str,
     remove_data: bool,
 ) -> sqlx::Result<()> {
+    remove_shadow_indexer(conn, namespace, identifier).await?;
+
     execute_query(
         conn,
         format!(
@@ -826,6 +943,271 @@ pub async fn remove_indexer(
     Ok(())
 }
 
+/// Register a new version of an indexer to be synced into a shadow schema.
+///
+/// An indexer has at most one shadow version, so this replaces any shadow
+/// version that was previously registered.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn register_shadow_indexer(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    manifest: Vec<u8>,
+    schema: Vec<u8>,
+    wasm: Vec<u8>,
+    pubkey: Option<&str>,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "INSERT INTO index_shadow_registry (index_id, manifest, schema, wasm, pubkey)
+        SELECT id, $3, $4, $5, $6 FROM index_registry
+        WHERE namespace = $1 AND identifier = $2
+        ON CONFLICT (index_id) DO UPDATE
+        SET manifest = $3, schema = $4, wasm = $5, pubkey = $6",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(manifest)
+    .bind(schema)
+    .bind(wasm)
+    .bind(pubkey)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+fn shadow_indexer_from_row(row: PgRow) -> ShadowIndexer {
+    ShadowIndexer {
+        namespace: row.get(0),
+        identifier: row.get(1),
+        manifest: row.get(2),
+        schema: row.get(3),
+        wasm: row.get(4),
+        pubkey: row.get(5),
+    }
+}
+
+/// Return the shadow version of the given indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn shadow_indexer(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<ShadowIndexer> {
+    let row = sqlx::query(
+        "SELECT r.namespace, r.identifier, s.manifest, s.schema, s.wasm, s.pubkey
+        FROM index_shadow_registry AS s
+        INNER JOIN index_registry AS r ON r.id = s.index_id
+        WHERE r.namespace = $1 AND r.identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .fetch_one(conn)
+    .await?;
+
+    Ok(shadow_indexer_from_row(row))
+}
+
+/// Return the shadow versions of all indexers.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn all_shadow_indexers(
+    conn: &mut PoolConnection<Postgres>,
+) -> sqlx::Result<Vec<ShadowIndexer>> {
+    Ok(sqlx::query(
+        "SELECT r.namespace, r.identifier, s.manifest, s.schema, s.wasm, s.pubkey
+        FROM index_shadow_registry AS s
+        INNER JOIN index_registry AS r ON r.id = s.index_id
+        ORDER BY s.id",
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(shadow_indexer_from_row)
+    .collect())
+}
+
+/// Remove the shadow version of the given indexer, along with its shadow schema.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn remove_shadow_indexer(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    let shadow_schema =
+        fully_qualified_namespace(namespace, &shadow_identifier(identifier));
+
+    execute_query(
+        conn,
+        format!("DROP SCHEMA IF EXISTS {shadow_schema} CASCADE"),
+    )
+    .await?;
+
+    sqlx::query(
+        "DELETE FROM index_shadow_registry WHERE index_id IN
+        (SELECT id FROM index_registry WHERE namespace = $1 AND identifier = $2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(conn)
+    .await?;
+
+    Ok(())
+}
+
+/// Remove the `TypeIds`, and their `Columns`, of the given indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn remove_type_ids(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<usize> {
+    sqlx::query(
+        "DELETE FROM graph_registry_columns WHERE type_id IN
+        (SELECT id FROM graph_registry_type_ids
+            WHERE schema_name = $1 AND schema_identifier = $2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(&mut *conn)
+    .await?;
+
+    let result = sqlx::query(
+        "DELETE FROM graph_registry_type_ids
+        WHERE schema_name = $1 AND schema_identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Remove the `GraphRoot` of the given version of an indexer's schema.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn remove_graph_root(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    version: &str,
+) -> sqlx::Result<usize> {
+    sqlx::query(
+        "DELETE FROM graph_registry_root_columns WHERE root_id IN
+        (SELECT id FROM graph_registry_graph_root
+            WHERE schema_name = $1 AND schema_identifier = $2 AND version = $3)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(version)
+    .execute(&mut *conn)
+    .await?;
+
+    let result = sqlx::query(
+        "DELETE FROM graph_registry_graph_root
+        WHERE schema_name = $1 AND schema_identifier = $2 AND version = $3",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(version)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Record an error that stopped the given indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn record_indexing_error(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+    error: &str,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "INSERT INTO index_errors (index_id, error)
+        SELECT id, $3 FROM index_registry WHERE namespace = $1 AND identifier = $2",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .bind(error)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Remove the errors recorded for the given indexer.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn clear_indexing_errors(
+    conn: &mut PoolConnection<Postgres>,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "DELETE FROM index_errors WHERE index_id IN
+        (SELECT id FROM index_registry WHERE namespace = $1 AND identifier = $2)",
+    )
+    .bind(namespace)
+    .bind(identifier)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Schedule a schema that is no longer used by any indexer to be dropped after
+/// the given grace period.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn retire_schema(
+    conn: &mut PoolConnection<Postgres>,
+    schema_name: &str,
+    grace_period_secs: u64,
+) -> sqlx::Result<usize> {
+    let result = sqlx::query(
+        "INSERT INTO index_retired_schemas (schema_name, drop_after)
+        VALUES ($1, now() + make_interval(secs => $2))",
+    )
+    .bind(schema_name)
+    .bind(grace_period_secs as f64)
+    .execute(conn)
+    .await?;
+
+    Ok(result.rows_affected() as usize)
+}
+
+/// Return the names of the retired schemas whose grace period has ended.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn expired_retired_schemas(
+    conn: &mut PoolConnection<Postgres>,
+) -> sqlx::Result<Vec<String>> {
+    Ok(sqlx::query(
+        "SELECT schema_name FROM index_retired_schemas
+        WHERE drop_after <= now()
+        ORDER BY id",
+    )
+    .fetch_all(conn)
+    .await?
+    .into_iter()
+    .map(|row| row.get(0))
+    .collect())
+}
+
+/// Drop a retired schema, along with all of its data.
+#[cfg_attr(feature = "metrics", metrics)]
+pub async fn drop_retired_schema(
+    conn: &mut PoolConnection<Postgres>,
+    schema_name: &str,
+) -> sqlx::Result<()> {
+    execute_query(conn, format!("DROP SCHEMA IF EXISTS {schema_name} CASCADE")).await?;
+
+    sqlx::query("DELETE FROM index_retired_schemas WHERE schema_name = $1")
+        .bind(schema_name)
+        .execute(conn)
+        .await?;
+
+    Ok(())
+}
+
 /// Create a new nonce for a requesting user's authentication.
 #[cfg_attr(feature = "metrics", metrics)]
 pub async fn create_nonce(conn: &mut PoolConnection<Postgres>) -> sqlx::Result<Nonce> {
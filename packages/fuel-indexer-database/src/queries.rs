@@ -18,6 +18,19 @@ pub async fn graph_root_latest(
     }
 }
 
+/// Return the version of the latest `GraphRoot` for a given indexer.
+pub async fn graph_root_latest_version(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<String> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::graph_root_latest_version(c, namespace, identifier).await
+        }
+    }
+}
+
 /// Persist a new `GraphRoot` to the database.
 pub async fn new_graph_root(
     conn: &mut IndexerConnection,
@@ -67,6 +80,20 @@ pub async fn type_id_insert(
     }
 }
 
+/// Move the `TypeIds` of a given indexer to a new schema version.
+pub async fn type_id_update_version(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    version: &str,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::type_id_update_version(c, namespace, identifier, version).await
+        }
+    }
+}
+
 /// Indicate whether or not a given schema has been persisted to the database.
 pub async fn schema_exists(
     conn: &mut IndexerConnection,
@@ -93,6 +120,18 @@ pub async fn new_column_insert(
     }
 }
 
+/// Remove the `Columns` associated with a set of `TypeIds`.
+pub async fn remove_columns_by_type_id(
+    conn: &mut IndexerConnection,
+    type_ids: Vec<i64>,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::remove_columns_by_type_id(c, type_ids).await
+        }
+    }
+}
+
 /// Return the set of `Columns` associated with a given `TypeId`.
 pub async fn list_column_by_id(
     conn: &mut IndexerConnection,
@@ -132,6 +171,19 @@ pub async fn put_object(
     }
 }
 
+/// Insert several blobs of serialized `FtColumns` into the database with a single query.
+pub async fn put_objects(
+    conn: &mut IndexerConnection,
+    query: String,
+    objects: Vec<Vec<u8>>,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::put_objects(c, query, objects).await
+        }
+    }
+}
+
 /// Fetch a blob of serialized `FtColumns` from the database.
 pub async fn get_object(
     conn: &mut IndexerConnection,
@@ -287,6 +339,31 @@ pub async fn last_block_height_for_indexer(
     }
 }
 
+/// Return the last block height that the given indexer has committed, if any.
+pub async fn last_committed_block_height(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<Option<u32>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::last_committed_block_height(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the names of the tables in a schema that keep a row for each version of a record.
+pub async fn versioned_table_names(
+    conn: &mut IndexerConnection,
+    schema_name: &str,
+) -> sqlx::Result<Vec<String>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::versioned_table_names(c, schema_name).await
+        }
+    }
+}
+
 pub async fn asset_already_exists(
     conn: &mut IndexerConnection,
     asset_type: &IndexerAssetType,
@@ -409,3 +486,149 @@ pub async fn put_many_to_many_record(
         }
     }
 }
+
+/// Register a new version of an indexer to be synced into a shadow schema.
+pub async fn register_shadow_indexer(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    manifest: Vec<u8>,
+    schema: Vec<u8>,
+    wasm: Vec<u8>,
+    pubkey: Option<&str>,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::register_shadow_indexer(
+                c, namespace, identifier, manifest, schema, wasm, pubkey,
+            )
+            .await
+        }
+    }
+}
+
+/// Return the shadow version of the given indexer.
+pub async fn shadow_indexer(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<ShadowIndexer> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::shadow_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Return the shadow versions of all indexers.
+pub async fn all_shadow_indexers(
+    conn: &mut IndexerConnection,
+) -> sqlx::Result<Vec<ShadowIndexer>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => postgres::all_shadow_indexers(c).await,
+    }
+}
+
+/// Remove the shadow version of the given indexer, along with its shadow schema.
+pub async fn remove_shadow_indexer(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::remove_shadow_indexer(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Remove the `TypeIds`, and their `Columns`, of the given indexer.
+pub async fn remove_type_ids(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::remove_type_ids(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Remove the `GraphRoot` of the given version of an indexer's schema.
+pub async fn remove_graph_root(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    version: &str,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::remove_graph_root(c, namespace, identifier, version).await
+        }
+    }
+}
+
+/// Record an error that stopped the given indexer.
+pub async fn record_indexing_error(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+    error: &str,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::record_indexing_error(c, namespace, identifier, error).await
+        }
+    }
+}
+
+/// Remove the errors recorded for the given indexer.
+pub async fn clear_indexing_errors(
+    conn: &mut IndexerConnection,
+    namespace: &str,
+    identifier: &str,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::clear_indexing_errors(c, namespace, identifier).await
+        }
+    }
+}
+
+/// Schedule a schema that is no longer used by any indexer to be dropped after
+/// the given grace period.
+pub async fn retire_schema(
+    conn: &mut IndexerConnection,
+    schema_name: &str,
+    grace_period_secs: u64,
+) -> sqlx::Result<usize> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::retire_schema(c, schema_name, grace_period_secs).await
+        }
+    }
+}
+
+/// Return the names of the retired schemas whose grace period has ended.
+pub async fn expired_retired_schemas(
+    conn: &mut IndexerConnection,
+) -> sqlx::Result<Vec<String>> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::expired_retired_schemas(c).await
+        }
+    }
+}
+
+/// Drop a retired schema, along with all of its data.
+pub async fn drop_retired_schema(
+    conn: &mut IndexerConnection,
+    schema_name: &str,
+) -> sqlx::Result<()> {
+    match conn {
+        IndexerConnection::Postgres(ref mut c) => {
+            postgres::drop_retired_schema(c, schema_name).await
+        }
+    }
+}
@@ -1,5 +1,9 @@
-use super::graphql::GraphqlError;
+use super::{dynamic::TEXT_SCALAR_TYPES, graphql::GraphqlError};
 use fuel_indexer_database::DbType;
+use fuel_indexer_lib::graphql::{
+    is_list_type,
+    types::{BlockRangeCol, IdCol, SearchCol},
+};
 use fuel_indexer_schema::db::tables::IndexerSchema;
 
 use async_graphql_value::{indexmap::IndexMap, Name, Value};
@@ -12,6 +16,41 @@ pub struct QueryParams {
     pub sorts: Vec<Sort>,
     pub offset: Option<u64>,
     pub limit: Option<u64>,
+
+    /// Number of results to return if neither a `limit` nor `last` is requested.
+    pub default_limit: Option<u64>,
+
+    /// Block height as of which versioned tables are queried. If not set, the
+    /// current version of each record is returned.
+    pub block: Option<u64>,
+
+    /// Fully qualified names of the versioned tables used in the query.
+    pub versioned_tables: Vec<String>,
+
+    /// Fully qualified names of the versioned tables that are left joined from a
+    /// nullable field, whose versions are selected in their join condition.
+    pub nullable_versioned_tables: Vec<String>,
+
+    /// Whether the results are returned as a Relay-style connection.
+    pub connection: bool,
+
+    /// Cursor of the result after which a connection's results start.
+    pub after: Option<Cursor>,
+
+    /// Cursor of the result before which a connection's results end.
+    pub before: Option<Cursor>,
+
+    /// Number of results to return from the end of a connection.
+    pub last: Option<u64>,
+
+    /// Whether the results are aggregated, rather than returned row by row.
+    pub aggregate: bool,
+
+    /// Fully qualified names of the columns by which aggregated results are grouped.
+    pub group_by: Vec<String>,
+
+    /// Whether the query reports the indexer's progress, rather than returning records.
+    pub meta: bool,
 }
 
 impl QueryParams {
@@ -36,6 +75,18 @@ impl QueryParams {
                 }),
                 ParamType::Offset(n) => self.offset = Some(n),
                 ParamType::Limit(n) => self.limit = Some(n),
+                ParamType::Block(n) => self.block = Some(n),
+                ParamType::Connection => self.connection = true,
+                ParamType::After(cursor) => self.after = Some(cursor),
+                ParamType::Before(cursor) => self.before = Some(cursor),
+                ParamType::Last(n) => self.last = Some(n),
+                ParamType::Aggregate => self.aggregate = true,
+                ParamType::Meta => self.meta = true,
+                ParamType::GroupBy(fields) => self.group_by.extend(
+                    fields
+                        .into_iter()
+                        .map(|field| format!("{fully_qualified_table_name}.{field}")),
+                ),
             }
         }
     }
@@ -44,11 +95,16 @@ impl QueryParams {
     pub(crate) fn get_filtering_expression(&self, db_type: &DbType) -> String {
         let mut query_clause = "".to_string();
 
-        if !self.filters.is_empty() {
+        if !self.filters.is_empty() || !self.versioned_tables.is_empty() {
             let where_expressions = self
                 .filters
                 .iter()
-                .map(|f| f.to_sql(db_type))
+                .map(|f| f.to_sql(self.block, db_type))
+                .chain(
+                    self.versioned_tables
+                        .iter()
+                        .map(|t| self.get_block_range_expression(t, db_type)),
+                )
                 .collect::<Vec<String>>()
                 .join(" AND ");
             query_clause =
@@ -58,6 +114,16 @@ impl QueryParams {
         query_clause
     }
 
+    /// Return a clause that selects the version of each record in a versioned table
+    /// that was current as of the query's block height.
+    pub(crate) fn get_block_range_expression(
+        &self,
+        fully_qualified_table: &str,
+        db_type: &DbType,
+    ) -> String {
+        block_range_expression(fully_qualified_table, self.block, db_type)
+    }
+
     /// Return a string comprised of modifiers to the order of the result set, if any.
     pub(crate) fn get_ordering_modififer(&self, db_type: &DbType) -> String {
         let mut query_clause = "".to_string();
@@ -80,6 +146,190 @@ impl QueryParams {
 
         query_clause
     }
+
+    /// Return a string that groups the result set of an aggregate query by the
+    /// requested columns, if any. Groups are returned in the order of those columns.
+    pub(crate) fn get_grouping_modifier(&self, db_type: &DbType) -> String {
+        match db_type {
+            DbType::Postgres => {
+                if self.group_by.is_empty() {
+                    "".to_string()
+                } else {
+                    let columns = self.group_by.join(", ");
+                    format!("GROUP BY {columns} ORDER BY {columns}")
+                }
+            }
+        }
+    }
+
+    /// Return the columns by which the results of a connection are ordered, along
+    /// with their sort order. The `ID` column comes last, so that each result has a
+    /// distinct position.
+    pub(crate) fn get_cursor_columns(&self, id_column: &str) -> Vec<(String, SortOrder)> {
+        let id_order = self
+            .sorts
+            .first()
+            .map(|s| s.order.clone())
+            .unwrap_or(SortOrder::Asc);
+
+        self.sorts
+            .iter()
+            .map(|s| (s.fully_qualified_table_name.clone(), s.order.clone()))
+            .chain(std::iter::once((id_column.to_string(), id_order)))
+            .collect()
+    }
+
+    /// Return a clause that selects the results of a connection that are between its
+    /// `after` and `before` cursors, if any.
+    pub(crate) fn get_keyset_expression(
+        &self,
+        columns: &[(String, SortOrder)],
+        db_type: &DbType,
+    ) -> Result<Option<String>, GraphqlError> {
+        let reversed = columns
+            .iter()
+            .map(|(col, order)| (col.clone(), order.reverse()))
+            .collect::<Vec<_>>();
+
+        let mut expressions = Vec::new();
+        if let Some(after) = &self.after {
+            expressions.push(after.to_sql(columns, db_type)?);
+        }
+        if let Some(before) = &self.before {
+            expressions.push(before.to_sql(&reversed, db_type)?);
+        }
+
+        if expressions.is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(expressions.join(" AND ")))
+        }
+    }
+}
+
+/// Return a clause that selects the version of each record in a versioned table
+/// that was current as of `block`, or the current version if `block` isn't set.
+fn block_range_expression(
+    fully_qualified_table: &str,
+    block: Option<u64>,
+    db_type: &DbType,
+) -> String {
+    let block_range = BlockRangeCol::to_lowercase_str();
+    match db_type {
+        DbType::Postgres => match block {
+            Some(height) => {
+                format!("{fully_qualified_table}.{block_range} @> {height}::bigint")
+            }
+            None => format!("upper_inf({fully_qualified_table}.{block_range})"),
+        },
+    }
+}
+
+/// An opaque position in the results of a connection.
+///
+/// A cursor holds the values of the sort columns of a result, followed by its `ID`,
+/// as a hex-encoded JSON array of strings. Since it doesn't depend on how many results
+/// come before it, a page that starts at a cursor stays put as new records are indexed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cursor {
+    /// The cursor as given in the query.
+    text: String,
+
+    /// Values of the sort columns of the result, followed by its `ID`.
+    values: Vec<Option<String>>,
+}
+
+impl Cursor {
+    /// Decode a cursor from a query argument.
+    pub fn decode(cursor: &str) -> Result<Cursor, GraphqlError> {
+        hex::decode(cursor)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .map(|values| Cursor {
+                text: cursor.to_string(),
+                values,
+            })
+            .ok_or_else(|| GraphqlError::InvalidCursor(cursor.to_string()))
+    }
+
+    /// Return a SQL expression that evaluates to the cursor of a result, given the
+    /// columns by which results are ordered.
+    pub(crate) fn sql_expression(
+        columns: &[(String, SortOrder)],
+        db_type: &DbType,
+    ) -> String {
+        match db_type {
+            DbType::Postgres => format!(
+                "encode(convert_to(json_build_array({})::text, 'UTF8'), 'hex')",
+                columns
+                    .iter()
+                    .map(|(col, _)| format!("{col}::text"))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
+        }
+    }
+
+    /// Return a clause that selects the results that come after this cursor, when
+    /// ordered by the given columns.
+    ///
+    /// Results are compared column by column, where `NULL`s come last in ascending
+    /// order and first in descending order, as they do in Postgres.
+    fn to_sql(
+        &self,
+        columns: &[(String, SortOrder)],
+        db_type: &DbType,
+    ) -> Result<String, GraphqlError> {
+        // A cursor from a query with a different order can't be used.
+        if self.values.len() != columns.len() {
+            return Err(GraphqlError::InvalidCursor(self.text.clone()));
+        }
+
+        let literal = |value: &Option<String>| {
+            value
+                .as_ref()
+                .map(|v| format!("'{}'", v.replace('\'', "''")))
+        };
+
+        match db_type {
+            DbType::Postgres => {
+                let mut alternatives = Vec::new();
+
+                for (i, ((col, order), value)) in
+                    columns.iter().zip(self.values.iter()).enumerate()
+                {
+                    let mut conditions = columns
+                        .iter()
+                        .zip(self.values.iter())
+                        .take(i)
+                        .map(|((col, _), value)| match literal(value) {
+                            Some(v) => format!("{col} = {v}"),
+                            None => format!("{col} IS NULL"),
+                        })
+                        .collect::<Vec<String>>();
+
+                    let follows = match (order, literal(value)) {
+                        (SortOrder::Asc, Some(v)) => {
+                            format!("({col} > {v} OR {col} IS NULL)")
+                        }
+                        // Nothing comes after `NULL`s in ascending order.
+                        (SortOrder::Asc, None) => continue,
+                        (SortOrder::Desc, Some(v)) => format!("{col} < {v}"),
+                        (SortOrder::Desc, None) => format!("{col} IS NOT NULL"),
+                    };
+                    conditions.push(follows);
+
+                    alternatives.push(format!("({})", conditions.join(" AND ")));
+                }
+
+                if alternatives.is_empty() {
+                    Ok("FALSE".to_string())
+                } else {
+                    Ok(format!("({})", alternatives.join(" OR ")))
+                }
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -89,9 +339,9 @@ pub struct Filter {
 }
 
 impl Filter {
-    pub fn to_sql(&self, db_type: &DbType) -> String {
+    pub fn to_sql(&self, block: Option<u64>, db_type: &DbType) -> String {
         self.filter_type
-            .to_sql(self.fully_qualified_table_name.clone(), db_type)
+            .to_sql(self.fully_qualified_table_name.clone(), block, db_type)
     }
 }
 
@@ -102,6 +352,14 @@ pub enum ParamType {
     Sort(String, SortOrder),
     Offset(u64),
     Limit(u64),
+    Block(u64),
+    Connection,
+    After(Cursor),
+    Before(Cursor),
+    Last(u64),
+    Aggregate,
+    GroupBy(Vec<String>),
+    Meta,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -116,6 +374,16 @@ pub enum SortOrder {
     Desc,
 }
 
+impl SortOrder {
+    /// Return the opposite sort order.
+    pub fn reverse(&self) -> SortOrder {
+        match self {
+            SortOrder::Asc => SortOrder::Desc,
+            SortOrder::Desc => SortOrder::Asc,
+        }
+    }
+}
+
 impl fmt::Display for SortOrder {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -128,7 +396,8 @@ impl fmt::Display for SortOrder {
 /// Represents the possible value types that the indexer's GraphQL API supports.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ParsedValue {
-    BigNumber(u128),
+    /// An arbitrary-precision number, validated to be a numeric literal.
+    BigNumber(String),
     Number(u64),
     String(String),
     Boolean(bool),
@@ -152,7 +421,7 @@ impl fmt::Display for ParsedValue {
                 write!(f, "{n}")
             }
             Self::String(s) => {
-                write!(f, "\'{s}\'")
+                write!(f, "\'{}\'", s.replace('\'', "''"))
             }
         }
     }
@@ -166,6 +435,8 @@ pub enum FilterType {
     Membership(Membership),
     NullValueCheck(NullValueCheck),
     LogicOp(LogicOp),
+    TextSearch(TextSearch),
+    Related(RelatedFilter),
 }
 
 /// Represents an operation in which a record is compared against a particular value.
@@ -178,6 +449,11 @@ pub enum Comparison {
     LessEqual(String, ParsedValue),
     Equals(String, ParsedValue),
     NotEquals(String, ParsedValue),
+    Contains(String, ParsedValue),
+    StartsWith(String, ParsedValue),
+    EndsWith(String, ParsedValue),
+    Like(String, ParsedValue),
+    ILike(String, ParsedValue),
 }
 
 /// Represents an operation in which a record's column value is checked for membership in a set.
@@ -194,6 +470,16 @@ pub enum NullValueCheck {
     OnlyNulls(Vec<String>),
 }
 
+/// Represents an operation in which records are matched against a full-text search query.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextSearch {
+    /// Text search configuration of the entity's search document.
+    pub language: String,
+
+    /// Search query, in web search syntax.
+    pub text: String,
+}
+
 /// Represents an operation in which filters are associated with one another and evaluated together.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum LogicOp {
@@ -202,9 +488,68 @@ pub enum LogicOp {
     Not(Box<FilterType>),
 }
 
+/// Represents an operation in which records are filtered by the records that they reference.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatedFilter {
+    /// Name of the field through which the records are referenced.
+    pub field: String,
+
+    /// How the referenced records are related to the filtered records.
+    pub relation: Relation,
+
+    /// Fully qualified name of the table of the referenced records.
+    pub table: String,
+
+    /// Column of the referenced table that the filtered records reference.
+    pub ref_column: String,
+
+    /// Whether the table of the referenced records is versioned.
+    pub versioned: bool,
+
+    /// How many of the referenced records have to match the filter.
+    pub quantifier: Quantifier,
+
+    /// Filter that is applied to the referenced records.
+    pub filter: Box<FilterType>,
+}
+
+/// Represents the way in which a record references other records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Relation {
+    /// The record's column references a single record.
+    ForeignKey,
+
+    /// The record references a list of records through a join table.
+    ManyToMany {
+        /// Fully qualified name of the join table.
+        join_table: String,
+
+        /// Column of the join table that references the filtered record.
+        parent_column: String,
+
+        /// Column of the join table that references the referenced record.
+        child_column: String,
+    },
+}
+
+/// Represents how many of the referenced records have to match a filter.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Quantifier {
+    Some,
+    Every,
+    None,
+}
+
 impl FilterType {
     /// Returns a string to be used as part of a SQL database query.
-    pub fn to_sql(&self, fully_qualified_table: String, db_type: &DbType) -> String {
+    ///
+    /// Versioned tables of referenced records are queried as of `block`, if set.
+    pub fn to_sql(
+        &self,
+        fully_qualified_table: String,
+        block: Option<u64>,
+        db_type: &DbType,
+    ) -> String {
         match db_type {
             DbType::Postgres => match self {
                 Self::Comparison(c) => match c {
@@ -229,25 +574,55 @@ impl FilterType {
                     Comparison::LessEqual(field, val) => {
                         format!("{fully_qualified_table}.{field} <= {val}",)
                     }
+                    // Wildcards in the value are matched literally, and the value is
+                    // then quoted like any other string. Fields that aren't stored as
+                    // text are matched against their text representation.
+                    Comparison::Contains(field, val) => format!(
+                        "{fully_qualified_table}.{field}::text LIKE {}",
+                        ParsedValue::String(format!("%{}%", escape_like_pattern(val)))
+                    ),
+                    Comparison::StartsWith(field, val) => format!(
+                        "{fully_qualified_table}.{field}::text LIKE {}",
+                        ParsedValue::String(format!("{}%", escape_like_pattern(val)))
+                    ),
+                    Comparison::EndsWith(field, val) => format!(
+                        "{fully_qualified_table}.{field}::text LIKE {}",
+                        ParsedValue::String(format!("%{}", escape_like_pattern(val)))
+                    ),
+                    Comparison::Like(field, val) => {
+                        format!("{fully_qualified_table}.{field}::text LIKE {val}")
+                    }
+                    Comparison::ILike(field, val) => {
+                        format!("{fully_qualified_table}.{field}::text ILIKE {val}")
+                    }
                 },
                 Self::IdSelection(id) => {
                     format!("{fully_qualified_table}.id = {id}")
                 }
+                Self::TextSearch(TextSearch { language, text }) => format!(
+                    "{fully_qualified_table}.{} @@ websearch_to_tsquery('{}', '{}')",
+                    SearchCol::to_lowercase_str(),
+                    language.replace('\'', "''"),
+                    text.replace('\'', "''")
+                ),
                 Self::LogicOp(lo) => match lo {
                     LogicOp::And(r1, r2) => format!(
                         "({} AND {})",
-                        r1.to_sql(fully_qualified_table.clone(), db_type),
-                        r2.to_sql(fully_qualified_table, db_type)
+                        r1.to_sql(fully_qualified_table.clone(), block, db_type),
+                        r2.to_sql(fully_qualified_table, block, db_type)
                     ),
                     LogicOp::Or(r1, r2) => format!(
                         "({} OR {})",
-                        r1.to_sql(fully_qualified_table.clone(), db_type),
-                        r2.to_sql(fully_qualified_table, db_type)
+                        r1.to_sql(fully_qualified_table.clone(), block, db_type),
+                        r2.to_sql(fully_qualified_table, block, db_type)
                     ),
-                    // The NOT logical operator does not get turned into a string as
-                    // it will have already been used to transform a filter into its
-                    // inverse equivalent.
-                    _ => "".to_string(),
+                    // Filters without an inverse equivalent are negated as a whole.
+                    LogicOp::Not(r) => {
+                        format!(
+                            "NOT ({})",
+                            r.to_sql(fully_qualified_table, block, db_type)
+                        )
+                    }
                 },
                 Self::Membership(m) => match m {
                     Membership::In(field, member_set) => {
@@ -271,6 +646,71 @@ impl FilterType {
                         )
                     }
                 },
+                Self::Related(RelatedFilter {
+                    field,
+                    relation,
+                    table,
+                    ref_column,
+                    versioned,
+                    quantifier,
+                    filter,
+                }) => {
+                    // Referenced tables are aliased after the path of fields that leads
+                    // to them, so that nested filters don't shadow their parent's table.
+                    let parent = fully_qualified_table
+                        .rsplit('.')
+                        .next()
+                        .unwrap_or(&fully_qualified_table);
+                    let alias = format!("{parent}_{field}");
+
+                    let (from, mut conditions) = match relation {
+                        Relation::ForeignKey => (
+                            format!("{table} AS {alias}"),
+                            vec![format!(
+                                "{alias}.{ref_column} = {fully_qualified_table}.{field}"
+                            )],
+                        ),
+                        Relation::ManyToMany {
+                            join_table,
+                            parent_column,
+                            child_column,
+                        } => (
+                            format!(
+                                "{join_table} AS {alias}_join INNER JOIN {table} AS {alias} ON {alias}.{ref_column} = {alias}_join.{child_column}"
+                            ),
+                            vec![format!(
+                                "{alias}_join.{parent_column} = {fully_qualified_table}.{}",
+                                IdCol::to_lowercase_str()
+                            )],
+                        ),
+                    };
+
+                    if *versioned {
+                        conditions.push(block_range_expression(&alias, block, db_type));
+                    }
+
+                    let filter = filter.to_sql(alias, block, db_type);
+                    match quantifier {
+                        Quantifier::Some | Quantifier::None => {
+                            conditions.push(format!("({filter})"))
+                        }
+                        // Every referenced record matches if none of them fail to match.
+                        Quantifier::Every => {
+                            conditions.push(format!("({filter}) IS NOT TRUE"))
+                        }
+                    }
+
+                    let subquery = format!(
+                        "SELECT 1 FROM {from} WHERE {}",
+                        conditions.join(" AND ")
+                    );
+                    match quantifier {
+                        Quantifier::Some => format!("EXISTS ({subquery})"),
+                        Quantifier::Every | Quantifier::None => {
+                            format!("NOT EXISTS ({subquery})")
+                        }
+                    }
+                }
                 Self::NullValueCheck(nvc) => match nvc {
                     NullValueCheck::NoNulls(column_list) => {
                         return column_list
@@ -295,6 +735,19 @@ impl FilterType {
 }
 
 impl FilterType {
+    /// Return the number of related entities that are joined in order to apply the filter.
+    pub(crate) fn joins(&self) -> usize {
+        match self {
+            FilterType::Related(related) => 1 + related.filter.joins(),
+            FilterType::LogicOp(LogicOp::And(left, right))
+            | FilterType::LogicOp(LogicOp::Or(left, right)) => {
+                left.joins() + right.joins()
+            }
+            FilterType::LogicOp(LogicOp::Not(filter)) => filter.joins(),
+            _ => 0,
+        }
+    }
+
     /// Invert a filter into its opposite filter.
     ///
     /// Each filter should have a inverse type when inverted in order to minimize
@@ -305,6 +758,24 @@ impl FilterType {
             FilterType::IdSelection(_) => Err(GraphqlError::UnsupportedNegation(
                 "ID selection".to_string(),
             )),
+            FilterType::TextSearch(_) => Err(GraphqlError::UnsupportedNegation(
+                "full-text search".to_string(),
+            )),
+            FilterType::Related(related) => {
+                let (quantifier, filter) = match related.quantifier {
+                    Quantifier::Some => (Quantifier::None, related.filter.clone()),
+                    Quantifier::None => (Quantifier::Some, related.filter.clone()),
+                    // Not every referenced record matches if some of them don't match.
+                    Quantifier::Every => {
+                        (Quantifier::Some, Box::new(related.filter.invert()?))
+                    }
+                };
+                Ok(FilterType::Related(RelatedFilter {
+                    quantifier,
+                    filter,
+                    ..related.clone()
+                }))
+            }
             FilterType::Comparison(c) => match c {
                 Comparison::Between(field, val1, val2) => {
                     Ok(FilterType::LogicOp(LogicOp::And(
@@ -336,6 +807,13 @@ impl FilterType {
                 Comparison::NotEquals(field, val) => Ok(FilterType::Comparison(
                     Comparison::Equals(field.clone(), val.clone()),
                 )),
+                Comparison::Contains(..)
+                | Comparison::StartsWith(..)
+                | Comparison::EndsWith(..)
+                | Comparison::Like(..)
+                | Comparison::ILike(..) => {
+                    Ok(FilterType::LogicOp(LogicOp::Not(Box::new(self.clone()))))
+                }
             },
             FilterType::Membership(mf) => match mf {
                 Membership::In(field, element_list) => Ok(FilterType::Membership(
@@ -436,6 +914,60 @@ pub fn parse_argument_into_param(
                 Err(GraphqlError::UnsupportedValueType(value.to_string()))
             }
         }
+        "block" => {
+            // Only versioned entities keep the state of their records at each block height.
+            let versioned = entity_type
+                .map(|e| schema.parsed().is_versioned_typedef(e))
+                .unwrap_or(false);
+            if !versioned {
+                return Err(GraphqlError::UnrecognizedArgument(
+                    entity_type
+                        .cloned()
+                        .unwrap_or("root level object".to_string()),
+                    arg.to_string(),
+                ));
+            }
+
+            if let Value::Object(obj) = value {
+                match obj.get("number") {
+                    Some(Value::Number(number)) => match number.as_u64() {
+                        Some(height) => Ok(ParamType::Block(height)),
+                        None => {
+                            Err(GraphqlError::UnsupportedValueType(number.to_string()))
+                        }
+                    },
+                    Some(other) => {
+                        Err(GraphqlError::UnsupportedValueType(other.to_string()))
+                    }
+                    None => Err(GraphqlError::NoPredicatesInFilter),
+                }
+            } else {
+                Err(GraphqlError::UnsupportedValueType(value.to_string()))
+            }
+        }
+        "search" => {
+            // Only entities with a `@search` directive have a search document.
+            let meta = match entity_type.and_then(|e| schema.parsed().search_meta(e)) {
+                Some(meta) => meta,
+                None => {
+                    return Err(GraphqlError::UnrecognizedArgument(
+                        entity_type
+                            .cloned()
+                            .unwrap_or("root level object".to_string()),
+                        arg.to_string(),
+                    ))
+                }
+            };
+
+            if let Value::String(text) = value {
+                Ok(ParamType::Filter(FilterType::TextSearch(TextSearch {
+                    language: meta.language.clone(),
+                    text,
+                })))
+            } else {
+                Err(GraphqlError::UnsupportedValueType(value.to_string()))
+            }
+        }
         "offset" => {
             if let Value::Number(number) = value {
                 if let Some(offset) = number.as_u64() {
@@ -447,6 +979,65 @@ pub fn parse_argument_into_param(
                 Err(GraphqlError::UnsupportedValueType(value.to_string()))
             }
         }
+        "after" | "before" => {
+            if let Value::String(cursor) = value {
+                let cursor = Cursor::decode(&cursor)?;
+                if arg == "after" {
+                    Ok(ParamType::After(cursor))
+                } else {
+                    Ok(ParamType::Before(cursor))
+                }
+            } else {
+                Err(GraphqlError::UnsupportedValueType(value.to_string()))
+            }
+        }
+        "last" => {
+            if let Value::Number(number) = value {
+                if let Some(last) = number.as_u64() {
+                    Ok(ParamType::Last(last))
+                } else {
+                    Err(GraphqlError::UnsupportedValueType(number.to_string()))
+                }
+            } else {
+                Err(GraphqlError::UnsupportedValueType(value.to_string()))
+            }
+        }
+        "groupBy" => {
+            // A single field may be passed in place of a list of fields.
+            let fields = match value {
+                Value::List(fields) => fields,
+                value @ Value::Enum(_) => vec![value],
+                other => {
+                    return Err(GraphqlError::UnsupportedValueType(other.to_string()))
+                }
+            };
+
+            let entity = entity_type.cloned().unwrap_or_default();
+            fields
+                .into_iter()
+                .map(|field| match field {
+                    Value::Enum(field) => {
+                        // Derived fields have no column to group by.
+                        let fid = format!("{entity}.{field}");
+                        if schema
+                            .parsed()
+                            .graphql_type(entity_type, field.as_str())
+                            .is_none()
+                            || schema.parsed().is_derived_field(&fid)
+                        {
+                            Err(GraphqlError::UnrecognizedField(
+                                entity.clone(),
+                                field.to_string(),
+                            ))
+                        } else {
+                            Ok(field.to_string())
+                        }
+                    }
+                    other => Err(GraphqlError::UnsupportedValueType(other.to_string())),
+                })
+                .collect::<Result<Vec<String>, GraphqlError>>()
+                .map(ParamType::GroupBy)
+        }
         "first" => {
             if let Value::Number(number) = value {
                 if let Some(limit) = number.as_u64() {
@@ -570,7 +1161,16 @@ fn parse_arg_pred_pair(
             }
         }
         other => {
-            if schema.parsed().graphql_type(entity_type, other).is_some() {
+            if let Value::Object(inner_obj) = &predicate {
+                if let Some(filter) =
+                    parse_related_filter(other, inner_obj, entity_type, schema)?
+                {
+                    return Ok(filter);
+                }
+            }
+
+            if let Some(field_type) = schema.parsed().graphql_type(entity_type, other) {
+                let parse_value = |value| parse_field_value(value, field_type);
                 if let Value::Object(inner_obj) = predicate {
                     for (key, predicate) in inner_obj.iter() {
                         match key.as_str() {
@@ -624,11 +1224,38 @@ fn parse_arg_pred_pair(
                                     parse_value(predicate)?,
                                 )))
                             }
+                            "contains" | "startsWith" | "endsWith" | "like" | "ilike" => {
+                                // Only fields stored as text can be matched against a pattern.
+                                if !TEXT_SCALAR_TYPES.contains(field_type.as_str()) {
+                                    return Err(
+                                        GraphqlError::UnsupportedFilterOperation(
+                                            key.to_string(),
+                                        ),
+                                    );
+                                }
+
+                                let value = match predicate {
+                                    Value::String(s) => ParsedValue::String(s.clone()),
+                                    value => {
+                                        return Err(GraphqlError::UnsupportedValueType(
+                                            value.to_string(),
+                                        ))
+                                    }
+                                };
+                                let field = other.to_string();
+                                return Ok(FilterType::Comparison(match key.as_str() {
+                                    "contains" => Comparison::Contains(field, value),
+                                    "startsWith" => Comparison::StartsWith(field, value),
+                                    "endsWith" => Comparison::EndsWith(field, value),
+                                    "like" => Comparison::Like(field, value),
+                                    _ => Comparison::ILike(field, value),
+                                }));
+                            }
                             "in" => {
                                 if let Value::List(elements) = predicate {
                                     let parsed_elements = elements
                                             .iter()
-                                            .map(parse_value)
+                                            .map(&parse_value)
                                             .collect::<Result<Vec<ParsedValue>,GraphqlError>>();
                                     if let Ok(elements) = parsed_elements {
                                         return Ok(FilterType::Membership(
@@ -659,6 +1286,132 @@ fn parse_arg_pred_pair(
     }
 }
 
+/// Operators that compare a field's column against a value.
+const COMPARISON_OPERATORS: [&str; 12] = [
+    "between",
+    "equals",
+    "gt",
+    "gte",
+    "lt",
+    "lte",
+    "in",
+    "contains",
+    "startsWith",
+    "endsWith",
+    "like",
+    "ilike",
+];
+
+/// Parse a predicate on a foreign key field into a filter on the records that the
+/// field references.
+///
+/// Returns `None` if the field isn't a foreign key field, or if the predicate compares
+/// the field's column itself. Predicates on list fields have to state whether `some`,
+/// `every` or `none` of the referenced records match the inner filter.
+fn parse_related_filter(
+    field: &str,
+    predicate: &IndexMap<Name, Value>,
+    entity_type: Option<&String>,
+    schema: &IndexerSchema,
+) -> Result<Option<FilterType>, GraphqlError> {
+    let entity = match entity_type {
+        Some(entity) => entity,
+        None => return Ok(None),
+    };
+
+    let (ref_table, ref_column) = match schema
+        .parsed()
+        .foreign_key_mappings()
+        .get(&entity.to_lowercase())
+        .and_then(|fks| fks.get(field))
+    {
+        Some(fk) => fk.clone(),
+        None => return Ok(None),
+    };
+
+    if predicate
+        .keys()
+        .any(|key| COMPARISON_OPERATORS.contains(&key.as_str()))
+    {
+        return Ok(None);
+    }
+
+    let related_type = match schema.parsed().graphql_type(entity_type, field) {
+        Some(typ) => typ.clone(),
+        None => return Ok(None),
+    };
+    let namespace = schema.parsed().fully_qualified_namespace();
+
+    let is_list = schema
+        .parsed()
+        .field_defs()
+        .get(&format!("{entity}.{field}"))
+        .map(|(f, _)| is_list_type(f))
+        .unwrap_or(false);
+
+    let (relation, quantifier, predicate) = if is_list {
+        let meta = schema
+            .parsed()
+            .join_table_meta()
+            .get(entity)
+            .and_then(|metas| metas.iter().find(|m| m.child_table_name() == ref_table))
+            .ok_or(GraphqlError::UnrecognizedField(
+                entity.to_string(),
+                field.to_string(),
+            ))?;
+
+        let (key, inner) = predicate
+            .iter()
+            .next()
+            .ok_or(GraphqlError::NoPredicatesInFilter)?;
+        let quantifier = match key.as_str() {
+            "some" => Quantifier::Some,
+            "every" => Quantifier::Every,
+            "none" => Quantifier::None,
+            other => {
+                return Err(GraphqlError::UnsupportedFilterOperation(other.to_string()))
+            }
+        };
+
+        let inner = match inner {
+            Value::Object(inner) => inner.clone(),
+            other => return Err(GraphqlError::UnsupportedValueType(other.to_string())),
+        };
+
+        (
+            Relation::ManyToMany {
+                join_table: format!("{namespace}.{}", meta.table_name()),
+                parent_column: format!(
+                    "{}_{}",
+                    meta.parent_table_name(),
+                    meta.parent_column_name()
+                ),
+                child_column: format!(
+                    "{}_{}",
+                    meta.child_table_name(),
+                    meta.child_column_name()
+                ),
+            },
+            quantifier,
+            inner,
+        )
+    } else {
+        (Relation::ForeignKey, Quantifier::Some, predicate.clone())
+    };
+
+    let filter = parse_filter_object(predicate, Some(&related_type), schema, &mut None)?;
+
+    Ok(Some(FilterType::Related(RelatedFilter {
+        field: field.to_string(),
+        relation,
+        table: format!("{namespace}.{ref_table}"),
+        ref_column,
+        versioned: schema.parsed().is_versioned_typedef(&related_type),
+        quantifier,
+        filter: Box::new(filter),
+    })))
+}
+
 /// Parse logical operators that operate on two components.
 ///
 /// `parse_binary_logical_operator` is a special parsing operation that
@@ -739,6 +1492,59 @@ fn parse_binary_logical_operator(
     }
 }
 
+/// Scalar types stored as arbitrary-precision numbers. Values for these types can be
+/// given as strings, since they may be out of the range of a GraphQL `Int`.
+const BIG_NUMBER_SCALAR_TYPES: [&str; 4] = ["BigDecimal", "Int16", "UInt16", "UInt32"];
+
+/// Escape the wildcards of a value, so that it's matched literally in a `LIKE` pattern.
+fn escape_like_pattern(value: &ParsedValue) -> String {
+    let value = match value {
+        ParsedValue::String(s) => s.clone(),
+        other => other.to_string(),
+    };
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Parse a value from the parsed GraphQL document into a `ParsedValue` that is
+/// compared against a field of type `field_type`.
+fn parse_field_value(
+    value: &Value,
+    field_type: &str,
+) -> Result<ParsedValue, GraphqlError> {
+    if !BIG_NUMBER_SCALAR_TYPES.contains(&field_type) {
+        return parse_value(value);
+    }
+
+    let number = match value {
+        Value::Number(n) => n.to_string(),
+        Value::String(s) => s.trim().to_string(),
+        _ => return Err(GraphqlError::UnsupportedValueType(value.to_string())),
+    };
+
+    if is_numeric_literal(&number) {
+        Ok(ParsedValue::BigNumber(number))
+    } else {
+        Err(GraphqlError::UnableToParseValue(number))
+    }
+}
+
+/// Whether `s` is a decimal number, optionally signed and in scientific notation.
+fn is_numeric_literal(s: &str) -> bool {
+    let s = s.strip_prefix('-').unwrap_or(s);
+    let (mantissa, exponent) = match s.split_once(['e', 'E']) {
+        Some((m, e)) => (m, Some(e.strip_prefix(['+', '-']).unwrap_or(e))),
+        None => (s, None),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, "0"));
+
+    let is_digits =
+        |part: &str| !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit());
+    is_digits(integer) && is_digits(fraction) && exponent.map_or(true, is_digits)
+}
+
 /// Parse a value from the parsed GraphQL document into a `ParsedValue` for use in the indexer.
 ///
 /// Value types from the parsed GraphQL query should be turned into `ParsedValue`
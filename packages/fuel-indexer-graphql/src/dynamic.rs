@@ -2,8 +2,8 @@ use std::collections::{HashMap, HashSet};
 
 use async_graphql::{
     dynamic::{
-        Enum, Field, FieldFuture, FieldValue, InputObject, InputValue, Object,
-        ResolverContext, Scalar, Schema as DynamicSchema,
+        Enum, Field, FieldFuture, FieldValue, InputObject, InputValue, Interface,
+        InterfaceField, Object, ResolverContext, Scalar, Schema as DynamicSchema,
         SchemaBuilder as DynamicSchemaBuilder, SchemaError, TypeRef,
     },
     Request,
@@ -11,11 +11,14 @@ use async_graphql::{
 use async_graphql_parser::types::{BaseType, Type};
 use async_graphql_value::Name;
 use fuel_indexer_database::{queries, IndexerConnectionPool};
+use fuel_indexer_lib::graphql::is_list_type;
 use fuel_indexer_schema::db::tables::IndexerSchema;
 use lazy_static::lazy_static;
 use serde_json::Value;
 
-use crate::graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult};
+use crate::graphql::{
+    GraphqlError, GraphqlQueryBuilder, GraphqlResult, QueryLimits, META_FIELD,
+};
 
 lazy_static! {
     /// Scalar types supported by the Fuel indexer. These should always stay up-to-date
@@ -23,6 +26,7 @@ lazy_static! {
     static ref SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
         "Address",
         "AssetId",
+        "BigDecimal",
         "Blob",
         "BlockHeight",
         "BlockId",
@@ -52,6 +56,7 @@ lazy_static! {
         "UID",
         "UInt1",
         "UInt16",
+        "UInt32",
         "UInt4",
         "UInt8",
         "Virtual",
@@ -59,22 +64,28 @@ lazy_static! {
 
     /// Scalar types that are represented by a numeric type. This ensures that the
     /// value type provided for a field filter matches the type of the scalar itself.
-    static ref NUMERIC_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
+    pub(crate) static ref NUMERIC_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
+        "BigDecimal",
         "Int16",
         "Int4",
         "Int8",
         "Timestamp",
         "Tai64Timestamp",
         "UInt16",
+        "UInt32",
         "UInt4",
         "UInt8",
     ]);
 
     /// Scalar types that are represented by a string type. This ensures that the
     /// value type provided for a field filter matches the type of the scalar itself.
-    static ref STRING_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
+    ///
+    /// `BigDecimal` and `UInt32` values don't fit in a GraphQL `Int`, so they are
+    /// passed as strings.
+    pub(crate) static ref STRING_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
         "Address",
         "AssetId",
+        "BigDecimal",
         "Blob",
         "BlockHeight",
         "BlockId",
@@ -94,6 +105,34 @@ lazy_static! {
         "Nonce",
         "Salt",
         "Signature",
+        "TxId",
+        "UID",
+        "UInt32",
+    ]);
+
+    /// Scalar types that are stored as text, and so can be matched against patterns.
+    ///
+    /// Unlike `STRING_SCALAR_TYPES`, this excludes numeric columns whose values are
+    /// only passed as strings.
+    pub(crate) static ref TEXT_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
+        "Address",
+        "AssetId",
+        "Blob",
+        "BlockId",
+        "Bytes32",
+        "Bytes4",
+        "Bytes64",
+        "Bytes8",
+        "Charfield",
+        "ContractId",
+        "HexString",
+        "ID",
+        "Identity",
+        "MessageId",
+        "Nonce",
+        "Salt",
+        "Signature",
+        "TxId",
         "UID",
     ]);
 
@@ -101,6 +140,7 @@ lazy_static! {
     static ref SORTABLE_SCALAR_TYPES: HashSet<&'static str> = HashSet::from([
         "Address",
         "AssetId",
+        "BigDecimal",
         "Charfield",
         "Color",
         "ContractId",
@@ -118,6 +158,7 @@ lazy_static! {
         "Timestamp",
         "UID",
         "UInt16",
+        "UInt32",
         "UInt4",
         "UInt8",
     ]);
@@ -135,13 +176,29 @@ lazy_static! {
         HashSet::from(["object"]);
 }
 
+/// Names of the types that are shared by the root fields of every indexer.
+const SORT_ORDER_TYPE: &str = "SortOrder";
+const BLOCK_FILTER_TYPE: &str = "BlockFilter";
+const PAGE_INFO_TYPE: &str = "PageInfo";
+const META_TYPE: &str = "_Meta";
+
+/// Scalar types that come with GraphQL itself.
+const BUILT_IN_SCALAR_TYPES: [&str; 5] = [
+    TypeRef::BOOLEAN,
+    TypeRef::FLOAT,
+    TypeRef::ID,
+    TypeRef::INT,
+    TypeRef::STRING,
+];
+
 /// Execute user query and return results.
 pub async fn execute_query(
     dynamic_request: Request,
     dynamic_schema: DynamicSchema,
     user_query: String,
     pool: IndexerConnectionPool,
-    schema: IndexerSchema,
+    schema: &IndexerSchema,
+    limits: QueryLimits,
 ) -> GraphqlResult<Value> {
     // Because the schema types from async-graphql expect each field to be resolved
     // separately, it became untenable to use the .execute() method of the dynamic
@@ -156,29 +213,126 @@ pub async fn execute_query(
             Ok(data)
         }
         Some(_) | None => {
-            let query =
-                GraphqlQueryBuilder::new(&schema, user_query.as_str())?.build()?;
+            let query = GraphqlQueryBuilder::new(schema, user_query.as_str())?
+                .with_limits(limits.clone())
+                .build()?;
 
-            let queries = query.as_sql(&schema, pool.database_type())?.join(";\n");
+            let queries = query.as_sql(schema, pool.database_type())?;
 
-            let mut conn = match pool.acquire().await {
-                Ok(c) => c,
-                Err(e) => return Err(GraphqlError::QueryError(e.to_string())),
-            };
+            Ok(Value::Array(
+                run_sql_queries(&pool, queries, limits.timeout).await?,
+            ))
+        }
+    }
+}
+
+/// Run the SQL queries of a user query, and return the results of all of them.
+///
+/// If a `timeout` is set, each query is cancelled after running for that many milliseconds.
+pub(crate) async fn run_sql_queries(
+    pool: &IndexerConnectionPool,
+    queries: Vec<String>,
+    timeout: Option<u64>,
+) -> GraphqlResult<Vec<Value>> {
+    let mut conn = match pool.acquire().await {
+        Ok(c) => c,
+        Err(e) => return Err(GraphqlError::QueryError(e.to_string())),
+    };
 
-            match queries::run_query(&mut conn, queries).await {
-                Ok(r) => Ok(r),
-                Err(e) => Err(GraphqlError::QueryError(e.to_string())),
+    // The timeout only lasts for the transaction, so that it isn't applied to
+    // whatever else the pooled connection is used for afterwards.
+    if let Some(timeout) = timeout {
+        queries::start_transaction(&mut conn)
+            .await
+            .map_err(|e| GraphqlError::QueryError(e.to_string()))?;
+        queries::execute_query(
+            &mut conn,
+            format!("SET LOCAL statement_timeout = {timeout}"),
+        )
+        .await
+        .map_err(|e| GraphqlError::QueryError(e.to_string()))?;
+    }
+
+    // A prepared statement can't contain multiple commands, so each query (e.g.
+    // one per implementor of an interface) is run separately.
+    let mut results = Vec::new();
+    let mut error = None;
+    for query in queries {
+        match queries::run_query(&mut conn, query).await {
+            Ok(Value::Array(rows)) => results.extend(rows),
+            Ok(r) => results.push(r),
+            Err(e) => {
+                // Postgres cancels statements that run past the timeout with `query_canceled`.
+                let code = e.as_database_error().and_then(|e| e.code());
+                error = Some(match (timeout, code) {
+                    (Some(timeout), Some(code)) if code == "57014" => {
+                        GraphqlError::QueryTimeout(timeout)
+                    }
+                    _ => GraphqlError::QueryError(e.to_string()),
+                });
+                break;
             }
         }
     }
+
+    if timeout.is_some() {
+        let ended = if error.is_none() {
+            queries::commit_transaction(&mut conn).await
+        } else {
+            queries::revert_transaction(&mut conn).await
+        };
+        ended.map_err(|e| GraphqlError::QueryError(e.to_string()))?;
+    }
+
+    match error {
+        Some(e) => Err(e),
+        None => Ok(results),
+    }
 }
 
 /// Build a dynamic schema. This allows for introspection, which allows for extensive
 /// auto-documentation and code suggestions.
 pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSchema> {
+    let (schema_builder, query_root) =
+        register_indexer_types(base_schema_builder(), schema, "QueryRoot", "")?;
+
+    Ok(schema_builder.register(query_root).finish()?)
+}
+
+/// Build a dynamic schema for federated queries, given the root field name and the
+/// schema of each indexer.
+///
+/// Each root field holds the root fields of one indexer. The types of an indexer are
+/// prefixed with the name of its root field, so that types of the same name from
+/// different indexers don't clash.
+pub fn build_federated_dynamic_schema<'a>(
+    indexers: impl IntoIterator<Item = (&'a str, &'a IndexerSchema)>,
+) -> GraphqlResult<DynamicSchema> {
+    let mut schema_builder = base_schema_builder();
+    let mut query_root = Object::new("QueryRoot");
+
+    for (name, schema) in indexers {
+        let (builder, indexer_root) = register_indexer_types(
+            schema_builder,
+            schema,
+            &format!("{name}_QueryRoot"),
+            &format!("{name}_"),
+        )?;
+        query_root = query_root.field(placeholder_field(
+            name,
+            TypeRef::named_nn(indexer_root.type_name()),
+        ));
+        schema_builder = builder.register(indexer_root);
+    }
+
+    Ok(schema_builder.register(query_root).finish()?)
+}
+
+/// Create a schema builder with the scalars and the types that are shared by the
+/// root fields of every indexer.
+fn base_schema_builder() -> DynamicSchemaBuilder {
     // Register scalars into dynamic schema so that users are aware of their existence.
-    let mut schema_builder: DynamicSchemaBuilder = SCALAR_TYPES.iter().fold(
+    let schema_builder: DynamicSchemaBuilder = SCALAR_TYPES.iter().fold(
         DynamicSchema::build("QueryRoot", None, None).introspection_only(),
         |sb, scalar| {
             // These types come pre-included in SchemaBuilder.
@@ -190,8 +344,88 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
         },
     );
 
+    let sort_enum = Enum::new(SORT_ORDER_TYPE).item("asc").item("desc");
+
+    // Versioned entities can be queried as of a given block height.
+    let block_filter = InputObject::new(BLOCK_FILTER_TYPE)
+        .field(InputValue::new("number", TypeRef::named_nn(TypeRef::INT)));
+
+    // Each page of a connection tells whether there are more results on either side
+    // of it, and where it starts and ends.
+    let page_info = Object::new(PAGE_INFO_TYPE)
+        .field(placeholder_field(
+            "hasNextPage",
+            TypeRef::named_nn(TypeRef::BOOLEAN),
+        ))
+        .field(placeholder_field(
+            "hasPreviousPage",
+            TypeRef::named_nn(TypeRef::BOOLEAN),
+        ))
+        .field(placeholder_field(
+            "startCursor",
+            TypeRef::named(TypeRef::STRING),
+        ))
+        .field(placeholder_field(
+            "endCursor",
+            TypeRef::named(TypeRef::STRING),
+        ));
+
+    // Every indexer reports its last committed block and its status in the registry.
+    let meta_block = Object::new("_MetaBlock")
+        .field(placeholder_field("height", TypeRef::named_nn("UInt4")))
+        .field(placeholder_field("id", TypeRef::named_nn("Bytes32")))
+        .field(placeholder_field("timestamp", TypeRef::named_nn("UInt8")));
+    let meta_indexer = Object::new("_MetaIndexer")
+        .field(placeholder_field(
+            "namespace",
+            TypeRef::named_nn(TypeRef::STRING),
+        ))
+        .field(placeholder_field(
+            "identifier",
+            TypeRef::named_nn(TypeRef::STRING),
+        ))
+        .field(placeholder_field(
+            "version",
+            TypeRef::named_nn(TypeRef::STRING),
+        ))
+        .field(placeholder_field(
+            "hasIndexingErrors",
+            TypeRef::named_nn(TypeRef::BOOLEAN),
+        ));
+    let meta = Object::new(META_TYPE)
+        .field(placeholder_field(
+            "block",
+            TypeRef::named(meta_block.type_name()),
+        ))
+        .field(placeholder_field(
+            "indexer",
+            TypeRef::named_nn(meta_indexer.type_name()),
+        ));
+
+    schema_builder
+        .register(sort_enum)
+        .register(block_filter)
+        .register(page_info)
+        .register(meta_block)
+        .register(meta_indexer)
+        .register(meta)
+}
+
+/// Register the types of an indexer, each prefixed with `type_prefix`, and return
+/// the object holding the root fields of the indexer, which is named `root_name`.
+fn register_indexer_types(
+    mut schema_builder: DynamicSchemaBuilder,
+    schema: &IndexerSchema,
+    root_name: &str,
+    type_prefix: &str,
+) -> GraphqlResult<(DynamicSchemaBuilder, Object)> {
+    let prefixed = |type_name: &str| prefixed_type_name(type_prefix, type_name);
+
     let mut input_objects = Vec::new();
 
+    // Names of the types whose lists of records have a filter object.
+    let mut list_filter_types = HashSet::new();
+
     // For some reason, async-graphql does not implement the Hash trait on any of the
     // type that we need for dynamic schemas. So we are essentially making a hash table
     // ourselves for the filter and sort objects.
@@ -203,9 +437,7 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
     // async-graphql requires a root query object so that the base entity
     // fields can be queried against. This QueryRoot does not appear anywhere
     // in the generated documentation nor is it required for the user to create.
-    let mut query_root = Object::new("QueryRoot");
-
-    let sort_enum = Enum::new("SortOrder").item("asc").item("desc");
+    let mut query_root = Object::new(root_name);
 
     for (entity_type, field_map) in schema.parsed().object_field_mappings() {
         if IGNORED_ENTITY_TYPES.contains(&entity_type.as_str()) {
@@ -219,28 +451,106 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
 
         // Field names will be added to this enum in order to allow
         // for filtering on the column itself, i.e. "has" operator.
-        let mut object_field_enum = Enum::new(format!("{entity_type}Fields"));
+        let mut object_field_enum = Enum::new(prefixed(&format!("{entity_type}Fields")));
+
+        // Aggregates of the entity return the values of the fields it's grouped by,
+        // and can compute functions over its numeric fields.
+        let mut aggregate_keys =
+            Object::new(prefixed(&format!("{entity_type}AggregateKeys")));
+        let mut aggregate_values =
+            Object::new(prefixed(&format!("{entity_type}AggregateValues")));
+        let mut aggregate_averages =
+            Object::new(prefixed(&format!("{entity_type}AggregateAverages")));
+        let mut has_numeric_fields = false;
 
         for (field_name, field_type) in field_map.clone() {
-            if IGNORED_ENTITY_FIELD_TYPES.contains(&field_name.as_str()) {
+            // Derived fields have no column, so they can't be filtered or sorted on.
+            if IGNORED_ENTITY_FIELD_TYPES.contains(&field_name.as_str())
+                || schema
+                    .parsed()
+                    .is_derived_field(&format!("{entity_type}.{field_name}"))
+            {
                 continue;
             }
 
+            let base_type = field_type.replace('!', "");
             let (field_filter_input_val, mut field_input_objects, sort_input_val) =
                 create_input_values_and_objects_for_field(
                     field_name.clone(),
                     field_type,
-                    entity_type.clone(),
-                    &sort_enum,
+                    prefixed(entity_type),
                 )?;
 
-            filter_input_vals.push(field_filter_input_val);
-            input_objects.append(&mut field_input_objects);
+            // Foreign key fields are filtered on the records that they reference,
+            // rather than on their own column.
+            let fid = format!("{entity_type}.{field_name}");
+            let is_foreign_key = schema
+                .parsed()
+                .foreign_key_mappings()
+                .get(&entity_type.to_lowercase())
+                .map(|fks| fks.contains_key(&field_name))
+                .unwrap_or(false);
+            if is_foreign_key {
+                let is_list = schema
+                    .parsed()
+                    .field_defs()
+                    .get(&fid)
+                    .map(|(def, _)| is_list_type(def))
+                    .unwrap_or(false);
+                let filter_type = if is_list {
+                    // Lists of references are filtered by whether some, every or
+                    // none of the referenced records match a filter.
+                    if list_filter_types.insert(base_type.clone()) {
+                        let related_filter = prefixed(&format!("{base_type}Filter"));
+                        input_objects.push(
+                            InputObject::new(prefixed(&format!("{base_type}ListFilter")))
+                                .field(InputValue::new(
+                                    "some",
+                                    TypeRef::named(&related_filter),
+                                ))
+                                .field(InputValue::new(
+                                    "every",
+                                    TypeRef::named(&related_filter),
+                                ))
+                                .field(InputValue::new(
+                                    "none",
+                                    TypeRef::named(&related_filter),
+                                )),
+                        );
+                    }
+                    prefixed(&format!("{base_type}ListFilter"))
+                } else {
+                    prefixed(&format!("{base_type}Filter"))
+                };
+                filter_input_vals
+                    .push(InputValue::new(&field_name, TypeRef::named(filter_type)));
+            } else {
+                filter_input_vals.push(field_filter_input_val);
+                input_objects.append(&mut field_input_objects);
+            }
 
             if let Some(input_val) = sort_input_val {
                 sort_input_vals.push(input_val);
             }
 
+            let key_type = if SCALAR_TYPES.contains(base_type.as_str()) {
+                base_type.clone()
+            } else {
+                TypeRef::STRING.to_string()
+            };
+            aggregate_keys = aggregate_keys
+                .field(placeholder_field(&field_name, TypeRef::named(key_type)));
+
+            if NUMERIC_SCALAR_TYPES.contains(base_type.as_str()) {
+                has_numeric_fields = true;
+                aggregate_values = aggregate_values
+                    .field(placeholder_field(&field_name, TypeRef::named(base_type)));
+                aggregate_averages = aggregate_averages.field(placeholder_field(
+                    &field_name,
+                    TypeRef::named(TypeRef::FLOAT),
+                ));
+            }
+
             object_field_enum = object_field_enum.item(field_name);
         }
 
@@ -248,7 +558,7 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             let filter_object = filter_input_vals
                 .into_iter()
                 .fold(
-                    InputObject::new(format!("{entity_type}Filter")),
+                    InputObject::new(prefixed(&format!("{entity_type}Filter"))),
                     |input_obj, input_val| input_obj.field(input_val),
                 )
                 .field(InputValue::new(
@@ -262,7 +572,7 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
 
         if !sort_input_vals.is_empty() {
             let sort_object = sort_input_vals.into_iter().fold(
-                InputObject::new(format!("{entity_type}Sort")),
+                InputObject::new(prefixed(&format!("{entity_type}Sort"))),
                 |input_obj, input_val| input_obj.field(input_val),
             );
 
@@ -275,11 +585,22 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
         // second time and construct the fields for the dynamic schema and add the
         // field arguments as well.
         let mut fields = Vec::new();
+        let mut interface_fields = Vec::new();
         for (field_name, field_type) in field_map {
             if IGNORED_ENTITY_FIELD_TYPES.contains(&field_name.as_str()) {
                 continue;
             }
 
+            // The field types of derived fields are cached without their list
+            // wrapper, so use the type from the field definition.
+            let fid = format!("{entity_type}.{field_name}");
+            let field_type = &match schema.parsed().field_defs().get(&fid) {
+                Some((def, _)) if schema.parsed().is_derived_field(&fid) => {
+                    def.ty.to_string()
+                }
+                _ => field_type.to_owned(),
+            };
+
             if let Some(field_def) = Type::new(field_type) {
                 let base_field_type = &field_def.base;
                 let nullable = field_def.nullable;
@@ -298,16 +619,16 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
                             if schema.parsed().is_virtual_typedef(field_type) {
                                 TypeRef::named(TypeRef::STRING)
                             } else {
-                                TypeRef::named(type_name.to_string())
+                                TypeRef::named(prefixed(type_name.as_str()))
                             }
                         } else if schema.parsed().is_virtual_typedef(field_type) {
                             TypeRef::named_nn(TypeRef::STRING)
                         } else {
-                            TypeRef::named_nn(type_name.to_string())
+                            TypeRef::named_nn(prefixed(type_name.as_str()))
                         }
                     }
                     BaseType::List(list_type) => {
-                        let inner_base_type = list_type.base.to_string();
+                        let inner_base_type = prefixed(&list_type.base.to_string());
                         let nullable_inner = list_type.nullable;
 
                         if nullable && nullable_inner {
@@ -322,6 +643,11 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
                     }
                 };
 
+                interface_fields.push(InterfaceField::new(
+                    field_name.to_string(),
+                    field_type.clone(),
+                ));
+
                 let field = create_field_with_assoc_args(
                     field_name.to_string(),
                     field_type,
@@ -336,25 +662,160 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
             }
         }
 
+        // Interfaces have no table of their own, so querying one returns the results of
+        // each implementor. Those can't be ordered or paginated as a whole.
+        if schema.parsed().is_interface_typedef(entity_type) {
+            let interface = interface_fields
+                .into_iter()
+                .fold(Interface::new(prefixed(entity_type)), |i, f| i.field(f));
+
+            let mut field = Field::new(
+                entity_type.to_lowercase(),
+                TypeRef::named(interface.type_name()),
+                move |_ctx: ResolverContext| {
+                    FieldFuture::new(async move { Ok(Some(FieldValue::value(1))) })
+                },
+            )
+            .argument(InputValue::new("id", TypeRef::named(TypeRef::STRING)));
+
+            if let Some(idx) = filter_tracker.get(entity_type) {
+                field = field.argument(InputValue::new(
+                    "filter",
+                    TypeRef::named(filter_object_list[*idx].type_name()),
+                ));
+            }
+
+            query_root = query_root.field(field);
+            schema_builder = schema_builder
+                .register(interface)
+                .register(object_field_enum);
+            continue;
+        }
+
         // Create object using all of the fields that were constructed for the entity
         // and repeat the same process in order to allow for introspection-related
         // functionality at the root query level.
         let obj = fields
             .into_iter()
-            .fold(Object::new(entity_type.clone()), |obj, f| obj.field(f));
+            .fold(Object::new(prefixed(entity_type)), |obj, f| obj.field(f));
+        let obj = match schema.parsed().objects().get(entity_type) {
+            Some(o) => o
+                .implements
+                .iter()
+                .fold(obj, |obj, i| obj.implement(prefixed(i.node.as_str()))),
+            None => obj,
+        };
 
         // Create field for entity object and add it to root level query object.
         let field = create_field_with_assoc_args(
             entity_type.to_string().to_lowercase(),
             TypeRef::named(obj.type_name()),
-            &BaseType::Named(Name::new(obj.type_name())),
+            &BaseType::Named(Name::new(entity_type)),
             &filter_tracker,
             &filter_object_list,
             &sorter_tracker,
             &sort_object_list,
         );
-        if !SCALAR_TYPES.contains(&obj.type_name()) {
-            query_root = query_root.field(field);
+        let add_entity_args = |field: Field| {
+            let field = if schema.parsed().is_versioned_typedef(entity_type) {
+                field
+                    .argument(InputValue::new("block", TypeRef::named(BLOCK_FILTER_TYPE)))
+            } else {
+                field
+            };
+            if schema.parsed().search_meta(entity_type).is_some() {
+                field.argument(InputValue::new("search", TypeRef::named(TypeRef::STRING)))
+            } else {
+                field
+            }
+        };
+        if !SCALAR_TYPES.contains(entity_type.as_str()) {
+            query_root = query_root.field(add_entity_args(field));
+
+            // The same results can be paged through with cursors, as a connection.
+            let edge = Object::new(prefixed(&format!("{entity_type}Edge")))
+                .field(placeholder_field(
+                    "node",
+                    TypeRef::named_nn(obj.type_name()),
+                ))
+                .field(placeholder_field(
+                    "cursor",
+                    TypeRef::named_nn(TypeRef::STRING),
+                ));
+            let connection = Object::new(prefixed(&format!("{entity_type}Connection")))
+                .field(placeholder_field(
+                    "edges",
+                    TypeRef::named_nn_list_nn(edge.type_name()),
+                ))
+                .field(placeholder_field(
+                    "pageInfo",
+                    TypeRef::named_nn(PAGE_INFO_TYPE),
+                ));
+
+            let mut connection_field = placeholder_field(
+                &format!("{}Connection", entity_type.to_lowercase()),
+                TypeRef::named_nn(connection.type_name()),
+            )
+            .argument(InputValue::new("first", TypeRef::named(TypeRef::INT)))
+            .argument(InputValue::new("after", TypeRef::named(TypeRef::STRING)))
+            .argument(InputValue::new("last", TypeRef::named(TypeRef::INT)))
+            .argument(InputValue::new("before", TypeRef::named(TypeRef::STRING)));
+            if let Some(idx) = filter_tracker.get(entity_type) {
+                connection_field = connection_field.argument(InputValue::new(
+                    "filter",
+                    TypeRef::named(filter_object_list[*idx].type_name()),
+                ));
+            }
+            if let Some(idx) = sorter_tracker.get(entity_type) {
+                connection_field = connection_field.argument(InputValue::new(
+                    "order",
+                    TypeRef::named(sort_object_list[*idx].type_name()),
+                ));
+            }
+
+            query_root = query_root.field(add_entity_args(connection_field));
+            schema_builder = schema_builder.register(edge).register(connection);
+
+            // Each result of an aggregate is a group of results.
+            let mut aggregate = Object::new(prefixed(&format!("{entity_type}Aggregate")))
+                .field(placeholder_field("count", TypeRef::named_nn(TypeRef::INT)))
+                .field(placeholder_field(
+                    "keys",
+                    TypeRef::named(aggregate_keys.type_name()),
+                ));
+            if has_numeric_fields {
+                for function in ["sum", "min", "max"] {
+                    aggregate = aggregate.field(placeholder_field(
+                        function,
+                        TypeRef::named(aggregate_values.type_name()),
+                    ));
+                }
+                aggregate = aggregate.field(placeholder_field(
+                    "avg",
+                    TypeRef::named(aggregate_averages.type_name()),
+                ));
+                schema_builder = schema_builder
+                    .register(aggregate_values)
+                    .register(aggregate_averages);
+            }
+
+            let mut aggregate_field = placeholder_field(
+                &format!("{}Aggregate", entity_type.to_lowercase()),
+                TypeRef::named_nn_list_nn(aggregate.type_name()),
+            )
+            .argument(InputValue::new(
+                "groupBy",
+                TypeRef::named_nn_list(object_field_enum.type_name()),
+            ));
+            if let Some(idx) = filter_tracker.get(entity_type) {
+                aggregate_field = aggregate_field.argument(InputValue::new(
+                    "filter",
+                    TypeRef::named(filter_object_list[*idx].type_name()),
+                ));
+            }
+
+            query_root = query_root.field(add_entity_args(aggregate_field));
+            schema_builder = schema_builder.register(aggregate).register(aggregate_keys);
         }
 
         schema_builder = schema_builder.register(obj).register(object_field_enum);
@@ -375,10 +836,30 @@ pub fn build_dynamic_schema(schema: &IndexerSchema) -> GraphqlResult<DynamicSche
         schema_builder = schema_builder.register(io);
     }
 
-    schema_builder = schema_builder.register(sort_enum);
-    schema_builder = schema_builder.register(query_root);
+    query_root =
+        query_root.field(placeholder_field(META_FIELD, TypeRef::named_nn(META_TYPE)));
+
+    Ok((schema_builder, query_root))
+}
+
+/// Return the name under which a type of an indexer is registered, given the prefix
+/// of the indexer's types. Scalars are shared by every indexer, so they aren't prefixed.
+fn prefixed_type_name(type_prefix: &str, type_name: &str) -> String {
+    if SCALAR_TYPES.contains(type_name) || BUILT_IN_SCALAR_TYPES.contains(&type_name) {
+        type_name.to_string()
+    } else {
+        format!("{type_prefix}{type_name}")
+    }
+}
 
-    Ok(schema_builder.finish()?)
+/// Create a field whose value is never resolved.
+///
+/// Because the dynamic schema is set to only resolve introspection
+/// queries, the resolver returns a dummy value.
+fn placeholder_field(name: &str, type_ref: TypeRef) -> Field {
+    Field::new(name, type_ref, move |_ctx: ResolverContext| {
+        FieldFuture::new(async move { Ok(Some(FieldValue::value(1))) })
+    })
 }
 
 /// Create input values and objects that are used to build introspection information for a field.
@@ -386,7 +867,6 @@ fn create_input_values_and_objects_for_field(
     field_name: String,
     field_type: String,
     entity_type: String,
-    sort_enum: &Enum,
 ) -> GraphqlResult<(InputValue, Vec<InputObject>, Option<InputValue>)> {
     let field_type =
         Type::new(&field_type).ok_or(GraphqlError::DynamicSchemaBuildError(
@@ -404,7 +884,7 @@ fn create_input_values_and_objects_for_field(
 
             if SORTABLE_SCALAR_TYPES.contains(field_type.as_str()) {
                 let sort_input_val =
-                    InputValue::new(field_name, TypeRef::named(sort_enum.type_name()));
+                    InputValue::new(field_name, TypeRef::named(SORT_ORDER_TYPE));
                 return Ok((
                     field_filter_input_val,
                     field_input_objects,
@@ -467,7 +947,12 @@ fn create_field_with_assoc_args(
                     .argument(id_selection_arg);
             }
         }
-        BaseType::List(_) => unimplemented!("List types are not currently supported"),
+        // List fields are only used for derived fields, which can only be paginated.
+        BaseType::List(_) => {
+            field = field
+                .argument(InputValue::new("offset", TypeRef::named(TypeRef::INT)))
+                .argument(InputValue::new("first", TypeRef::named(TypeRef::INT)));
+        }
     }
 
     field
@@ -483,7 +968,9 @@ fn create_filter_val_and_objects_for_field<'a>(
 ) -> (InputValue, Vec<InputObject>) {
     let mut input_objs: Vec<InputObject> = Vec::new();
 
-    let filter_arg_type = if NUMERIC_SCALAR_TYPES.contains(field_type) {
+    let filter_arg_type = if NUMERIC_SCALAR_TYPES.contains(field_type)
+        && !STRING_SCALAR_TYPES.contains(field_type)
+    {
         TypeRef::INT
     } else {
         TypeRef::STRING
@@ -496,7 +983,7 @@ fn create_filter_val_and_objects_for_field<'a>(
             .field(InputValue::new("min", TypeRef::named_nn(filter_arg_type)))
             .field(InputValue::new("max", TypeRef::named_nn(filter_arg_type)));
 
-    let complete_comparison_obj =
+    let mut complete_comparison_obj =
         InputObject::new(format!("{obj_name}{field_name}FilterObject"))
             .field(InputValue::new(
                 "between",
@@ -512,6 +999,14 @@ fn create_filter_val_and_objects_for_field<'a>(
                 TypeRef::named_nn_list(filter_arg_type),
             ));
 
+    // Fields stored as text can also be matched against patterns.
+    if TEXT_SCALAR_TYPES.contains(field_type) {
+        for op in ["contains", "startsWith", "endsWith", "like", "ilike"] {
+            complete_comparison_obj = complete_comparison_obj
+                .field(InputValue::new(op, TypeRef::named(TypeRef::STRING)));
+        }
+    }
+
     let input_val_for_field = InputValue::new(
         field_name,
         TypeRef::named(complete_comparison_obj.type_name()),
@@ -0,0 +1,368 @@
+use crate::{
+    dynamic::run_sql_queries,
+    graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult, QueryLimits},
+};
+use async_graphql::{dynamic::Schema as DynamicSchema, Request};
+use async_graphql_parser::{
+    parse_query,
+    types::{
+        DocumentOperations, ExecutableDocument, OperationDefinition, OperationType,
+        Selection, SelectionSet, TypeCondition,
+    },
+    Positioned,
+};
+use async_graphql_value::Name;
+use fuel_indexer_database::IndexerConnectionPool;
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+
+/// Return the name of the root field under which an indexer is queried in a
+/// federated query.
+pub fn federated_field_name(namespace: &str, identifier: &str) -> String {
+    format!("{namespace}_{identifier}")
+}
+
+/// Return the root field name of each of the given `(namespace, identifier)` pairs,
+/// along with the pair.
+///
+/// Names made of a namespace and an identifier that contain underscores could be
+/// shared by several indexers, in which case the name is ambiguous and left out.
+pub fn federated_field_names(
+    indexers: Vec<(String, String)>,
+) -> HashMap<String, (String, String)> {
+    let mut names: HashMap<String, Option<(String, String)>> = HashMap::new();
+    for (namespace, identifier) in indexers {
+        names
+            .entry(federated_field_name(&namespace, &identifier))
+            .and_modify(|indexer| *indexer = None)
+            .or_insert(Some((namespace, identifier)));
+    }
+
+    names
+        .into_iter()
+        .filter_map(|(name, indexer)| Some((name, indexer?)))
+        .collect()
+}
+
+/// Validate a query against several indexers with their federated dynamic schema,
+/// and return its results.
+///
+/// Introspection fields are answered by the dynamic schema. Every other root field
+/// selects from the indexer that it's named after, amongst the given schemas, and is
+/// answered as if its selections had been sent to that indexer alone.
+pub async fn execute_federated_query(
+    request: Request,
+    dynamic_schema: &DynamicSchema,
+    schemas: &HashMap<String, &IndexerSchema>,
+    pool: &IndexerConnectionPool,
+    limits: QueryLimits,
+) -> GraphqlResult<Value> {
+    let query = request.query.clone();
+    let operation_name = request.operation_name.clone();
+
+    // The dynamic schema only resolves introspection queries, so executing the
+    // request validates it, and resolves its indexer fields to placeholders that
+    // are then replaced by the results of their subqueries.
+    let response = dynamic_schema.execute(request).await;
+    if !response.errors.is_empty() {
+        let messages = response
+            .errors
+            .into_iter()
+            .map(|e| e.message)
+            .collect::<Vec<_>>();
+        return Err(GraphqlError::InvalidQuery(messages.join(" ")));
+    }
+    let mut data = match response.data.into_json()? {
+        Value::Object(data) => data,
+        _ => serde_json::Map::new(),
+    };
+
+    for subquery in split_federated_query(&query, operation_name.as_deref())? {
+        let Some(schema) = schemas.get(&subquery.field) else {
+            return Err(GraphqlError::UnrecognizedIndexer(subquery.field));
+        };
+        let key = subquery.key.clone();
+        data.insert(key, subquery.execute(pool, schema, limits.clone()).await?);
+    }
+
+    Ok(Value::Object(data))
+}
+
+/// The part of a federated query that selects from one indexer.
+#[derive(Debug)]
+pub struct FederatedSubquery {
+    /// Key under which the results of the subquery are returned.
+    pub key: String,
+
+    /// Name of the root field of the queried indexer.
+    pub field: String,
+
+    /// Query against the indexer, made of the selections of its root field.
+    document: ExecutableDocument,
+}
+
+impl FederatedSubquery {
+    /// Run the subquery against the schema of its indexer, and return its results.
+    pub async fn execute(
+        self,
+        pool: &IndexerConnectionPool,
+        schema: &IndexerSchema,
+        limits: QueryLimits,
+    ) -> GraphqlResult<Value> {
+        let query = GraphqlQueryBuilder::from_document(schema, self.document)
+            .with_limits(limits.clone())
+            .build()?;
+        let queries = query.as_sql(schema, pool.database_type())?;
+
+        Ok(Value::Array(
+            run_sql_queries(pool, queries, limits.timeout).await?,
+        ))
+    }
+}
+
+/// Split a query against several indexers into a query against each of them, one
+/// per root field of the query, leaving out introspection fields.
+///
+/// In a federated query, the types of each indexer are prefixed with the name of
+/// its root field. That prefix is removed from the type conditions of fragments, so
+/// that each subquery only refers to the types of its own indexer.
+pub fn split_federated_query(
+    query: &str,
+    operation_name: Option<&str>,
+) -> GraphqlResult<Vec<FederatedSubquery>> {
+    let document = parse_query::<&str>(query)?;
+
+    let operation = match (&document.operations, operation_name) {
+        (DocumentOperations::Single(operation), _) => operation,
+        (DocumentOperations::Multiple(operations), Some(operation_name)) => operations
+            .iter()
+            .find(|(name, _)| name.as_str() == operation_name)
+            .map(|(_, operation)| operation)
+            .ok_or_else(|| {
+                GraphqlError::OperationNotSupported(operation_name.to_string())
+            })?,
+        (DocumentOperations::Multiple(operations), None) if operations.len() == 1 => {
+            operations.values().next().expect("One operation.")
+        }
+        (DocumentOperations::Multiple(_), None) => {
+            return Err(GraphqlError::OperationNotSupported(
+                "Multiple operations".to_string(),
+            ))
+        }
+    };
+    if operation.node.ty != OperationType::Query {
+        return Err(GraphqlError::OperationNotSupported(
+            operation.node.ty.to_string(),
+        ));
+    }
+
+    let mut subqueries = Vec::new();
+    for selection in &operation.node.selection_set.node.items {
+        let Selection::Field(field) = &selection.node else {
+            return Err(GraphqlError::SelectionNotSupported);
+        };
+        let field = &field.node;
+
+        let name = field.name.node.to_string();
+        if name.starts_with("__") {
+            continue;
+        }
+        let type_prefix = format!("{name}_");
+
+        // Fragments can only be resolved by the indexer whose types they are on.
+        let fragment_names = used_fragments(&document, &field.selection_set.node);
+        let fragments = document
+            .fragments
+            .iter()
+            .filter(|(name, _)| fragment_names.contains(*name))
+            .map(|(name, fragment)| {
+                let mut fragment = fragment.clone();
+                strip_type_prefix(&mut fragment.node.type_condition.node, &type_prefix);
+                strip_selections_type_prefix(
+                    &mut fragment.node.selection_set.node,
+                    &type_prefix,
+                );
+                (name.clone(), fragment)
+            })
+            .collect();
+
+        let mut selection_set = field.selection_set.clone();
+        strip_selections_type_prefix(&mut selection_set.node, &type_prefix);
+        let subquery = OperationDefinition {
+            ty: OperationType::Query,
+            variable_definitions: operation.node.variable_definitions.clone(),
+            directives: vec![],
+            selection_set,
+        };
+
+        subqueries.push(FederatedSubquery {
+            key: field
+                .alias
+                .as_ref()
+                .map(|alias| alias.node.to_string())
+                .unwrap_or_else(|| name.clone()),
+            field: name,
+            document: ExecutableDocument {
+                operations: DocumentOperations::Single(Positioned::new(
+                    subquery,
+                    operation.pos,
+                )),
+                fragments,
+            },
+        });
+    }
+
+    Ok(subqueries)
+}
+
+/// Remove the prefix of an indexer's types from a type condition.
+fn strip_type_prefix(condition: &mut TypeCondition, type_prefix: &str) {
+    if let Some(type_name) = condition.on.node.as_str().strip_prefix(type_prefix) {
+        condition.on.node = Name::new(type_name);
+    }
+}
+
+/// Remove the prefix of an indexer's types from the type conditions of the inline
+/// fragments in a selection set.
+fn strip_selections_type_prefix(selections: &mut SelectionSet, type_prefix: &str) {
+    for selection in &mut selections.items {
+        match &mut selection.node {
+            Selection::Field(field) => strip_selections_type_prefix(
+                &mut field.node.selection_set.node,
+                type_prefix,
+            ),
+            Selection::InlineFragment(fragment) => {
+                if let Some(condition) = &mut fragment.node.type_condition {
+                    strip_type_prefix(&mut condition.node, type_prefix);
+                }
+                strip_selections_type_prefix(
+                    &mut fragment.node.selection_set.node,
+                    type_prefix,
+                );
+            }
+            Selection::FragmentSpread(_) => {}
+        }
+    }
+}
+
+/// Return the names of the fragments spread in a selection set, including those
+/// spread in the fragments themselves.
+fn used_fragments(
+    document: &ExecutableDocument,
+    selections: &SelectionSet,
+) -> HashSet<Name> {
+    let mut used = HashSet::new();
+    let mut queue = vec![selections];
+
+    while let Some(selections) = queue.pop() {
+        for selection in &selections.items {
+            match &selection.node {
+                Selection::Field(field) => queue.push(&field.node.selection_set.node),
+                Selection::InlineFragment(fragment) => {
+                    queue.push(&fragment.node.selection_set.node)
+                }
+                Selection::FragmentSpread(spread) => {
+                    let name = &spread.node.fragment_name.node;
+                    if used.insert(name.clone()) {
+                        if let Some(fragment) = document.fragments.get(name) {
+                            queue.push(&fragment.node.selection_set.node);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    used
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_federated_field_names() {
+        let names = federated_field_names(vec![
+            ("fuellabs".to_string(), "explorer".to_string()),
+            ("fuel_labs".to_string(), "swaps".to_string()),
+            ("fuel".to_string(), "labs_swaps".to_string()),
+        ]);
+
+        assert_eq!(names.len(), 1);
+        assert_eq!(
+            names.get("fuellabs_explorer"),
+            Some(&("fuellabs".to_string(), "explorer".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_split_federated_query() {
+        let subqueries = split_federated_query(
+            r#"
+            query {
+                __schema { queryType { name } }
+                fuellabs_explorer { block { ...blockFields } }
+                pools: fuellabs_swaps {
+                    pool { ... on fuellabs_swaps_Pool { id } }
+                }
+            }
+
+            fragment blockFields on fuellabs_explorer_Block { id height }
+            "#,
+            None,
+        )
+        .unwrap();
+
+        assert_eq!(subqueries.len(), 2);
+        assert_eq!(subqueries[0].key, "fuellabs_explorer");
+        assert_eq!(subqueries[0].field, "fuellabs_explorer");
+        assert_eq!(
+            subqueries[0].document.fragments[&Name::new("blockFields")]
+                .node
+                .type_condition
+                .node
+                .on
+                .node
+                .as_str(),
+            "Block"
+        );
+        assert_eq!(subqueries[1].key, "pools");
+        assert_eq!(subqueries[1].field, "fuellabs_swaps");
+        assert!(subqueries[1].document.fragments.is_empty());
+
+        let DocumentOperations::Single(operation) = &subqueries[1].document.operations
+        else {
+            panic!("Expected a single operation.");
+        };
+        let Selection::Field(pool) = &operation.node.selection_set.node.items[0].node
+        else {
+            panic!("Expected a field.");
+        };
+        let Selection::InlineFragment(fragment) =
+            &pool.node.selection_set.node.items[0].node
+        else {
+            panic!("Expected an inline fragment.");
+        };
+        assert_eq!(
+            fragment
+                .node
+                .type_condition
+                .as_ref()
+                .unwrap()
+                .node
+                .on
+                .node
+                .as_str(),
+            "Pool"
+        );
+
+        assert!(matches!(
+            split_federated_query(
+                "subscription { fuellabs_explorer { block { id } } }",
+                None
+            ),
+            Err(GraphqlError::OperationNotSupported(_))
+        ));
+    }
+}
@@ -1,19 +1,28 @@
 use super::{
     arguments::{parse_argument_into_param, ParamType, QueryParams},
+    dynamic::NUMERIC_SCALAR_TYPES,
     queries::{JoinCondition, QueryElement, QueryJoinNode, UserQuery},
 };
 use async_graphql_parser::{
     parse_query,
     types::{
         DocumentOperations, ExecutableDocument, Field, FragmentDefinition,
-        FragmentSpread, OperationDefinition, OperationType, SelectionSet, TypeCondition,
+        FragmentSpread, InlineFragment, OperationDefinition, OperationType, SelectionSet,
+        TypeCondition,
     },
 };
 use fuel_indexer_database_types::DbType;
+use fuel_indexer_lib::{
+    config::WebApiConfig,
+    graphql::{field_id, types::IdCol},
+};
 use fuel_indexer_schema::db::tables::IndexerSchema;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use thiserror::Error;
 
+/// Name of the top-level field that reports the progress of an indexer.
+pub const META_FIELD: &str = "_meta";
+
 pub type GraphqlResult<T> = Result<T, GraphqlError>;
 
 #[derive(Debug, Error)]
@@ -52,8 +61,40 @@ pub enum GraphqlError {
     MissingPartnerForBinaryLogicalOperator,
     #[error("Paginated query must have an order applied to at least one field")]
     UnorderedPaginatedQuery,
+    #[error("Invalid cursor: {0:?}")]
+    InvalidCursor(String),
+    #[error("Arguments {0:?} and {1:?} can't be used together")]
+    ConflictingArguments(String, String),
+    #[error("Field {0:?} must be in groupBy to be selected as a key")]
+    UngroupedField(String),
     #[error("Query error: {0:?}")]
     QueryError(String),
+    #[error("Query depth of {0} exceeds the maximum depth of {1}")]
+    MaxDepthExceeded(usize, usize),
+    #[error("Query joins {0} related entities, which exceeds the maximum of {1}")]
+    MaxJoinsExceeded(usize, usize),
+    #[error("Query requests {0} records, which exceeds the maximum of {1}")]
+    MaxLimitExceeded(u64, u64),
+    #[error("Query exceeded the timeout of {0}ms")]
+    QueryTimeout(u64),
+    #[error("Unrecognized indexer: {0:?}")]
+    UnrecognizedIndexer(String),
+    #[error("Invalid query: {0:?}")]
+    InvalidQuery(String),
+}
+
+impl GraphqlError {
+    /// Return a code for errors raised by the limits of a query, by which clients
+    /// can tell them apart from errors in the query itself.
+    pub fn code(&self) -> Option<&'static str> {
+        match self {
+            Self::MaxDepthExceeded(..) => Some("MAX_DEPTH_EXCEEDED"),
+            Self::MaxJoinsExceeded(..) => Some("MAX_JOINS_EXCEEDED"),
+            Self::MaxLimitExceeded(..) => Some("MAX_LIMIT_EXCEEDED"),
+            Self::QueryTimeout(_) => Some("QUERY_TIMEOUT"),
+            _ => None,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -65,6 +106,11 @@ pub enum Selection {
         alias: Option<String>,
     },
     Fragment(String),
+    /// Selections that only apply to the implementor `cond` of an interface.
+    Typed {
+        cond: String,
+        selections: Selections,
+    },
 }
 
 #[derive(Clone, Debug)]
@@ -93,6 +139,40 @@ impl Selections {
                         alias,
                         ..
                     } = &field.node;
+
+                    // Connections wrap the results of a top-level entity query in edges,
+                    // each with a cursor from which the next page can be requested.
+                    if let (None, Some(entity)) =
+                        (field_type, connection_entity(schema, name.node.as_str()))
+                    {
+                        let selection =
+                            Selections::connection(schema, entity, &field.node)?;
+                        if let Selection::Field { sub_selections, .. } = &selection {
+                            has_fragments |= sub_selections.has_fragments;
+                        }
+                        selections.push(selection);
+                        continue;
+                    }
+
+                    // The indexer's progress is reported alongside its entities.
+                    if field_type.is_none() && name.node.as_str() == META_FIELD {
+                        selections.push(Selections::meta(&field.node)?);
+                        continue;
+                    }
+
+                    // Aggregates compute values over all of the (filtered) results of a
+                    // top-level entity query, optionally in groups.
+                    if let (None, Some(entity)) =
+                        (field_type, aggregate_entity(schema, name.node.as_str()))
+                    {
+                        selections.push(Selections::aggregate(
+                            schema,
+                            entity,
+                            &field.node,
+                        )?);
+                        continue;
+                    }
+
                     let subfield_type =
                         match schema.parsed().graphql_type(field_type, &name.to_string())
                         {
@@ -111,6 +191,71 @@ impl Selections {
                             }
                         };
 
+                    // Only top-level entities can be queried as of a block height, or searched.
+                    if let (Some(parent), Some((arg, _))) = (
+                        field_type,
+                        arguments.iter().find(|(arg, _)| {
+                            matches!(arg.node.as_str(), "block" | "search")
+                        }),
+                    ) {
+                        return Err(GraphqlError::UnrecognizedArgument(
+                            format!("{parent}.{name}"),
+                            arg.to_string(),
+                        ));
+                    }
+
+                    // Cursors only point to the results of connections.
+                    if let Some((arg, _)) = arguments.iter().find(|(arg, _)| {
+                        matches!(arg.node.as_str(), "after" | "before" | "last")
+                    }) {
+                        return Err(GraphqlError::UnrecognizedArgument(
+                            field_type.cloned().unwrap_or(subfield_type.to_string()),
+                            arg.to_string(),
+                        ));
+                    }
+
+                    // Only aggregates are grouped.
+                    if let Some((arg, _)) = arguments
+                        .iter()
+                        .find(|(arg, _)| arg.node.as_str() == "groupBy")
+                    {
+                        return Err(GraphqlError::UnrecognizedArgument(
+                            field_type.cloned().unwrap_or(subfield_type.to_string()),
+                            arg.to_string(),
+                        ));
+                    }
+
+                    // Each implementor of an interface is queried separately, so the results
+                    // can't be ordered or paginated as a whole.
+                    if field_type.is_none()
+                        && schema.parsed().is_interface_typedef(subfield_type)
+                    {
+                        if let Some((arg, _)) = arguments.iter().find(|(arg, _)| {
+                            matches!(arg.node.as_str(), "order" | "first" | "offset")
+                        }) {
+                            return Err(GraphqlError::UnrecognizedArgument(
+                                subfield_type.to_string(),
+                                arg.to_string(),
+                            ));
+                        }
+                    }
+
+                    // Derived fields are aggregated into a list, which can be paginated
+                    // but not filtered or ordered.
+                    if let Some(parent) = field_type {
+                        let fid = format!("{parent}.{name}");
+                        if schema.parsed().is_derived_field(&fid) {
+                            if let Some((arg, _)) = arguments.iter().find(|(arg, _)| {
+                                !matches!(arg.node.as_str(), "first" | "offset")
+                            }) {
+                                return Err(GraphqlError::UnrecognizedArgument(
+                                    fid,
+                                    arg.to_string(),
+                                ));
+                            }
+                        }
+                    }
+
                     let params = arguments
                         .iter()
                         .map(|(arg, value)| {
@@ -144,8 +289,48 @@ impl Selections {
                     has_fragments = true;
                     selections.push(Selection::Fragment(fragment_name.to_string()));
                 }
-                // TODO: Support inline fragments
-                _ => return Err(GraphqlError::SelectionNotSupported),
+                async_graphql_parser::types::Selection::InlineFragment(frag) => {
+                    let InlineFragment {
+                        type_condition,
+                        selection_set,
+                        ..
+                    } = &frag.node;
+
+                    let cond = match (type_condition, field_type) {
+                        (Some(c), _) => c.node.on.to_string(),
+                        (None, Some(typ)) => typ.to_string(),
+                        (None, None) => return Err(GraphqlError::SelectionNotSupported),
+                    };
+
+                    if !schema.parsed().has_type(&cond) {
+                        return Err(GraphqlError::UnrecognizedType(cond));
+                    }
+
+                    let frag = Fragment::new(schema, cond, &selection_set.node)?;
+                    has_fragments |= frag.has_fragments();
+
+                    match field_type {
+                        Some(typ)
+                            if frag.check_cond(Some(typ))
+                                || frag.is_implemented_by(schema, Some(typ)) =>
+                        {
+                            selections.extend(frag.selections.get_selections());
+                        }
+                        Some(typ) if frag.applies_to(schema, typ) => {
+                            selections.push(Selection::Typed {
+                                cond: frag.cond,
+                                selections: frag.selections,
+                            });
+                        }
+                        Some(typ) => {
+                            return Err(GraphqlError::InvalidFragmentSelection(
+                                frag,
+                                typ.to_string(),
+                            ))
+                        }
+                        None => return Err(GraphqlError::SelectionNotSupported),
+                    }
+                }
             }
         }
 
@@ -155,6 +340,280 @@ impl Selections {
         })
     }
 
+    /// Parse the selection of the `_meta` field, whose fields report the last block
+    /// committed by the indexer and the state of the indexer itself.
+    fn meta(field: &Field) -> GraphqlResult<Selection> {
+        if let Some((arg, _)) = field.arguments.first() {
+            return Err(GraphqlError::UnrecognizedArgument(
+                "_Meta".to_string(),
+                arg.to_string(),
+            ));
+        }
+
+        let mut selections = Vec::new();
+        for item in &field.selection_set.node.items {
+            let async_graphql_parser::types::Selection::Field(f) = &item.node else {
+                return Err(GraphqlError::SelectionNotSupported);
+            };
+
+            let name = f.node.name.to_string();
+            let (typ, fields): (&str, &[&str]) = match name.as_str() {
+                "block" => ("_MetaBlock", &["height", "id", "timestamp"]),
+                "indexer" => (
+                    "_MetaIndexer",
+                    &["namespace", "identifier", "version", "hasIndexingErrors"],
+                ),
+                _ => {
+                    return Err(GraphqlError::UnrecognizedField(
+                        "_Meta".to_string(),
+                        name,
+                    ))
+                }
+            };
+
+            let mut sub_selections = Vec::new();
+            for item in &f.node.selection_set.node.items {
+                let async_graphql_parser::types::Selection::Field(f) = &item.node else {
+                    return Err(GraphqlError::SelectionNotSupported);
+                };
+
+                let sub_name = f.node.name.to_string();
+                if !fields.contains(&sub_name.as_str()) {
+                    return Err(GraphqlError::UnrecognizedField(
+                        typ.to_string(),
+                        sub_name,
+                    ));
+                }
+
+                sub_selections.push(Selection::Field {
+                    name: sub_name,
+                    params: Vec::new(),
+                    sub_selections: Selections {
+                        has_fragments: false,
+                        selections: vec![],
+                    },
+                    alias: f.node.alias.as_ref().map(|a| a.to_string()),
+                });
+            }
+
+            selections.push(Selection::Field {
+                name,
+                params: Vec::new(),
+                sub_selections: Selections {
+                    has_fragments: false,
+                    selections: sub_selections,
+                },
+                alias: f.node.alias.as_ref().map(|a| a.to_string()),
+            });
+        }
+
+        Ok(Selection::Field {
+            name: META_FIELD.to_string(),
+            params: vec![ParamType::Meta],
+            sub_selections: Selections {
+                has_fragments: false,
+                selections,
+            },
+            alias: field.alias.as_ref().map(|a| a.to_string()),
+        })
+    }
+
+    /// Parse the selection of a connection of `entity` into a selection of the
+    /// entity itself, whose sub-selections are those of each edge's `node`.
+    ///
+    /// The `cursor` of each edge, and the `pageInfo` of the connection, are always
+    /// returned in full.
+    fn connection(
+        schema: &IndexerSchema,
+        entity: &String,
+        field: &Field,
+    ) -> GraphqlResult<Selection> {
+        let connection_type = format!("{entity}Connection");
+
+        let mut params = vec![ParamType::Connection];
+        for (arg, value) in field.arguments.iter() {
+            // Pages of a connection start at a cursor, rather than an offset.
+            if arg.node.as_str() == "offset" {
+                return Err(GraphqlError::UnrecognizedArgument(
+                    connection_type,
+                    arg.to_string(),
+                ));
+            }
+
+            params.push(parse_argument_into_param(
+                Some(entity),
+                arg.node.as_str(),
+                value.node.clone(),
+                schema,
+            )?);
+        }
+
+        let mut node = None;
+        for item in &field.selection_set.node.items {
+            let async_graphql_parser::types::Selection::Field(f) = &item.node else {
+                return Err(GraphqlError::SelectionNotSupported);
+            };
+
+            match f.node.name.node.as_str() {
+                "pageInfo" => {}
+                "edges" => {
+                    for item in &f.node.selection_set.node.items {
+                        let async_graphql_parser::types::Selection::Field(f) = &item.node
+                        else {
+                            return Err(GraphqlError::SelectionNotSupported);
+                        };
+
+                        match f.node.name.node.as_str() {
+                            "cursor" => {}
+                            "node" => {
+                                node = Some(Selections::new(
+                                    schema,
+                                    Some(entity),
+                                    &f.node.selection_set.node,
+                                )?)
+                            }
+                            other => {
+                                return Err(GraphqlError::UnrecognizedField(
+                                    format!("{entity}Edge"),
+                                    other.to_string(),
+                                ))
+                            }
+                        }
+                    }
+                }
+                other => {
+                    return Err(GraphqlError::UnrecognizedField(
+                        connection_type,
+                        other.to_string(),
+                    ))
+                }
+            }
+        }
+
+        Ok(Selection::Field {
+            name: entity.to_lowercase(),
+            params,
+            sub_selections: node.unwrap_or(Selections {
+                has_fragments: false,
+                selections: vec![],
+            }),
+            alias: Some(field.alias.as_ref().unwrap_or(&field.name).node.to_string()),
+        })
+    }
+
+    /// Parse the selection of an aggregate of `entity` into a selection of the entity,
+    /// whose sub-selections are the aggregate functions and group keys to return.
+    ///
+    /// `sum`, `avg`, `min` and `max` are computed over numeric fields, and `keys` can
+    /// only contain fields that the results are grouped by.
+    fn aggregate(
+        schema: &IndexerSchema,
+        entity: &String,
+        field: &Field,
+    ) -> GraphqlResult<Selection> {
+        let aggregate_type = format!("{entity}Aggregate");
+
+        let mut params = vec![ParamType::Aggregate];
+        let mut group_by = Vec::new();
+        for (arg, value) in field.arguments.iter() {
+            if !matches!(arg.node.as_str(), "filter" | "groupBy" | "block" | "search") {
+                return Err(GraphqlError::UnrecognizedArgument(
+                    aggregate_type,
+                    arg.to_string(),
+                ));
+            }
+
+            let param = parse_argument_into_param(
+                Some(entity),
+                arg.node.as_str(),
+                value.node.clone(),
+                schema,
+            )?;
+            if let ParamType::GroupBy(fields) = &param {
+                group_by.extend(fields.clone());
+            }
+            params.push(param);
+        }
+
+        let mut selections = Vec::new();
+        for item in &field.selection_set.node.items {
+            let async_graphql_parser::types::Selection::Field(f) = &item.node else {
+                return Err(GraphqlError::SelectionNotSupported);
+            };
+
+            let name = f.node.name.node.to_string();
+            let mut sub_selections = Vec::new();
+            for item in &f.node.selection_set.node.items {
+                let async_graphql_parser::types::Selection::Field(sub) = &item.node
+                else {
+                    return Err(GraphqlError::SelectionNotSupported);
+                };
+
+                let sub_name = sub.node.name.node.to_string();
+                let is_numeric = schema
+                    .parsed()
+                    .graphql_type(Some(entity), &sub_name)
+                    .map(|typ| {
+                        NUMERIC_SCALAR_TYPES.contains(typ.replace('!', "").as_str())
+                    })
+                    .unwrap_or(false);
+
+                match name.as_str() {
+                    "keys" if !group_by.contains(&sub_name) => {
+                        return Err(GraphqlError::UngroupedField(sub_name))
+                    }
+                    "sum" | "avg" | "min" | "max" if !is_numeric => {
+                        return Err(GraphqlError::UnrecognizedField(
+                            format!("{aggregate_type}.{name}"),
+                            sub_name,
+                        ))
+                    }
+                    _ => {}
+                }
+
+                sub_selections.push(Selection::Field {
+                    name: sub_name,
+                    params: vec![],
+                    sub_selections: Selections {
+                        has_fragments: false,
+                        selections: vec![],
+                    },
+                    alias: sub.node.alias.as_ref().map(|a| a.node.to_string()),
+                });
+            }
+
+            // Only `count` is a single value; the others are objects of fields.
+            if (name == "count") != sub_selections.is_empty()
+                || !matches!(
+                    name.as_str(),
+                    "count" | "sum" | "avg" | "min" | "max" | "keys"
+                )
+            {
+                return Err(GraphqlError::UnrecognizedField(aggregate_type, name));
+            }
+
+            selections.push(Selection::Field {
+                name,
+                params: vec![],
+                sub_selections: Selections {
+                    has_fragments: false,
+                    selections: sub_selections,
+                },
+                alias: f.node.alias.as_ref().map(|a| a.node.to_string()),
+            });
+        }
+
+        Ok(Selection::Field {
+            name: entity.to_lowercase(),
+            params,
+            sub_selections: Selections {
+                has_fragments: false,
+                selections,
+            },
+            alias: Some(field.alias.as_ref().unwrap_or(&field.name).node.to_string()),
+        })
+    }
+
     pub fn resolve_fragments(
         &mut self,
         schema: &IndexerSchema,
@@ -167,24 +626,43 @@ impl Selections {
 
         for selection in &mut self.selections {
             match selection {
-                Selection::Fragment(name) => {
-                    if let Some(frag) = fragments.get(name) {
-                        if !frag.check_cond(cond) {
-                            if let Some(c) = cond {
-                                return Err(GraphqlError::InvalidFragmentSelection(
-                                    frag.clone(),
-                                    c.to_string(),
-                                ));
-                            } else {
-                                return Err(GraphqlError::FragmentResolverFailed);
-                            }
-                        }
+                Selection::Fragment(name) => match (fragments.get(name), cond) {
+                    (Some(frag), Some(c)) if frag.applies_to(schema, c) => {
+                        resolved += 1;
+                        selections.push(Selection::Typed {
+                            cond: frag.cond.clone(),
+                            selections: frag.selections.clone(),
+                        });
+                    }
+                    (Some(frag), _)
+                        if frag.check_cond(cond)
+                            || frag.is_implemented_by(schema, cond) =>
+                    {
                         resolved += 1;
                         selections.extend(frag.selections.get_selections());
-                    } else {
+                    }
+                    (Some(frag), Some(c)) => {
+                        return Err(GraphqlError::InvalidFragmentSelection(
+                            frag.clone(),
+                            c.to_string(),
+                        ));
+                    }
+                    (Some(_), None) => {
+                        return Err(GraphqlError::FragmentResolverFailed);
+                    }
+                    (None, _) => {
                         has_fragments = true;
                         selections.push(Selection::Fragment(name.to_string()));
                     }
+                },
+                // The selections of an aggregate are functions, and those of `_meta`
+                // are reports, rather than fields, and can't contain fragments.
+                Selection::Field { params, .. }
+                    if params
+                        .iter()
+                        .any(|p| matches!(p, ParamType::Aggregate | ParamType::Meta)) =>
+                {
+                    selections.push(selection.clone());
                 }
                 Selection::Field {
                     name,
@@ -216,6 +694,22 @@ impl Selections {
                         alias: alias.clone(),
                     });
                 }
+                Selection::Typed {
+                    cond,
+                    selections: typed_selections,
+                } => {
+                    resolved += typed_selections.resolve_fragments(
+                        schema,
+                        Some(&cond.clone()),
+                        fragments,
+                    )?;
+                    has_fragments |= typed_selections.has_fragments;
+
+                    selections.push(Selection::Typed {
+                        cond: cond.to_string(),
+                        selections: typed_selections.clone(),
+                    });
+                }
             }
         }
 
@@ -227,6 +721,174 @@ impl Selections {
     pub fn get_selections(&self) -> Vec<Selection> {
         self.selections.clone()
     }
+
+    /// Return the depth of the most deeply nested entity in the selections, where
+    /// each entity is one level deeper than the entity that it's selected from.
+    fn depth(&self) -> usize {
+        self.selections
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field {
+                    params,
+                    sub_selections,
+                    ..
+                } if !sub_selections.selections.is_empty() => {
+                    // The selections of an aggregate are functions, and those of `_meta`
+                    // are reports, rather than entities.
+                    if params
+                        .iter()
+                        .any(|p| matches!(p, ParamType::Aggregate | ParamType::Meta))
+                    {
+                        1
+                    } else {
+                        1 + sub_selections.depth()
+                    }
+                }
+                Selection::Typed { selections, .. } => selections.depth(),
+                _ => 0,
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Return the number of related entities that are joined in order to resolve the
+    /// selections, either because they're selected or because they're filtered by.
+    fn joins(&self) -> usize {
+        self.selections
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field {
+                    params,
+                    sub_selections,
+                    ..
+                } => {
+                    let filtered: usize = params
+                        .iter()
+                        .map(|param| match param {
+                            ParamType::Filter(filter) => filter.joins(),
+                            _ => 0,
+                        })
+                        .sum();
+                    let selected = if params
+                        .iter()
+                        .any(|p| matches!(p, ParamType::Aggregate | ParamType::Meta))
+                    {
+                        0
+                    } else {
+                        sub_selections.entities() + sub_selections.joins()
+                    };
+                    filtered + selected
+                }
+                Selection::Typed { selections, .. } => selections.joins(),
+                Selection::Fragment(_) => 0,
+            })
+            .sum()
+    }
+
+    /// Return the number of entities that are selected at this level.
+    fn entities(&self) -> usize {
+        self.selections
+            .iter()
+            .map(|selection| match selection {
+                Selection::Field { sub_selections, .. }
+                    if !sub_selections.selections.is_empty() =>
+                {
+                    1
+                }
+                Selection::Typed { selections, .. } => selections.entities(),
+                _ => 0,
+            })
+            .sum()
+    }
+
+    /// Return the largest number of records that are requested with `first` or `last`.
+    fn max_requested(&self) -> Option<u64> {
+        self.selections
+            .iter()
+            .filter_map(|selection| match selection {
+                Selection::Field {
+                    params,
+                    sub_selections,
+                    ..
+                } => params
+                    .iter()
+                    .filter_map(|param| match param {
+                        ParamType::Limit(n) | ParamType::Last(n) => Some(*n),
+                        _ => None,
+                    })
+                    .chain(sub_selections.max_requested())
+                    .max(),
+                Selection::Typed { selections, .. } => selections.max_requested(),
+                Selection::Fragment(_) => None,
+            })
+            .max()
+    }
+
+    /// Return the selections that apply to `typ`, where `Selection::Typed` selections
+    /// for other implementors of an interface are left out.
+    fn for_implementor(&self, typ: &str) -> Selections {
+        let mut selections: Vec<Selection> = Vec::new();
+
+        for selection in &self.selections {
+            let applicable = match selection {
+                Selection::Typed { cond, selections } if cond == typ => {
+                    selections.for_implementor(typ).selections
+                }
+                Selection::Typed { .. } => continue,
+                selection => vec![selection.clone()],
+            };
+
+            // The same field may be selected for the interface and for the implementor.
+            for selection in applicable {
+                let is_duplicate = matches!(
+                    &selection,
+                    Selection::Field { sub_selections, .. } if sub_selections.selections.is_empty()
+                        && selections.iter().any(|s| response_key(s) == response_key(&selection))
+                );
+                if !is_duplicate {
+                    selections.push(selection);
+                }
+            }
+        }
+
+        Selections {
+            has_fragments: self.has_fragments,
+            selections,
+        }
+    }
+}
+
+/// If `name` is the name of a top-level connection field (e.g. `swapConnection`),
+/// return the entity type whose results it wraps.
+///
+/// Only object types have connections. Interfaces are left out, since their results
+/// can't be ordered as a whole.
+fn connection_entity<'a>(schema: &'a IndexerSchema, name: &str) -> Option<&'a String> {
+    let entity = name.strip_suffix("Connection")?;
+    schema
+        .parsed()
+        .graphql_type(None, entity)
+        .filter(|typ| schema.parsed().objects().contains_key(*typ))
+}
+
+/// If `name` is the name of a top-level aggregate field (e.g. `swapAggregate`),
+/// return the entity type whose results it aggregates.
+///
+/// As with connections, only object types have aggregates.
+fn aggregate_entity<'a>(schema: &'a IndexerSchema, name: &str) -> Option<&'a String> {
+    let entity = name.strip_suffix("Aggregate")?;
+    schema
+        .parsed()
+        .graphql_type(None, entity)
+        .filter(|typ| schema.parsed().objects().contains_key(*typ))
+}
+
+/// The key of a field selection in the query response.
+fn response_key(selection: &Selection) -> Option<&String> {
+    match selection {
+        Selection::Field { name, alias, .. } => Some(alias.as_ref().unwrap_or(name)),
+        _ => None,
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -254,6 +916,32 @@ impl Fragment {
         }
     }
 
+    /// Whether this fragment is on an implementor of the interface `cond`, and so
+    /// only applies to the results of that implementor.
+    pub fn applies_to(&self, schema: &IndexerSchema, cond: &str) -> bool {
+        schema.parsed().is_interface_typedef(cond)
+            && schema
+                .parsed()
+                .interface_implementors(cond)
+                .contains(&&self.cond)
+    }
+
+    /// Whether this fragment is on an interface that `cond` implements, and so
+    /// applies to all of its results.
+    pub fn is_implemented_by(
+        &self,
+        schema: &IndexerSchema,
+        cond: Option<&String>,
+    ) -> bool {
+        cond.map(|c| {
+            schema
+                .parsed()
+                .interface_implementors(&self.cond)
+                .contains(&c)
+        })
+        .unwrap_or(false)
+    }
+
     pub fn has_fragments(&self) -> bool {
         self.selections.has_fragments
     }
@@ -290,6 +978,16 @@ impl Operation {
     }
 
     pub fn parse(&self, schema: &IndexerSchema) -> Vec<UserQuery> {
+        self.parse_with_default_limit(schema, None)
+    }
+
+    /// Parse the operation into queries that return `default_limit` records, unless
+    /// they request a number themselves.
+    fn parse_with_default_limit(
+        &self,
+        schema: &IndexerSchema,
+        default_limit: Option<u64>,
+    ) -> Vec<UserQuery> {
         let Operation {
             namespace,
             identifier,
@@ -299,15 +997,25 @@ impl Operation {
 
         let mut queries = Vec::new();
 
-        for selection in selections.get_selections() {
+        for (selection, typename) in expand_interfaces(schema, selections) {
             let mut elements: Vec<QueryElement> = Vec::new();
             let mut entities: Vec<String> = Vec::new();
 
             let mut joins: HashMap<String, QueryJoinNode> = HashMap::new();
-            let mut query_params: QueryParams = QueryParams::default();
+            let mut query_params: QueryParams = QueryParams {
+                default_limit,
+                ..QueryParams::default()
+            };
 
             let mut nested_entity_stack: Vec<String> = Vec::new();
 
+            // Tables joined from a nullable field, or from a table that is itself
+            // joined from one, might have no row to join for a given parent row.
+            let mut nullable_tables: HashSet<String> = HashSet::new();
+
+            // Used to give each table in a derived field subquery a unique alias.
+            let mut derived_count = 0;
+
             // Selections can have their own set of subselections and so on, so a queue
             // is created with the first level of selections. In order to track the containing
             // entity of the selection, an entity list of the same length is created.
@@ -345,6 +1053,39 @@ impl Operation {
                     );
                 }
 
+                // Aggregates select functions over the entity's columns, rather than
+                // the entity's fields, so there's nothing left to resolve.
+                if query_params.aggregate {
+                    elements.append(&mut aggregate_elements(
+                        schema,
+                        &format!("{namespace}_{identifier}"),
+                        &entity_name,
+                        &selections,
+                    ));
+                    queue.clear();
+                    entities.clear();
+                }
+
+                // The `_meta` field reports on the indexer from the registry and from
+                // its metadata, rather than selecting from an entity's table.
+                if query_params.meta {
+                    elements.append(&mut meta_elements(
+                        namespace,
+                        identifier,
+                        &selections,
+                    ));
+                    queue.clear();
+                    entities.clear();
+                }
+
+                // Results of an interface query are told apart by the implementor they're from.
+                if let Some(typename) = typename {
+                    elements.push(QueryElement::Field {
+                        key: "__typename".to_string(),
+                        value: format!("'{typename}'"),
+                    });
+                }
+
                 let mut last_seen_entities_len = entities.len();
 
                 while let Some(current) = queue.pop() {
@@ -373,10 +1114,19 @@ impl Operation {
                     } = current
                     {
                         if subselections.selections.is_empty() {
+                            let cast = if is_arbitrary_precision_field(
+                                schema,
+                                &entity_name,
+                                &field_name,
+                            ) {
+                                "::text"
+                            } else {
+                                ""
+                            };
                             elements.push(QueryElement::Field {
                                 key: alias.unwrap_or(field_name.clone()),
                                 value: format!(
-                                    "{namespace}_{identifier}.{entity_name}.{field_name}"
+                                    "{namespace}_{identifier}.{entity_name}.{field_name}{cast}"
                                 ),
                             });
                             if !filters.is_empty() {
@@ -385,8 +1135,26 @@ impl Operation {
                                     format!("{namespace}_{identifier}.{entity_name}"),
                                 );
                             }
+                        } else if let Some(subquery) = derived_field_subquery(
+                            schema,
+                            &format!("{namespace}_{identifier}"),
+                            &format!("{namespace}_{identifier}.{entity_name}"),
+                            &entity_name,
+                            &field_name,
+                            &filters,
+                            &subselections,
+                            &query_params,
+                            &mut derived_count,
+                        ) {
+                            // Derived fields are aggregated by a correlated subquery, rather
+                            // than joined, since there can be many child rows per parent row.
+                            elements.push(QueryElement::Field {
+                                key: alias.unwrap_or(field_name.clone()),
+                                value: subquery,
+                            });
                         } else {
                             let mut new_entity = field_name.clone();
+                            let mut null_column = None;
                             // If the current entity has a foreign key on the current
                             // selection, join the foreign table on that primary key
                             // and set the field as the innermost entity by pushing to the stack.
@@ -398,10 +1166,18 @@ impl Operation {
                                 if let Some((foreign_key_table, foreign_key_col)) =
                                     field_to_foreign_key.get(&field_name.to_lowercase())
                                 {
+                                    let referencing_key_table =
+                                        format!("{namespace}_{identifier}.{entity_name}");
+                                    let optional = is_optional_field(
+                                        schema,
+                                        &entity_name,
+                                        &field_name,
+                                    );
                                     let join_condition = JoinCondition {
-                                        referencing_key_table: format!(
-                                            "{namespace}_{identifier}.{entity_name}"
-                                        ),
+                                        nullable: optional
+                                            || nullable_tables
+                                                .contains(&referencing_key_table),
+                                        referencing_key_table,
                                         referencing_key_col: field_name.clone(),
                                         primary_key_table: format!(
                                             "{namespace}_{identifier}.{foreign_key_table}"
@@ -409,6 +1185,22 @@ impl Operation {
                                         primary_key_col: foreign_key_col.clone(),
                                     };
 
+                                    if join_condition.nullable {
+                                        nullable_tables.insert(
+                                            join_condition.primary_key_table.clone(),
+                                        );
+                                    }
+
+                                    // A missing related record is returned as a null
+                                    // object, rather than an object of null fields.
+                                    if optional {
+                                        null_column = Some(format!(
+                                            "{}.{}",
+                                            join_condition.primary_key_table,
+                                            join_condition.primary_key_col
+                                        ));
+                                    }
+
                                     // Joins are modelled like a directed graph in
                                     // order to ensure that tables can be joined in
                                     // a dependent order, if necessary.
@@ -488,6 +1280,7 @@ impl Operation {
 
                             elements.push(QueryElement::ObjectOpeningBoundary {
                                 key: alias.unwrap_or(field_name.clone()),
+                                null_column,
                             });
 
                             queue.append(&mut subselections.get_selections());
@@ -505,6 +1298,23 @@ impl Operation {
                     ]);
                 }
 
+                // Versioned tables keep a row for each version of a record, so only
+                // the version that was current as of the query's block height is selected.
+                let mut joined_tables = joins.keys().cloned().collect::<Vec<String>>();
+                joined_tables.sort();
+                let (nullable_versioned_tables, versioned_tables) =
+                    std::iter::once(format!("{namespace}_{identifier}.{entity_name}"))
+                        .chain(joined_tables)
+                        .filter(|t| {
+                            t.rsplit('.')
+                                .next()
+                                .map(|table| is_versioned_table(schema, table))
+                                .unwrap_or(false)
+                        })
+                        .partition(|t| nullable_tables.contains(t));
+                query_params.versioned_tables = versioned_tables;
+                query_params.nullable_versioned_tables = nullable_versioned_tables;
+
                 let query = UserQuery {
                     elements,
                     joins,
@@ -522,17 +1332,500 @@ impl Operation {
     }
 }
 
-#[derive(Debug)]
-pub struct GraphqlQuery {
-    operations: Vec<Operation>,
+/// Replace each root selection of an interface with a selection of each of its
+/// implementors, paired with the name of the implementor.
+fn expand_interfaces(
+    schema: &IndexerSchema,
+    selections: &Selections,
+) -> Vec<(Selection, Option<String>)> {
+    let mut expanded = Vec::new();
+
+    for selection in selections.get_selections() {
+        let interface = match &selection {
+            Selection::Field { name, .. } => schema
+                .parsed()
+                .graphql_type(None, name)
+                .filter(|typ| schema.parsed().is_interface_typedef(typ)),
+            _ => None,
+        };
+
+        match (selection, interface) {
+            (
+                Selection::Field {
+                    params,
+                    sub_selections,
+                    alias,
+                    name,
+                },
+                Some(interface),
+            ) => {
+                for implementor in schema.parsed().interface_implementors(interface) {
+                    expanded.push((
+                        Selection::Field {
+                            name: implementor.to_lowercase(),
+                            params: params.clone(),
+                            sub_selections: sub_selections.for_implementor(implementor),
+                            alias: Some(alias.clone().unwrap_or(name.clone())),
+                        },
+                        Some(implementor.to_string()),
+                    ));
+                }
+            }
+            (selection, _) => expanded.push((selection, None)),
+        }
+    }
+
+    expanded
 }
 
-impl GraphqlQuery {
-    pub fn parse(&self, schema: &IndexerSchema) -> Vec<UserQuery> {
+/// If `field_name` is a `@derivedFrom` field of `entity_name`, return a correlated
+/// subquery that aggregates the selected fields of the child rows into a JSON array.
+///
+/// The child rows are paginated by the `first` and `offset` arguments of the field,
+/// in the order of their IDs. If `first` isn't given, the query's default limit is used.
+#[allow(clippy::too_many_arguments)]
+fn derived_field_subquery(
+    schema: &IndexerSchema,
+    namespace_identifier: &str,
+    parent_table: &str,
+    entity_name: &str,
+    field_name: &str,
+    params: &[ParamType],
+    selections: &Selections,
+    query_params: &QueryParams,
+    count: &mut usize,
+) -> Option<String> {
+    let parent = schema
+        .parsed()
+        .graphql_type(None, &entity_name.to_lowercase())?;
+    let (child, child_field) = schema
+        .parsed()
+        .derived_field_mappings()
+        .get(&format!("{parent}.{field_name}"))?;
+
+    let child_table = child.to_lowercase();
+    let (_, ref_col) = schema
+        .parsed()
+        .foreign_key_mappings()
+        .get(&child_table)?
+        .get(child_field)?;
+
+    *count += 1;
+    let alias = format!("derived_{count}");
+
+    let fields = json_object_fields(
+        schema,
+        namespace_identifier,
+        &alias,
+        &child_table,
+        selections,
+        query_params,
+        count,
+    );
+
+    let condition = subquery_condition(
+        schema,
+        &alias,
+        &child_table,
+        format!("{alias}.{child_field} = {parent_table}.{ref_col}"),
+        query_params,
+    );
+
+    let limit = params
+        .iter()
+        .find_map(|param| match param {
+            ParamType::Limit(n) => Some(*n),
+            _ => None,
+        })
+        .or(query_params.default_limit);
+    let offset = params.iter().find_map(|param| match param {
+        ParamType::Offset(n) => Some(*n),
+        _ => None,
+    });
+
+    if limit.is_none() && offset.is_none() {
+        return Some(format!(
+            "(SELECT COALESCE(json_agg(json_build_object({fields})), '[]'::json) FROM {namespace_identifier}.{child_table} AS {alias} WHERE {condition})"
+        ));
+    }
+
+    // The page of child rows is selected first, and then aggregated in the same order.
+    let id = format!("{alias}.{}", IdCol::to_lowercase_str());
+    let limit = limit.map(|n| format!(" LIMIT {n}")).unwrap_or_default();
+    let offset = offset.map(|n| format!(" OFFSET {n}")).unwrap_or_default();
+    Some(format!(
+        "(SELECT COALESCE(json_agg(json_build_object({fields}) ORDER BY {id}), '[]'::json) FROM (SELECT * FROM {namespace_identifier}.{child_table} AS {alias} WHERE {condition} ORDER BY {id}{limit}{offset}) AS {alias})"
+    ))
+}
+
+/// Return the `WHERE` condition of a correlated subquery on `table`, which is
+/// aliased as `alias`.
+///
+/// If `table` is versioned, only the version of each row that was current as of
+/// the query's block height is selected.
+fn subquery_condition(
+    schema: &IndexerSchema,
+    alias: &str,
+    table: &str,
+    condition: String,
+    query_params: &QueryParams,
+) -> String {
+    if is_versioned_table(schema, table) {
+        format!(
+            "{condition} AND {}",
+            query_params.get_block_range_expression(alias, &DbType::Postgres)
+        )
+    } else {
+        condition
+    }
+}
+
+/// Whether the table with the given name is the table of a `@versioned` type.
+fn is_versioned_table(schema: &IndexerSchema, table: &str) -> bool {
+    schema
+        .parsed()
+        .graphql_type(None, table)
+        .map(|typ| schema.parsed().is_versioned_typedef(typ))
+        .unwrap_or(false)
+}
+
+/// Whether `field_name` on `entity_name` is a nullable field.
+fn is_optional_field(
+    schema: &IndexerSchema,
+    entity_name: &str,
+    field_name: &str,
+) -> bool {
+    schema
+        .parsed()
+        .graphql_type(None, &entity_name.to_lowercase())
+        .and_then(|typ| {
+            schema
+                .parsed()
+                .field_type_optionality()
+                .get(&field_id(typ, field_name))
+        })
+        .copied()
+        .unwrap_or(false)
+}
+
+/// Whether `field_name` on `entity_name` is stored as an arbitrary-precision `numeric`.
+fn is_arbitrary_precision_field(
+    schema: &IndexerSchema,
+    entity_name: &str,
+    field_name: &str,
+) -> bool {
+    schema
+        .parsed()
+        .graphql_type(None, &entity_name.to_lowercase())
+        .and_then(|typ| schema.parsed().graphql_type(Some(typ), field_name))
+        .map(|typ| matches!(typ.replace('!', "").as_str(), "UInt32" | "BigDecimal"))
+        .unwrap_or(false)
+}
+
+/// Return the elements of a `_meta` query, with an object for each selected report.
+///
+/// The last block committed by the indexer is selected from its `IndexMetadataEntity`
+/// table as `_meta_block`, which is NULL if the indexer hasn't committed a block yet.
+fn meta_elements(
+    namespace: &str,
+    identifier: &str,
+    selections: &Selections,
+) -> Vec<QueryElement> {
+    let literal = |s: &str| format!("'{}'", s.replace('\'', "''"));
+    let (namespace, identifier) = (literal(namespace), literal(identifier));
+
+    let mut elements = Vec::new();
+    for selection in selections.get_selections() {
+        let Selection::Field {
+            name,
+            sub_selections,
+            alias,
+            ..
+        } = selection
+        else {
+            continue;
+        };
+
+        elements.push(QueryElement::ObjectOpeningBoundary {
+            key: alias.unwrap_or(name.clone()),
+            null_column: (name == "block").then(|| "_meta_block.id".to_string()),
+        });
+        for selection in sub_selections.get_selections() {
+            let Selection::Field {
+                name: field_name,
+                alias,
+                ..
+            } = selection
+            else {
+                continue;
+            };
+
+            let value = match field_name.as_str() {
+                "height" => "_meta_block.block_height".to_string(),
+                "id" => "_meta_block.block_id".to_string(),
+                "timestamp" => "_meta_block.time".to_string(),
+                "namespace" => namespace.clone(),
+                "identifier" => identifier.clone(),
+                "version" => format!(
+                    "(SELECT version FROM graph_registry_graph_root \
+                    WHERE schema_name = {namespace} AND schema_identifier = {identifier} \
+                    ORDER BY id DESC LIMIT 1)"
+                ),
+                _ => format!(
+                    "EXISTS (SELECT 1 FROM index_errors \
+                    INNER JOIN index_registry ON index_errors.index_id = index_registry.id \
+                    WHERE index_registry.namespace = {namespace} \
+                    AND index_registry.identifier = {identifier})"
+                ),
+            };
+            elements.push(QueryElement::Field {
+                key: alias.unwrap_or(field_name),
+                value,
+            });
+        }
+        elements.push(QueryElement::ObjectClosingBoundary);
+    }
+
+    elements
+}
+
+/// Return the elements of an aggregate query of `entity_name`, with a field for each
+/// selected aggregate function or group key.
+fn aggregate_elements(
+    schema: &IndexerSchema,
+    namespace_identifier: &str,
+    entity_name: &str,
+    selections: &Selections,
+) -> Vec<QueryElement> {
+    let mut elements = Vec::new();
+
+    for selection in selections.get_selections() {
+        let Selection::Field {
+            name,
+            sub_selections,
+            alias,
+            ..
+        } = selection
+        else {
+            continue;
+        };
+
+        let key = alias.unwrap_or(name.clone());
+        if name == "count" {
+            elements.push(QueryElement::Field {
+                key,
+                value: "COUNT(*)".to_string(),
+            });
+            continue;
+        }
+
+        elements.push(QueryElement::ObjectOpeningBoundary {
+            key,
+            null_column: None,
+        });
+        for selection in sub_selections.get_selections() {
+            let Selection::Field {
+                name: field_name,
+                alias,
+                ..
+            } = selection
+            else {
+                continue;
+            };
+
+            let column = format!("{namespace_identifier}.{entity_name}.{field_name}");
+            let value = match name.as_str() {
+                "keys" => column,
+                function => format!("{}({column})", function.to_uppercase()),
+            };
+
+            // Averages are floats, but other values of arbitrary precision fields are
+            // returned as text, so they're not truncated to a float.
+            let value = if name != "avg"
+                && is_arbitrary_precision_field(schema, entity_name, &field_name)
+            {
+                format!("{value}::text")
+            } else {
+                value
+            };
+            elements.push(QueryElement::Field {
+                key: alias.unwrap_or(field_name),
+                value,
+            });
+        }
+        elements.push(QueryElement::ObjectClosingBoundary);
+    }
+
+    elements
+}
+
+/// Return the `json_build_object` arguments for the selected fields of a row of
+/// `entity_name`, where `table` refers to that row.
+///
+/// Foreign key objects and derived fields are resolved with correlated subqueries.
+fn json_object_fields(
+    schema: &IndexerSchema,
+    namespace_identifier: &str,
+    table: &str,
+    entity_name: &str,
+    selections: &Selections,
+    query_params: &QueryParams,
+    count: &mut usize,
+) -> String {
+    let mut fields = Vec::new();
+
+    for selection in selections.get_selections() {
+        let Selection::Field {
+            name,
+            params,
+            sub_selections,
+            alias,
+        } = selection
+        else {
+            continue;
+        };
+
+        let key = alias.unwrap_or(name.clone());
+
+        if sub_selections.selections.is_empty() {
+            if is_arbitrary_precision_field(schema, entity_name, &name) {
+                // Returned as text, so these values are not truncated to a float.
+                fields.push(format!("'{key}', {table}.{name}::text"));
+            } else {
+                fields.push(format!("'{key}', {table}.{name}"));
+            }
+            continue;
+        }
+
+        if let Some(subquery) = derived_field_subquery(
+            schema,
+            namespace_identifier,
+            table,
+            entity_name,
+            &name,
+            &params,
+            &sub_selections,
+            query_params,
+            count,
+        ) {
+            fields.push(format!("'{key}', {subquery}"));
+            continue;
+        }
+
+        if let Some((fk_table, fk_col)) = schema
+            .parsed()
+            .foreign_key_mappings()
+            .get(entity_name)
+            .and_then(|fks| fks.get(&name.to_lowercase()))
+        {
+            *count += 1;
+            let fk_alias = format!("derived_{count}");
+            let fk_fields = json_object_fields(
+                schema,
+                namespace_identifier,
+                &fk_alias,
+                fk_table,
+                &sub_selections,
+                query_params,
+                count,
+            );
+            let condition = subquery_condition(
+                schema,
+                &fk_alias,
+                fk_table,
+                format!("{fk_alias}.{fk_col} = {table}.{name}"),
+                query_params,
+            );
+            fields.push(format!(
+                "'{key}', (SELECT json_build_object({fk_fields}) FROM {namespace_identifier}.{fk_table} AS {fk_alias} WHERE {condition})"
+            ));
+        }
+    }
+
+    fields.join(", ")
+}
+
+/// Limits on the size of the GraphQL queries that are run, and of their results.
+///
+/// Limits that aren't set are unlimited.
+#[derive(Clone, Debug, Default)]
+pub struct QueryLimits {
+    /// Max depth of nested entities.
+    pub max_depth: Option<usize>,
+
+    /// Max number of related entities that are selected or filtered by.
+    pub max_joins: Option<usize>,
+
+    /// Number of records returned by queries that don't request a number themselves.
+    ///
+    /// Defaults to `max_limit`, so that such queries are bound as well.
+    pub default_limit: Option<u64>,
+
+    /// Max number of records that can be requested with `first` or `last`.
+    pub max_limit: Option<u64>,
+
+    /// Max number of milliseconds that each SQL statement can run for.
+    pub timeout: Option<u64>,
+}
+
+impl From<&WebApiConfig> for QueryLimits {
+    fn from(config: &WebApiConfig) -> Self {
+        Self {
+            max_depth: config.max_query_depth,
+            max_joins: config.max_query_joins,
+            default_limit: config.default_query_limit,
+            max_limit: config.max_query_limit,
+            timeout: config.query_timeout,
+        }
+    }
+}
+
+impl QueryLimits {
+    /// Number of records returned by queries that don't request a number themselves.
+    fn effective_default_limit(&self) -> Option<u64> {
+        self.default_limit.or(self.max_limit)
+    }
+
+    /// Return an error if the (resolved) selections of an operation exceed any limit.
+    fn check(&self, selections: &Selections) -> GraphqlResult<()> {
+        if let Some(max_depth) = self.max_depth {
+            let depth = selections.depth();
+            if depth > max_depth {
+                return Err(GraphqlError::MaxDepthExceeded(depth, max_depth));
+            }
+        }
+
+        if let Some(max_joins) = self.max_joins {
+            let joins = selections.joins();
+            if joins > max_joins {
+                return Err(GraphqlError::MaxJoinsExceeded(joins, max_joins));
+            }
+        }
+
+        if let (Some(max_limit), Some(requested)) =
+            (self.max_limit, selections.max_requested())
+        {
+            if requested > max_limit {
+                return Err(GraphqlError::MaxLimitExceeded(requested, max_limit));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct GraphqlQuery {
+    operations: Vec<Operation>,
+    default_limit: Option<u64>,
+}
+
+impl GraphqlQuery {
+    pub fn parse(&self, schema: &IndexerSchema) -> Vec<UserQuery> {
         let queries: Vec<UserQuery> = self
             .operations
             .iter()
-            .flat_map(|o| o.parse(schema))
+            .flat_map(|o| o.parse_with_default_limit(schema, self.default_limit))
             .collect::<Vec<UserQuery>>();
 
         queries
@@ -547,7 +1840,7 @@ impl GraphqlQuery {
 
         queries
             .into_iter()
-            .map(|mut q| q.to_sql(&db_type))
+            .map(|q| q.to_sql(&db_type))
             .collect::<Result<Vec<String>, GraphqlError>>()
     }
 }
@@ -555,6 +1848,7 @@ impl GraphqlQuery {
 pub struct GraphqlQueryBuilder<'a> {
     schema: &'a IndexerSchema,
     document: ExecutableDocument,
+    limits: QueryLimits,
 }
 
 impl<'a> GraphqlQueryBuilder<'a> {
@@ -563,27 +1857,102 @@ impl<'a> GraphqlQueryBuilder<'a> {
         query: &'a str,
     ) -> GraphqlResult<GraphqlQueryBuilder<'a>> {
         let document = parse_query::<&str>(query)?;
-        Ok(GraphqlQueryBuilder { schema, document })
+        Ok(GraphqlQueryBuilder {
+            schema,
+            document,
+            limits: QueryLimits::default(),
+        })
+    }
+
+    /// Create a builder for a query that has already been parsed.
+    pub fn from_document(
+        schema: &'a IndexerSchema,
+        document: ExecutableDocument,
+    ) -> GraphqlQueryBuilder<'a> {
+        GraphqlQueryBuilder {
+            schema,
+            document,
+            limits: QueryLimits::default(),
+        }
+    }
+
+    /// Limit the size of the queries that are built, and of their results.
+    pub fn with_limits(mut self, limits: QueryLimits) -> Self {
+        self.limits = limits;
+        self
     }
 
     pub fn build(self) -> GraphqlResult<GraphqlQuery> {
         let fragments = self.process_fragments()?;
-        let operations = self.process_operations(fragments)?;
-        Ok(GraphqlQuery { operations })
+        let operations = self.process_operations(fragments, OperationType::Query)?;
+        Ok(GraphqlQuery {
+            operations,
+            default_limit: self.limits.effective_default_limit(),
+        })
+    }
+
+    /// Build the subscriptions of the document. Each subscription is parsed like a
+    /// query, whose results are returned again whenever they change.
+    pub fn build_subscription(self) -> GraphqlResult<GraphqlQuery> {
+        let fragments = self.process_fragments()?;
+        let operations =
+            self.process_operations(fragments, OperationType::Subscription)?;
+        // Subscribers are sent every record that changed, however many there are.
+        Ok(GraphqlQuery {
+            operations,
+            default_limit: None,
+        })
+    }
+
+    /// Whether the document contains a subscription, rather than a query.
+    pub fn is_subscription(&self) -> bool {
+        self.document
+            .operations
+            .iter()
+            .any(|(_, operation)| operation.node.ty == OperationType::Subscription)
     }
 
     fn process_operation(
         &self,
         operation: &OperationDefinition,
         fragments: &HashMap<String, Fragment>,
+        operation_type: OperationType,
     ) -> GraphqlResult<Operation> {
         match operation.ty {
-            OperationType::Query => {
+            ty @ (OperationType::Query | OperationType::Subscription)
+                if ty == operation_type =>
+            {
                 // TODO: directives and variable definitions....
                 let OperationDefinition { selection_set, .. } = operation;
                 let mut selections =
                     Selections::new(self.schema, None, &selection_set.node)?;
                 selections.resolve_fragments(self.schema, None, fragments)?;
+                self.limits.check(&selections)?;
+
+                // Subscribers are sent the records that changed, so there's nothing
+                // to aggregate or paginate.
+                if ty == OperationType::Subscription {
+                    for selection in selections.get_selections() {
+                        if let Selection::Field { params, alias, .. } = selection {
+                            if params.iter().any(|p| {
+                                matches!(
+                                    p,
+                                    ParamType::Aggregate
+                                        | ParamType::Connection
+                                        | ParamType::Limit(_)
+                                        | ParamType::Offset(_)
+                                )
+                            }) {
+                                return Err(GraphqlError::OperationNotSupported(
+                                    format!(
+                                        "Subscription to {}",
+                                        alias.unwrap_or_default()
+                                    ),
+                                ));
+                            }
+                        }
+                    }
+                }
 
                 Ok(Operation::new(
                     self.schema.parsed().namespace().to_string(),
@@ -591,6 +1960,9 @@ impl<'a> GraphqlQueryBuilder<'a> {
                     selections,
                 ))
             }
+            OperationType::Query => {
+                Err(GraphqlError::OperationNotSupported("Query".into()))
+            }
             OperationType::Mutation => {
                 Err(GraphqlError::OperationNotSupported("Mutation".into()))
             }
@@ -603,17 +1975,26 @@ impl<'a> GraphqlQueryBuilder<'a> {
     fn process_operations(
         &self,
         fragments: HashMap<String, Fragment>,
+        operation_type: OperationType,
     ) -> GraphqlResult<Vec<Operation>> {
         let mut operations = vec![];
 
         match &self.document.operations {
             DocumentOperations::Single(operation_def) => {
-                let op = self.process_operation(&operation_def.node, &fragments)?;
+                let op = self.process_operation(
+                    &operation_def.node,
+                    &fragments,
+                    operation_type,
+                )?;
                 operations.push(op);
             }
             DocumentOperations::Multiple(operation_map) => {
                 for (_name, operation_def) in operation_map.iter() {
-                    let op = self.process_operation(&operation_def.node, &fragments)?;
+                    let op = self.process_operation(
+                        &operation_def.node,
+                        &fragments,
+                        operation_type,
+                    )?;
                     operations.push(op);
                 }
             }
@@ -781,6 +2162,9 @@ type Tx @entity {
             elements: vec![
                 QueryElement::ObjectOpeningBoundary {
                     key: "block".to_string(),
+                    null_column: Some(
+                        "fuel_indexer_test_test_index.block.id".to_string(),
+                    ),
                 },
                 QueryElement::Field {
                     key: "height".to_string(),
@@ -813,6 +2197,7 @@ type Tx @entity {
                                 primary_key_table: "fuel_indexer_test_test_index.block"
                                     .to_string(),
                                 primary_key_col: "id".to_string(),
+                                nullable: true,
                             },
                         )]),
                         dependents: HashMap::new(),
@@ -830,6 +2215,7 @@ type Tx @entity {
                                 primary_key_table: "fuel_indexer_test_test_index.block"
                                     .to_string(),
                                 primary_key_col: "id".to_string(),
+                                nullable: true,
                             },
                         )]),
                         dependencies: HashMap::new(),
@@ -843,4 +2229,828 @@ type Tx @entity {
         }];
         assert_eq!(expected, operation.parse(&schema));
     }
+
+    #[test]
+    fn test_operation_parse_derived_field_into_subquery() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    label: Charfield!
+    positions: [Position!]! @derivedFrom(field: "account")
+}
+
+type Position @entity {
+    id: ID!
+    amount: UInt8!
+    account: Account!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let query = GraphqlQueryBuilder::new(
+            &schema,
+            "query { account { id positions { id amount } } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+
+        let queries = query.parse(&schema);
+        assert_eq!(
+            queries[0].elements,
+            vec![
+                QueryElement::Field {
+                    key: "id".to_string(),
+                    value: "fuel_indexer_test_test_index.account.id".to_string(),
+                },
+                QueryElement::Field {
+                    key: "positions".to_string(),
+                    value: "(SELECT COALESCE(json_agg(json_build_object('id', derived_1.id, 'amount', derived_1.amount)), '[]'::json) FROM fuel_indexer_test_test_index.position AS derived_1 WHERE derived_1.account = fuel_indexer_test_test_index.account.id)".to_string(),
+                },
+            ]
+        );
+        assert!(queries[0].joins.is_empty());
+
+        // Derived fields are paginated like list queries, in the order of their IDs.
+        let query = GraphqlQueryBuilder::new(
+            &schema,
+            "query { account { positions(first: 2, offset: 4) { id } } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap();
+        assert_eq!(
+            query.parse(&schema)[0].elements[0],
+            QueryElement::Field {
+                key: "positions".to_string(),
+                value: "(SELECT COALESCE(json_agg(json_build_object('id', derived_1.id) ORDER BY derived_1.id), '[]'::json) FROM (SELECT * FROM fuel_indexer_test_test_index.position AS derived_1 WHERE derived_1.account = fuel_indexer_test_test_index.account.id ORDER BY derived_1.id LIMIT 2 OFFSET 4) AS derived_1)".to_string(),
+            }
+        );
+
+        // They take the default limit, and are bound by the max limit, of the query.
+        let limits = QueryLimits {
+            default_limit: Some(10),
+            max_limit: Some(20),
+            ..QueryLimits::default()
+        };
+        let query =
+            GraphqlQueryBuilder::new(&schema, "query { account { positions { id } } }")
+                .unwrap()
+                .with_limits(limits.clone())
+                .build()
+                .unwrap();
+        assert!(matches!(
+            &query.parse(&schema)[0].elements[0],
+            QueryElement::Field { value, .. } if value.contains("ORDER BY derived_1.id LIMIT 10)")
+        ));
+        assert!(matches!(
+            GraphqlQueryBuilder::new(
+                &schema,
+                "query { account { positions(first: 50) { id } } }",
+            )
+            .unwrap()
+            .with_limits(limits)
+            .build(),
+            Err(GraphqlError::MaxLimitExceeded(50, 20))
+        ));
+
+        assert!(GraphqlQueryBuilder::new(
+            &schema,
+            "query { account { positions(order: { amount: asc }) { id } } }",
+        )
+        .and_then(|q| q.build())
+        .is_err());
+    }
+
+    #[test]
+    fn test_operation_parse_versioned_entity_as_of_block() {
+        let schema = r#"
+type Wallet @entity @versioned {
+    id: ID!
+    balance: UInt8!
+}
+
+type Account @entity {
+    id: ID!
+    wallet: Wallet!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            "query { wallet(block: { number: 10 }) { id balance } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert_eq!(queries[0].query_params.block, Some(10));
+        assert_eq!(
+            queries[0].to_sql(&DbType::Postgres).unwrap(),
+            "SELECT json_build_object('id', fuel_indexer_test_test_index.wallet.id, 'balance', fuel_indexer_test_test_index.wallet.balance) FROM fuel_indexer_test_test_index.wallet  WHERE  fuel_indexer_test_test_index.wallet._block_range @> 10::bigint "
+        );
+
+        // Joined versioned tables are queried at their current version by default.
+        let queries = GraphqlQueryBuilder::new(
+            &schema,
+            "query { account { id wallet { balance } } }",
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert_eq!(
+            queries[0].query_params.versioned_tables,
+            vec!["fuel_indexer_test_test_index.wallet".to_string()]
+        );
+        assert_eq!(
+            queries[0]
+                .query_params
+                .get_filtering_expression(&DbType::Postgres),
+            "WHERE  upper_inf(fuel_indexer_test_test_index.wallet._block_range)"
+        );
+
+        // Only versioned entities can be queried as of a block height.
+        assert!(GraphqlQueryBuilder::new(
+            &schema,
+            "query { account(block: { number: 10 }) { id } }",
+        )
+        .and_then(|q| q.build())
+        .is_err());
+    }
+
+    #[test]
+    fn test_operation_parse_filter_on_related_entities() {
+        let schema = r#"
+type Token @entity {
+    id: ID!
+    symbol: Charfield!
+}
+
+type Swap @entity {
+    id: ID!
+    token: Token!
+}
+
+type Pool @entity {
+    id: ID!
+    tokens: [Token!]!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { swap(filter: { token: { symbol: { equals: "ETH" } } }) { id } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert_eq!(
+            queries[0]
+                .query_params
+                .get_filtering_expression(&DbType::Postgres),
+            "WHERE  EXISTS (SELECT 1 FROM fuel_indexer_test_test_index.token AS swap_token WHERE swap_token.id = fuel_indexer_test_test_index.swap.token AND (swap_token.symbol = 'ETH'))"
+        );
+
+        // Comparisons against the foreign key column itself are still supported.
+        let queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { swap(filter: { token: { equals: "1" } }) { id } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert_eq!(
+            queries[0]
+                .query_params
+                .get_filtering_expression(&DbType::Postgres),
+            "WHERE  fuel_indexer_test_test_index.swap.token = '1'"
+        );
+
+        let queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { pool(filter: { tokens: { every: { symbol: { equals: "ETH" } } } }) { id } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert_eq!(
+            queries[0]
+                .query_params
+                .get_filtering_expression(&DbType::Postgres),
+            "WHERE  NOT EXISTS (SELECT 1 FROM fuel_indexer_test_test_index.pools_tokens AS pool_tokens_join INNER JOIN fuel_indexer_test_test_index.token AS pool_tokens ON pool_tokens.id = pool_tokens_join.token_id WHERE pool_tokens_join.pool_id = fuel_indexer_test_test_index.pool.id AND (pool_tokens.symbol = 'ETH') IS NOT TRUE)"
+        );
+
+        // List fields have to be quantified.
+        assert!(GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { pool(filter: { tokens: { symbol: { equals: "ETH" } } }) { id } }"#,
+        )
+        .and_then(|q| q.build())
+        .is_err());
+    }
+
+    #[test]
+    fn test_operation_parse_with_query_limits() {
+        let schema = r#"
+type Token @entity {
+    id: ID!
+    symbol: Charfield!
+}
+
+type Pool @entity {
+    id: ID!
+    token: Token!
+}
+
+type Swap @entity {
+    id: ID!
+    pool: Pool!
+    amount: U64!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let build = |query: &str, limits: QueryLimits| {
+            GraphqlQueryBuilder::new(&schema, query)
+                .unwrap()
+                .with_limits(limits)
+                .build()
+        };
+
+        let nested = "query { swap { id pool { id token { symbol } } } }";
+        let limits = |max_depth| QueryLimits {
+            max_depth: Some(max_depth),
+            ..QueryLimits::default()
+        };
+        assert!(build(nested, limits(3)).is_ok());
+        assert!(matches!(
+            build(nested, limits(2)),
+            Err(GraphqlError::MaxDepthExceeded(3, 2))
+        ));
+
+        // Related entities are joined both to be selected and to be filtered by.
+        let joined = r#"query { swap(filter: { pool: { token: { symbol: { equals: "ETH" } } } }) { pool { id } } }"#;
+        let limits = |max_joins| QueryLimits {
+            max_joins: Some(max_joins),
+            ..QueryLimits::default()
+        };
+        assert!(build(joined, limits(3)).is_ok());
+        assert!(matches!(
+            build(joined, limits(2)),
+            Err(GraphqlError::MaxJoinsExceeded(3, 2))
+        ));
+
+        let limits = QueryLimits {
+            default_limit: Some(10),
+            max_limit: Some(20),
+            ..QueryLimits::default()
+        };
+        assert!(matches!(
+            build(
+                "query { swap(order: { amount: asc }, first: 50) { id } }",
+                limits.clone()
+            ),
+            Err(GraphqlError::MaxLimitExceeded(50, 20))
+        ));
+        assert!(matches!(
+            build(
+                "query { swapConnection(last: 50) { edges { cursor } } }",
+                limits.clone()
+            ),
+            Err(GraphqlError::MaxLimitExceeded(50, 20))
+        ));
+
+        // Queries that don't request a number of records return the default number.
+        let sql = build("query { swap { id } }", limits.clone())
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+        assert!(
+            sql[0].ends_with("ORDER BY fuel_indexer_test_test_index.swap.id LIMIT 10")
+        );
+
+        // An explicit order is kept.
+        let sql = build(
+            "query { swap(order: { amount: desc }) { id } }",
+            limits.clone(),
+        )
+        .unwrap()
+        .as_sql(&schema, DbType::Postgres)
+        .unwrap();
+        assert!(sql[0].ends_with(
+            "ORDER BY fuel_indexer_test_test_index.swap.amount DESC LIMIT 10"
+        ));
+
+        // Without a default limit, queries return at most the max number of records.
+        let max_only = QueryLimits {
+            max_limit: Some(20),
+            ..QueryLimits::default()
+        };
+        let sql = build("query { swap { id } }", max_only)
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+        assert!(
+            sql[0].ends_with("ORDER BY fuel_indexer_test_test_index.swap.id LIMIT 20")
+        );
+
+        let sql = build(
+            "query { swapConnection { edges { node { id } } } }",
+            limits.clone(),
+        )
+        .unwrap()
+        .as_sql(&schema, DbType::Postgres)
+        .unwrap();
+        assert!(sql[0].contains("LIMIT 11"));
+
+        let sql = build("query { swapAggregate { count } }", limits)
+            .unwrap()
+            .as_sql(&schema, DbType::Postgres)
+            .unwrap();
+        assert!(!sql[0].contains("LIMIT"));
+    }
+
+    #[test]
+    fn test_operation_parse_searchable_entity_with_search_text() {
+        let schema = r#"
+type Post @entity @search(fields: ["title", "body"]) {
+    id: ID!
+    title: Charfield!
+    body: Blob
+}
+
+type Comment @entity {
+    id: ID!
+    post: Post!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { post(search: "fuel's indexer") { id title } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert_eq!(
+            queries[0].to_sql(&DbType::Postgres).unwrap(),
+            "SELECT json_build_object('id', fuel_indexer_test_test_index.post.id, 'title', fuel_indexer_test_test_index.post.title) FROM fuel_indexer_test_test_index.post  WHERE  fuel_indexer_test_test_index.post._search @@ websearch_to_tsquery('english', 'fuel''s indexer') "
+        );
+
+        // Only entities with a `@search` directive can be searched.
+        assert!(GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { comment(search: "fuel") { id } }"#,
+        )
+        .and_then(|q| q.build())
+        .is_err());
+
+        // Nested entities can't be searched.
+        assert!(GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { comment { id post(search: "fuel") { id } } }"#,
+        )
+        .and_then(|q| q.build())
+        .is_err());
+    }
+
+    #[test]
+    fn test_operation_parse_connection_with_cursor() {
+        let schema = r#"
+type Swap @entity {
+    id: ID!
+    amount: UInt8!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        // The cursor of a swap with an amount of 500 and an `ID` of "abc".
+        let cursor = "5b22353030222c2022616263225d";
+
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            &format!(
+                r#"query {{ swapConnection(order: {{ amount: desc }}, first: 2, after: "{cursor}") {{ edges {{ node {{ id amount }} cursor }} pageInfo {{ hasNextPage endCursor }} }} }}"#
+            ),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+
+        assert_eq!(queries[0].entity_name, "swap");
+        assert_eq!(queries[0].alias, Some("swapConnection".to_string()));
+        assert!(queries[0].query_params.connection);
+        assert_eq!(queries[0].query_params.limit, Some(2));
+
+        let sql = queries[0].to_sql(&DbType::Postgres).unwrap();
+        assert!(sql.contains(
+            "WHERE ((fuel_indexer_test_test_index.swap.amount < '500') OR (fuel_indexer_test_test_index.swap.amount = '500' AND fuel_indexer_test_test_index.swap.id < 'abc'))"
+        ));
+        assert!(sql.contains(
+            "ORDER BY fuel_indexer_test_test_index.swap.amount DESC, fuel_indexer_test_test_index.swap.id DESC"
+        ));
+        assert!(sql.contains("LIMIT 3"));
+        assert!(!sql.contains("COUNT(*) as count"));
+
+        // Pages counted from the end are selected in reverse. Without an order, the
+        // cursor of a swap with an `ID` of "abc" only holds its `ID`.
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { swapConnection(last: 2, before: "5b22616263225d") { edges { node { id } } } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        let sql = queries[0].to_sql(&DbType::Postgres).unwrap();
+        assert!(sql.contains("WHERE ((fuel_indexer_test_test_index.swap.id < 'abc'))"));
+        assert!(sql.contains("ORDER BY fuel_indexer_test_test_index.swap.id DESC"));
+
+        // A cursor must match the order of the query.
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            &format!(
+                r#"query {{ swapConnection(first: 2, after: "{cursor}") {{ edges {{ cursor }} }} }}"#
+            ),
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert!(matches!(
+            queries[0].to_sql(&DbType::Postgres),
+            Err(GraphqlError::InvalidCursor(_))
+        ));
+
+        // Cursors only apply to connections, which don't take an offset.
+        for query in [
+            format!(r#"query {{ swap(first: 2, after: "{cursor}") {{ id }} }}"#),
+            "query { swapConnection(first: 2, offset: 2) { edges { cursor } } }"
+                .to_string(),
+            r#"query { swapConnection(after: "not a cursor") { edges { cursor } } }"#
+                .to_string(),
+        ] {
+            assert!(GraphqlQueryBuilder::new(&schema, &query)
+                .and_then(|q| q.build())
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_operation_parse_aggregate_with_group_by() {
+        let schema = r#"
+type Swap @entity {
+    id: ID!
+    pool: Charfield!
+    amount: UInt8!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { volumes: swapAggregate(filter: { amount: { gt: 10 } }, groupBy: [pool]) { keys { pool } count total: sum { amount } avg { amount } } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+
+        assert_eq!(queries[0].entity_name, "swap");
+        assert_eq!(queries[0].alias, Some("volumes".to_string()));
+        assert!(queries[0].query_params.aggregate);
+
+        let expected = "SELECT json_build_object('keys', json_build_object('pool', fuel_indexer_test_test_index.swap.pool), 'count', COUNT(*), 'total', json_build_object('amount', SUM(fuel_indexer_test_test_index.swap.amount)), 'avg', json_build_object('amount', AVG(fuel_indexer_test_test_index.swap.amount))) FROM fuel_indexer_test_test_index.swap  WHERE  fuel_indexer_test_test_index.swap.amount > 10 GROUP BY fuel_indexer_test_test_index.swap.pool ORDER BY fuel_indexer_test_test_index.swap.pool";
+        assert_eq!(queries[0].to_sql(&DbType::Postgres).unwrap(), expected);
+
+        // Functions only apply to numeric fields, keys must be grouped by, and
+        // aggregates aren't paginated.
+        for query in [
+            "query { swapAggregate { sum { pool } } }",
+            "query { swapAggregate { keys { pool } } }",
+            "query { swapAggregate(first: 2) { count } }",
+            "query { swapAggregate { median { amount } } }",
+            "query { swap(groupBy: [pool]) { id } }",
+        ] {
+            assert!(GraphqlQueryBuilder::new(&schema, query)
+                .and_then(|q| q.build())
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_operation_parse_aggregate_over_uint32_field() {
+        let schema = r#"
+type Vault @entity {
+    id: ID!
+    balance: UInt32!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { vaultAggregate { sum { balance } avg { balance } } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+
+        assert_eq!(
+            queries[0].to_sql(&DbType::Postgres).unwrap(),
+            "SELECT json_build_object('sum', json_build_object('balance', SUM(fuel_indexer_test_test_index.vault.balance)::text), 'avg', json_build_object('balance', AVG(fuel_indexer_test_test_index.vault.balance))) FROM fuel_indexer_test_test_index.vault   "
+        );
+    }
+
+    #[test]
+    fn test_operation_parse_aggregate_over_bigdecimal_field() {
+        let schema = r#"
+type Vault @entity {
+    id: ID!
+    rate: BigDecimal!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { vaultAggregate { min { rate } max { rate } } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+
+        assert_eq!(
+            queries[0].to_sql(&DbType::Postgres).unwrap(),
+            "SELECT json_build_object('min', json_build_object('rate', MIN(fuel_indexer_test_test_index.vault.rate)::text), 'max', json_build_object('rate', MAX(fuel_indexer_test_test_index.vault.rate)::text)) FROM fuel_indexer_test_test_index.vault   "
+        );
+    }
+
+    #[test]
+    fn test_operation_parse_subscription() {
+        let schema = r#"
+type Swap @entity {
+    id: ID!
+    amount: UInt8!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let builder = GraphqlQueryBuilder::new(
+            &schema,
+            "subscription { swap(filter: { amount: { gt: 10 } }) { id amount } }",
+        )
+        .unwrap();
+        assert!(builder.is_subscription());
+
+        let queries = builder.build_subscription().unwrap().parse(&schema);
+        assert_eq!(queries[0].entity_name, "swap");
+        assert_eq!(queries[0].query_params.filters.len(), 1);
+
+        // Subscriptions and queries are built separately.
+        assert!(GraphqlQueryBuilder::new(&schema, "query { swap { id } }")
+            .and_then(|q| q.build_subscription())
+            .is_err());
+        assert!(
+            GraphqlQueryBuilder::new(&schema, "subscription { swap { id } }")
+                .and_then(|q| q.build())
+                .is_err()
+        );
+
+        // Subscribers are only sent the records that changed, so those aren't
+        // aggregated or paginated.
+        for query in [
+            "subscription { swap(order: { amount: asc }, first: 5) { id } }",
+            "subscription { swapAggregate { count } }",
+            "subscription { swapConnection { edges { cursor } } }",
+        ] {
+            assert!(GraphqlQueryBuilder::new(&schema, query)
+                .and_then(|q| q.build_subscription())
+                .is_err());
+        }
+    }
+
+    #[test]
+    fn test_operation_parse_arbitrary_precision_fields() {
+        let schema = r#"
+type Vault @entity {
+    id: ID!
+    balance: UInt32!
+    rate: BigDecimal!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { vault(filter: { balance: { gt: "340282366920938463463374607431768211456" } }) { id balance rate } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert_eq!(
+            queries[0].to_sql(&DbType::Postgres).unwrap(),
+            "SELECT json_build_object('id', fuel_indexer_test_test_index.vault.id, 'balance', fuel_indexer_test_test_index.vault.balance::text, 'rate', fuel_indexer_test_test_index.vault.rate::text) FROM fuel_indexer_test_test_index.vault  WHERE  fuel_indexer_test_test_index.vault.balance > 340282366920938463463374607431768211456 "
+        );
+
+        let mut queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { vault(filter: { rate: { between: { min: "-0.5", max: 1 } } }) { id } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .parse(&schema);
+        assert_eq!(
+            queries[0].to_sql(&DbType::Postgres).unwrap(),
+            "SELECT json_build_object('id', fuel_indexer_test_test_index.vault.id) FROM fuel_indexer_test_test_index.vault  WHERE  fuel_indexer_test_test_index.vault.rate BETWEEN -0.5 AND 1 "
+        );
+
+        // Values must be numeric, so they can be inlined unquoted.
+        assert!(GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { vault(filter: { balance: { equals: "1; DROP TABLE vault" } }) { id } }"#,
+        )
+        .and_then(|q| q.build())
+        .is_err());
+    }
+
+    #[test]
+    fn test_operation_parse_interface_into_query_per_implementor() {
+        let schema = r#"
+interface Event {
+    id: ID!
+    block: UInt4!
+}
+
+type Swap implements Event @entity {
+    id: ID!
+    block: UInt4!
+    amount: UInt8!
+}
+
+type Deposit implements Event @entity {
+    id: ID!
+    block: UInt4!
+}
+"#;
+
+        let schema = IndexerSchema::new(
+            "fuel_indexer_test",
+            "test_index",
+            &GraphQLSchema::new(schema.to_string()),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )
+        .unwrap();
+
+        let queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"
+query { event(filter: { block: { gt: 5 } }) { id ...SwapFields } }
+fragment SwapFields on Swap { id amount }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .as_sql(&schema, DbType::Postgres)
+        .unwrap();
+        assert_eq!(
+            queries,
+            vec![
+                "SELECT json_build_object('__typename', 'Deposit', 'id', fuel_indexer_test_test_index.deposit.id) FROM fuel_indexer_test_test_index.deposit  WHERE  fuel_indexer_test_test_index.deposit.block > 5 ".to_string(),
+                "SELECT json_build_object('__typename', 'Swap', 'id', fuel_indexer_test_test_index.swap.id, 'amount', fuel_indexer_test_test_index.swap.amount) FROM fuel_indexer_test_test_index.swap  WHERE  fuel_indexer_test_test_index.swap.block > 5 ".to_string(),
+            ]
+        );
+
+        // Inline fragments are resolved the same way.
+        let queries = GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { event { block ... on Swap { amount } } }"#,
+        )
+        .unwrap()
+        .build()
+        .unwrap()
+        .as_sql(&schema, DbType::Postgres)
+        .unwrap();
+        assert_eq!(
+            queries[1],
+            "SELECT json_build_object('__typename', 'Swap', 'block', fuel_indexer_test_test_index.swap.block, 'amount', fuel_indexer_test_test_index.swap.amount) FROM fuel_indexer_test_test_index.swap   "
+        );
+
+        // Results from separate tables can't be paginated as a whole.
+        assert!(
+            GraphqlQueryBuilder::new(&schema, r#"query { event(first: 5) { id } }"#,)
+                .and_then(|q| q.build())
+                .is_err()
+        );
+
+        // Fragments must be on the interface or one of its implementors.
+        assert!(GraphqlQueryBuilder::new(
+            &schema,
+            r#"query { swap { id ... on Deposit { block } } }"#,
+        )
+        .and_then(|q| q.build())
+        .is_err());
+    }
 }
@@ -1,4 +1,6 @@
 pub mod arguments;
 pub mod dynamic;
+pub mod federation;
 pub mod graphql;
 pub mod queries;
+pub mod subscriptions;
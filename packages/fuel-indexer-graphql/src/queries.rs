@@ -1,16 +1,27 @@
-use super::{arguments::QueryParams, graphql::GraphqlError};
+use super::{
+    arguments::{Cursor, QueryParams},
+    graphql::GraphqlError,
+};
 use fuel_indexer_database::DbType;
+use fuel_indexer_lib::graphql::types::IdCol;
 
 use std::{collections::HashMap, fmt::Display};
 
 /// Represents a part of a user query. Each part can be a key-value pair
 /// describing an entity field and its corresponding database table, or a
 /// boundary for a nested object; opening boundaries contain a string to
-/// be used as a JSON key in the final database query.
+/// be used as a JSON key in the final database query, and optionally a column
+/// which, when NULL, makes the nested object itself NULL.
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub enum QueryElement {
-    Field { key: String, value: String },
-    ObjectOpeningBoundary { key: String },
+    Field {
+        key: String,
+        value: String,
+    },
+    ObjectOpeningBoundary {
+        key: String,
+        null_column: Option<String>,
+    },
     ObjectClosingBoundary,
 }
 
@@ -21,6 +32,10 @@ pub struct JoinCondition {
     pub referencing_key_col: String,
     pub primary_key_table: String,
     pub primary_key_col: String,
+
+    /// Whether a referencing row might have no primary key row to join, in which
+    /// case the referencing row is kept rather than filtered out.
+    pub nullable: bool,
 }
 
 impl Display for JoinCondition {
@@ -79,8 +94,7 @@ impl UserQuery {
 
                 let sorted_joins = self.get_topologically_sorted_joins();
 
-                let mut last_seen_primary_key_table = "".to_string();
-                let mut joins: Vec<String> = Vec::new();
+                let mut grouped_joins: Vec<Vec<JoinCondition>> = Vec::new();
 
                 // For each clause in the list of topologically-sorted joins,
                 // check if the clause's primary key table matches the last primary key
@@ -89,19 +103,21 @@ impl UserQuery {
                 // This is required because Postgres does not allow for joined primary key tables
                 // to be mentioned multiple times.
                 for sj in sorted_joins {
-                    if sj.primary_key_table == last_seen_primary_key_table {
-                        if let Some(elem) = joins.last_mut() {
-                            *elem = format!("{elem} AND {sj}")
+                    match grouped_joins.last_mut() {
+                        Some(group)
+                            if group[0].primary_key_table == sj.primary_key_table =>
+                        {
+                            group.push(sj)
                         }
-                    } else {
-                        joins.push(format!(
-                            "INNER JOIN {} ON {}",
-                            sj.primary_key_table, sj
-                        ));
-                        last_seen_primary_key_table = sj.primary_key_table;
+                        _ => grouped_joins.push(vec![sj]),
                     }
                 }
 
+                let joins = grouped_joins
+                    .iter()
+                    .map(|group| self.join_clause(group, db_type))
+                    .collect::<Vec<String>>();
+
                 let joins_str = if !joins.is_empty() {
                     joins.join(" ")
                 } else {
@@ -110,7 +126,29 @@ impl UserQuery {
 
                 // If there's a limit applied to the query, then we need to create a query
                 // with pagination info. Otherwise, we can return the entire result set.
-                let query: String = if let Some(limit) = self.query_params.limit {
+                let query: String = if self.query_params.meta {
+                    // There's a single report, whether or not the indexer has
+                    // committed a block yet.
+                    format!(
+                        "SELECT json_build_object({}) FROM (SELECT 1) AS _meta \
+                        LEFT JOIN (SELECT * FROM {}.indexmetadataentity \
+                        ORDER BY block_height DESC LIMIT 1) AS _meta_block ON true",
+                        selections_str, self.namespace_identifier,
+                    )
+                } else if self.query_params.aggregate {
+                    // Aggregates return a row per group, or a single row if ungrouped.
+                    format!(
+                        "SELECT json_build_object({}) FROM {}.{} {} {} {}",
+                        selections_str,
+                        self.namespace_identifier,
+                        self.entity_name,
+                        joins_str,
+                        self.query_params.get_filtering_expression(db_type),
+                        self.query_params.get_grouping_modifier(db_type)
+                    )
+                } else if self.query_params.connection {
+                    self.create_connection_query(db_type, selections_str, joins_str)?
+                } else if let Some(limit) = self.query_params.limit {
                     // Paginated queries must have an order applied to at least one field.
                     if !self.query_params.sorts.is_empty() {
                         self.create_query_with_pageinfo(
@@ -123,14 +161,33 @@ impl UserQuery {
                         return Err(GraphqlError::UnorderedPaginatedQuery);
                     }
                 } else {
+                    let limit = self
+                        .query_params
+                        .default_limit
+                        .map(|n| format!(" LIMIT {n}"))
+                        .unwrap_or_default();
+
+                    // Records are ordered by ID when no order is given, so that the
+                    // default limit always returns the same records.
+                    let ordering =
+                        if !limit.is_empty() && self.query_params.sorts.is_empty() {
+                            format!(
+                                "ORDER BY {}.{}.{}",
+                                self.namespace_identifier,
+                                self.entity_name,
+                                IdCol::to_lowercase_str()
+                            )
+                        } else {
+                            self.query_params.get_ordering_modififer(db_type)
+                        };
                     format!(
-                        "SELECT json_build_object({}) FROM {}.{} {} {} {}",
+                        "SELECT json_build_object({}) FROM {}.{} {} {} {}{limit}",
                         selections_str,
                         self.namespace_identifier,
                         self.entity_name,
                         joins_str,
                         self.query_params.get_filtering_expression(db_type),
-                        self.query_params.get_ordering_modififer(db_type)
+                        ordering
                     )
                 };
 
@@ -139,6 +196,29 @@ impl UserQuery {
         }
     }
 
+    /// Returns the clause joining the primary key table of a group of join conditions.
+    ///
+    /// The table is left joined if every condition is nullable, so that referencing rows
+    /// without a related record are kept. The version of a left joined versioned table is
+    /// then selected in the join condition, since filtering on it would drop those rows.
+    fn join_clause(&self, conditions: &[JoinCondition], db_type: &DbType) -> String {
+        let table = &conditions[0].primary_key_table;
+        let mut on = conditions
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<String>>();
+
+        if self.query_params.nullable_versioned_tables.contains(table) {
+            on.push(self.query_params.get_block_range_expression(table, db_type));
+        }
+
+        if conditions.iter().all(|c| c.nullable) {
+            format!("LEFT JOIN {table} ON {}", on.join(" AND "))
+        } else {
+            format!("INNER JOIN {table} ON {}", on.join(" AND "))
+        }
+    }
+
     /// Returns a SQL query that contains the requested results and a PageInfo object.
     fn create_query_with_pageinfo(
         &self,
@@ -205,6 +285,137 @@ impl UserQuery {
         }
     }
 
+    /// Returns a SQL query that contains the requested results as a Relay-style connection.
+    ///
+    /// Instead of skipping a number of records, a page starts right after (or ends right
+    /// before) the record that a cursor points to, which is found by comparing the sort
+    /// columns and `ID` of each record against the cursor. One more record than requested
+    /// is selected, in order to tell whether there's another page.
+    fn create_connection_query(
+        &self,
+        db_type: &DbType,
+        selections_str: String,
+        joins_str: String,
+    ) -> Result<String, GraphqlError> {
+        let params = &self.query_params;
+
+        if params.limit.is_some() && params.last.is_some() {
+            return Err(GraphqlError::ConflictingArguments(
+                "first".to_string(),
+                "last".to_string(),
+            ));
+        }
+
+        match db_type {
+            db_type @ DbType::Postgres => {
+                let id_column = format!(
+                    "{}.{}.{}",
+                    self.namespace_identifier,
+                    self.entity_name,
+                    IdCol::to_lowercase_str()
+                );
+                let columns = params.get_cursor_columns(&id_column);
+
+                // Pages counted from the end of the connection are selected in reverse,
+                // and put back in order afterwards.
+                let backward = params.last.is_some();
+                let page_size = if backward {
+                    params.last
+                } else {
+                    params.limit.or(params.default_limit)
+                };
+
+                let ordering = columns
+                    .iter()
+                    .map(|(col, order)| {
+                        let order = if backward {
+                            order.reverse()
+                        } else {
+                            order.clone()
+                        };
+                        format!("{col} {order}")
+                    })
+                    .collect::<Vec<String>>()
+                    .join(", ");
+
+                let mut filtering = params.get_filtering_expression(db_type);
+                if let Some(keyset) = params.get_keyset_expression(&columns, db_type)? {
+                    filtering = if filtering.is_empty() {
+                        format!("WHERE {keyset}")
+                    } else {
+                        format!("{filtering} AND {keyset}")
+                    };
+                }
+
+                let cursor = Cursor::sql_expression(&columns, db_type);
+                let limit = page_size
+                    .map(|n| format!("LIMIT {}", n + 1))
+                    .unwrap_or_default();
+                let page_filter = page_size
+                    .map(|n| format!("WHERE edge_position <= {n}"))
+                    .unwrap_or_default();
+                let has_more = page_size
+                    .map(|n| format!("((SELECT COUNT(*) FROM selection_cte) > {n})"))
+                    .unwrap_or("false".to_string());
+
+                let (has_next_page, has_previous_page) = if backward {
+                    (params.before.is_some().to_string(), has_more)
+                } else {
+                    (has_more, params.after.is_some().to_string())
+                };
+                let (first_edge, last_edge) = if backward {
+                    ("DESC", "ASC")
+                } else {
+                    ("ASC", "DESC")
+                };
+
+                let alias = self.alias.clone().unwrap_or(self.entity_name.clone());
+
+                let selection_cte = format!(
+                    r#"WITH selection_cte AS (
+                        SELECT json_build_object({}) AS edge_node,
+                        {cursor} AS edge_cursor,
+                        row_number() OVER (ORDER BY {ordering}) AS edge_position
+                        FROM {}.{}
+                        {}
+                        {}
+                        ORDER BY {ordering}
+                        {limit}),"#,
+                    selections_str,
+                    self.namespace_identifier,
+                    self.entity_name,
+                    joins_str,
+                    filtering,
+                );
+
+                let page_cte =
+                    format!("page_cte AS (SELECT * FROM selection_cte {page_filter})");
+
+                let selection_query = format!(
+                    r#"SELECT json_build_object(
+                        '{alias}', json_build_object(
+                            'edges', COALESCE((
+                                SELECT json_agg(
+                                    json_build_object('node', edge_node, 'cursor', edge_cursor)
+                                    ORDER BY edge_position {first_edge}
+                                )
+                                FROM page_cte
+                            ), '[]'::json),
+                            'pageInfo', json_build_object(
+                                'hasNextPage', {has_next_page},
+                                'hasPreviousPage', {has_previous_page},
+                                'startCursor', (SELECT edge_cursor FROM page_cte ORDER BY edge_position {first_edge} LIMIT 1),
+                                'endCursor', (SELECT edge_cursor FROM page_cte ORDER BY edge_position {last_edge} LIMIT 1)
+                            )
+                        )
+                    );"#
+                );
+
+                Ok([selection_cte, page_cte, selection_query].join("\n"))
+            }
+        }
+    }
+
     /// Parses QueryElements into a list of strings that can be used to create a selection statement.
     ///
     /// Each database type should have a way to return result sets as a JSON-friendly structure,
@@ -214,6 +425,9 @@ impl UserQuery {
 
         let mut selections = Vec::new();
 
+        // Whether each open nested object is wrapped in a NULL check.
+        let mut null_checks = Vec::new();
+
         match db_type {
             DbType::Postgres => {
                 while let Some(e) = peekable_elements.next() {
@@ -239,12 +453,26 @@ impl UserQuery {
 
                         // If the element is an object opener boundary, then we need to set a
                         // key so that the recipient can properly refer to the nested object.
-                        QueryElement::ObjectOpeningBoundary { key } => {
-                            selections.push(format!("'{key}', json_build_object("))
+                        // If the nested object might have no related record, then it
+                        // is NULL instead of an object with NULL fields.
+                        QueryElement::ObjectOpeningBoundary { key, null_column } => {
+                            match null_column {
+                                Some(column) => selections.push(format!(
+                                    "'{key}', CASE WHEN {column} IS NULL THEN NULL ELSE json_build_object("
+                                )),
+                                None => {
+                                    selections.push(format!("'{key}', json_build_object("))
+                                }
+                            }
+                            null_checks.push(null_column.is_some());
                         }
 
                         QueryElement::ObjectClosingBoundary => {
-                            selections.push(")".to_string());
+                            if null_checks.pop().unwrap_or(false) {
+                                selections.push(") END".to_string());
+                            } else {
+                                selections.push(")".to_string());
+                            }
 
                             if let Some(next_element) = peekable_elements.peek() {
                                 match next_element {
@@ -283,7 +511,7 @@ impl UserQuery {
                             ));
                         }
 
-                        QueryElement::ObjectOpeningBoundary { key } => {
+                        QueryElement::ObjectOpeningBoundary { key, .. } => {
                             selections.push(format!(
                                 "{}->'{}' AS {}",
                                 self.entity_name, key, key
@@ -369,6 +597,7 @@ mod tests {
             },
             QueryElement::ObjectOpeningBoundary {
                 key: "nested_object_key".to_string(),
+                null_column: None,
             },
             QueryElement::Field {
                 key: "nested_field_key".to_string(),
@@ -408,6 +637,7 @@ mod tests {
             },
             QueryElement::ObjectOpeningBoundary {
                 key: "tx".to_string(),
+                null_column: None,
             },
             QueryElement::Field {
                 key: "hash".to_string(),
@@ -434,6 +664,7 @@ mod tests {
                                 referencing_key_col: "block".to_string(),
                                 primary_key_table: "name_ident.block".to_string(),
                                 primary_key_col: "id".to_string(),
+                                nullable: false,
                             },
                         )]),
                     },
@@ -449,6 +680,7 @@ mod tests {
                                 referencing_key_col: "block".to_string(),
                                 primary_key_table: "name_ident.block".to_string(),
                                 primary_key_col: "id".to_string(),
+                                nullable: false,
                             },
                         )]),
                     },
@@ -464,6 +696,7 @@ mod tests {
                 sorts: vec![],
                 offset: None,
                 limit: None,
+                ..QueryParams::default()
             },
             alias: None,
         };
@@ -0,0 +1,215 @@
+use crate::{
+    arguments::{Filter, FilterType, Membership, ParsedValue},
+    dynamic::run_sql_queries,
+    graphql::{GraphqlError, GraphqlQueryBuilder, GraphqlResult, QueryLimits},
+    queries::UserQuery,
+};
+use async_graphql::{
+    async_trait::async_trait,
+    futures_util::{
+        future,
+        stream::{self, BoxStream, StreamExt},
+    },
+    Data, Executor, Request, Response, ServerError, Value,
+};
+use fuel_indexer_database::IndexerConnectionPool;
+use fuel_indexer_lib::{
+    graphql::types::IdCol,
+    utils::{subscribe_to_entity_changes, EntityChanges},
+};
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use fuel_indexer_types::type_id;
+use serde_json::Map;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+
+/// Executes the GraphQL requests sent to an indexer over a WebSocket.
+///
+/// Queries are run once. Subscriptions are run again each time the indexer commits
+/// changes to the entities that they select, and return the changed records that
+/// match their filters.
+#[derive(Clone)]
+pub struct SubscriptionExecutor {
+    schema: Arc<IndexerSchema>,
+    pool: IndexerConnectionPool,
+    limits: QueryLimits,
+}
+
+impl SubscriptionExecutor {
+    /// Create a new `SubscriptionExecutor`.
+    pub fn new(schema: IndexerSchema, pool: IndexerConnectionPool) -> Self {
+        Self {
+            schema: Arc::new(schema),
+            pool,
+            limits: QueryLimits::default(),
+        }
+    }
+
+    /// Limit the size of the requests that are executed, and of their results.
+    pub fn with_limits(mut self, limits: QueryLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Return each query of a subscription, along with the `TypeId` of the entity
+    /// that it selects.
+    fn subscriptions(
+        &self,
+        builder: GraphqlQueryBuilder,
+    ) -> GraphqlResult<Vec<(i64, UserQuery)>> {
+        builder
+            .with_limits(self.limits.clone())
+            .build_subscription()?
+            .parse(&self.schema)
+            .into_iter()
+            .map(|query| {
+                let typ = self
+                    .schema
+                    .parsed()
+                    .graphql_type(None, &query.entity_name)
+                    .ok_or_else(|| {
+                        GraphqlError::UnrecognizedType(query.entity_name.clone())
+                    })?;
+                Ok((type_id(&query.namespace_identifier, typ), query))
+            })
+            .collect()
+    }
+
+    /// Return the results of the subscription for the records in `changes`, keyed
+    /// by the name (or alias) of each subscribed field, or `None` if none of the
+    /// changed records match the subscription.
+    async fn changed_records(
+        &self,
+        subscriptions: &[(i64, UserQuery)],
+        changes: &EntityChanges,
+    ) -> GraphqlResult<Option<Value>> {
+        let mut data = Map::new();
+        let mut has_records = false;
+
+        for (type_id, query) in subscriptions {
+            let key = query.alias.clone().unwrap_or(query.entity_name.clone());
+            let records = data.entry(key).or_insert(serde_json::Value::Array(vec![]));
+
+            let Some(ids) = changes.changes.get(type_id) else {
+                continue;
+            };
+
+            // Only the changed records are selected, and the subscription's own
+            // filters are evaluated against their new values.
+            let mut query = query.clone();
+            query.query_params.filters.push(Filter {
+                fully_qualified_table_name: format!(
+                    "{}.{}",
+                    query.namespace_identifier, query.entity_name
+                ),
+                filter_type: FilterType::Membership(Membership::In(
+                    IdCol::to_lowercase_string(),
+                    ids.iter().cloned().map(ParsedValue::String).collect(),
+                )),
+            });
+
+            let sql = query.to_sql(&self.pool.database_type())?;
+            let rows =
+                run_sql_queries(&self.pool, vec![sql], self.limits.timeout).await?;
+            has_records |= !rows.is_empty();
+            if let serde_json::Value::Array(records) = records {
+                records.extend(rows);
+            }
+        }
+
+        if !has_records {
+            return Ok(None);
+        }
+
+        Ok(Some(Value::from_json(serde_json::Value::Object(data))?))
+    }
+}
+
+/// Return a GraphQL response for the results of a request.
+fn into_response(result: GraphqlResult<Value>) -> Response {
+    match result {
+        Ok(data) => Response::new(data),
+        Err(e) => {
+            let mut error = ServerError::new(e.to_string(), None);
+            if let Some(code) = e.code() {
+                error
+                    .extensions
+                    .get_or_insert_with(Default::default)
+                    .set("code", code);
+            }
+            Response::from_errors(vec![error])
+        }
+    }
+}
+
+#[async_trait]
+impl Executor for SubscriptionExecutor {
+    async fn execute(&self, request: Request) -> Response {
+        let result = async {
+            let queries = GraphqlQueryBuilder::new(&self.schema, &request.query)?
+                .with_limits(self.limits.clone())
+                .build()?
+                .as_sql(&self.schema, self.pool.database_type())?;
+            let results =
+                run_sql_queries(&self.pool, queries, self.limits.timeout).await?;
+            Ok(Value::from_json(serde_json::Value::Array(results))?)
+        }
+        .await;
+
+        into_response(result)
+    }
+
+    fn execute_stream(
+        &self,
+        request: Request,
+        _session_data: Option<Arc<Data>>,
+    ) -> BoxStream<'static, Response> {
+        let subscriptions = match GraphqlQueryBuilder::new(&self.schema, &request.query) {
+            Ok(builder) if builder.is_subscription() => self.subscriptions(builder),
+            Ok(_) => {
+                let executor = self.clone();
+                return stream::once(async move { executor.execute(request).await })
+                    .boxed();
+            }
+            Err(e) => Err(e),
+        };
+
+        let subscriptions = match subscriptions {
+            Ok(subscriptions) => Arc::new(subscriptions),
+            Err(e) => return stream::once(future::ready(into_response(Err(e)))).boxed(),
+        };
+
+        let executor = self.clone();
+        let receiver = subscribe_to_entity_changes();
+
+        stream::unfold(receiver, move |mut receiver| {
+            let executor = executor.clone();
+            let subscriptions = subscriptions.clone();
+            async move {
+                loop {
+                    let changes = match receiver.recv().await {
+                        Ok(changes) => changes,
+                        // Changes that were missed can't be recovered, but later
+                        // changes are still sent.
+                        Err(RecvError::Lagged(_)) => continue,
+                        Err(RecvError::Closed) => return None,
+                    };
+
+                    let parsed = executor.schema.parsed();
+                    if changes.namespace != parsed.namespace()
+                        || changes.identifier != parsed.identifier()
+                    {
+                        continue;
+                    }
+
+                    match executor.changed_records(&subscriptions, &changes).await {
+                        Ok(Some(data)) => return Some((Response::new(data), receiver)),
+                        Ok(None) => continue,
+                        Err(e) => return Some((into_response(Err(e)), receiver)),
+                    }
+                }
+            }
+        })
+        .boxed()
+    }
+}
@@ -162,6 +162,42 @@ pub struct IndexerArgs {
     #[clap(long, help = "Number of seconds over which to allow --rate-limit-rps.")]
     pub rate_limit_window_size: Option<u64>,
 
+    /// Max depth of nested entities in a GraphQL query.
+    #[clap(long, help = "Max depth of nested entities in a GraphQL query.")]
+    pub max_query_depth: Option<usize>,
+
+    /// Max number of related entities that a GraphQL query can select or filter by.
+    #[clap(
+        long,
+        help = "Max number of related entities that a GraphQL query can select or filter by."
+    )]
+    pub max_query_joins: Option<usize>,
+
+    /// Number of records returned by a GraphQL query that doesn't specify `first` or `last`.
+    #[clap(
+        long,
+        help = "Number of records returned by a GraphQL query that doesn't specify `first` or `last`."
+    )]
+    pub default_query_limit: Option<u64>,
+
+    /// Max value of `first` or `last` in a GraphQL query.
+    #[clap(long, help = "Max value of `first` or `last` in a GraphQL query.")]
+    pub max_query_limit: Option<u64>,
+
+    /// Amount of time (milliseconds) that each SQL statement of a GraphQL query can run for.
+    #[clap(
+        long,
+        help = "Amount of time (milliseconds) that each SQL statement of a GraphQL query can run for."
+    )]
+    pub query_timeout: Option<u64>,
+
+    /// Max number of GraphQL responses cached per indexer, between blocks.
+    #[clap(
+        long,
+        help = "Max number of GraphQL responses cached per indexer, between blocks."
+    )]
+    pub response_cache_size: Option<usize>,
+
     /// The number of WASM opcodes after which the indexer's event handler will stop execution.
     #[clap(
         long,
@@ -313,6 +349,42 @@ pub struct ApiServerArgs {
     #[clap(long, help = "Number of seconds over which to allow --rate-limit-rps.")]
     pub rate_limit_window_size: Option<u64>,
 
+    /// Max depth of nested entities in a GraphQL query.
+    #[clap(long, help = "Max depth of nested entities in a GraphQL query.")]
+    pub max_query_depth: Option<usize>,
+
+    /// Max number of related entities that a GraphQL query can select or filter by.
+    #[clap(
+        long,
+        help = "Max number of related entities that a GraphQL query can select or filter by."
+    )]
+    pub max_query_joins: Option<usize>,
+
+    /// Number of records returned by a GraphQL query that doesn't specify `first` or `last`.
+    #[clap(
+        long,
+        help = "Number of records returned by a GraphQL query that doesn't specify `first` or `last`."
+    )]
+    pub default_query_limit: Option<u64>,
+
+    /// Max value of `first` or `last` in a GraphQL query.
+    #[clap(long, help = "Max value of `first` or `last` in a GraphQL query.")]
+    pub max_query_limit: Option<u64>,
+
+    /// Amount of time (milliseconds) that each SQL statement of a GraphQL query can run for.
+    #[clap(
+        long,
+        help = "Amount of time (milliseconds) that each SQL statement of a GraphQL query can run for."
+    )]
+    pub query_timeout: Option<u64>,
+
+    /// Max number of GraphQL responses cached per indexer, between blocks.
+    #[clap(
+        long,
+        help = "Max number of GraphQL responses cached per indexer, between blocks."
+    )]
+    pub response_cache_size: Option<usize>,
+
     /// Allow the web API to accept raw SQL queries.
     #[clap(long, help = "Allow the web API to accept raw SQL queries.")]
     pub accept_sql_queries: bool,
@@ -103,6 +103,12 @@ impl Default for IndexerArgs {
             rate_limit: defaults::RATE_LIMIT_ENABLED,
             rate_limit_request_count: Some(defaults::RATE_LIMIT_REQUEST_COUNT),
             rate_limit_window_size: Some(defaults::RATE_LIMIT_WINDOW_SIZE),
+            max_query_depth: None,
+            max_query_joins: None,
+            default_query_limit: None,
+            max_query_limit: None,
+            query_timeout: None,
+            response_cache_size: None,
             replace_indexer: defaults::REPLACE_INDEXER,
             remove_data: defaults::REMOVE_DATA,
             accept_sql_queries: defaults::ACCEPT_SQL,
@@ -217,6 +223,12 @@ impl From<IndexerArgs> for IndexerConfig {
                 host: args.web_api_host,
                 port: args.web_api_port,
                 max_body_size: args.max_body_size,
+                max_query_depth: args.max_query_depth,
+                max_query_joins: args.max_query_joins,
+                default_query_limit: args.default_query_limit,
+                max_query_limit: args.max_query_limit,
+                query_timeout: args.query_timeout,
+                response_cache_size: args.response_cache_size,
             },
             metrics: args.metrics,
             stop_idle_indexers: args.stop_idle_indexers,
@@ -304,6 +316,12 @@ impl From<ApiServerArgs> for IndexerConfig {
                 host: args.web_api_host,
                 port: args.web_api_port,
                 max_body_size: args.max_body_size,
+                max_query_depth: args.max_query_depth,
+                max_query_joins: args.max_query_joins,
+                default_query_limit: args.default_query_limit,
+                max_query_limit: args.max_query_limit,
+                query_timeout: args.query_timeout,
+                response_cache_size: args.response_cache_size,
             },
             metrics: args.metrics,
             stop_idle_indexers: defaults::STOP_IDLE_INDEXERS,
@@ -445,6 +463,46 @@ impl IndexerConfig {
                 let size = max_body_size.as_str().unwrap();
                 config.web_api.max_body_size = usize::from_str(size).unwrap();
             }
+
+            let max_query_depth =
+                section.get(&serde_yaml::Value::String("max_query_depth".into()));
+            if let Some(max_query_depth) = max_query_depth {
+                config.web_api.max_query_depth =
+                    Some(max_query_depth.as_u64().unwrap() as usize);
+            }
+
+            let max_query_joins =
+                section.get(&serde_yaml::Value::String("max_query_joins".into()));
+            if let Some(max_query_joins) = max_query_joins {
+                config.web_api.max_query_joins =
+                    Some(max_query_joins.as_u64().unwrap() as usize);
+            }
+
+            let default_query_limit =
+                section.get(&serde_yaml::Value::String("default_query_limit".into()));
+            if let Some(default_query_limit) = default_query_limit {
+                config.web_api.default_query_limit =
+                    Some(default_query_limit.as_u64().unwrap());
+            }
+
+            let max_query_limit =
+                section.get(&serde_yaml::Value::String("max_query_limit".into()));
+            if let Some(max_query_limit) = max_query_limit {
+                config.web_api.max_query_limit = Some(max_query_limit.as_u64().unwrap());
+            }
+
+            let query_timeout =
+                section.get(&serde_yaml::Value::String("query_timeout".into()));
+            if let Some(query_timeout) = query_timeout {
+                config.web_api.query_timeout = Some(query_timeout.as_u64().unwrap());
+            }
+
+            let response_cache_size =
+                section.get(&serde_yaml::Value::String("response_cache_size".into()));
+            if let Some(response_cache_size) = response_cache_size {
+                config.web_api.response_cache_size =
+                    Some(response_cache_size.as_u64().unwrap() as usize);
+            }
         }
 
         if let Some(section) = content.get(database_config_key) {
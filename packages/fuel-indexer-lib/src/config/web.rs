@@ -22,6 +22,31 @@ pub struct WebApiConfig {
     /// Max body size for web API requests.
     #[serde(default)]
     pub max_body_size: usize,
+
+    /// Max depth of nested entities in a GraphQL query.
+    #[serde(default)]
+    pub max_query_depth: Option<usize>,
+
+    /// Max number of related entities that a GraphQL query can select or filter by.
+    #[serde(default)]
+    pub max_query_joins: Option<usize>,
+
+    /// Number of records returned by a GraphQL query that doesn't specify `first` or `last`.
+    /// Defaults to `max_query_limit`.
+    #[serde(default)]
+    pub default_query_limit: Option<u64>,
+
+    /// Max value of `first` or `last` in a GraphQL query.
+    #[serde(default)]
+    pub max_query_limit: Option<u64>,
+
+    /// Max number of milliseconds that each SQL statement of a GraphQL query can run for.
+    #[serde(default)]
+    pub query_timeout: Option<u64>,
+
+    /// Max number of GraphQL responses cached per indexer, between blocks.
+    #[serde(default)]
+    pub response_cache_size: Option<usize>,
 }
 
 impl std::string::ToString for WebApiConfig {
@@ -45,6 +70,12 @@ impl Default for WebApiConfig {
             host: defaults::WEB_API_HOST.into(),
             port: defaults::WEB_API_PORT.into(),
             max_body_size: defaults::MAX_BODY_SIZE,
+            max_query_depth: None,
+            max_query_joins: None,
+            default_query_limit: None,
+            max_query_limit: None,
+            query_timeout: None,
+            response_cache_size: None,
         }
     }
 }
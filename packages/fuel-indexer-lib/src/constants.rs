@@ -92,12 +92,14 @@ lazy_static! {
 
     /// Set of types that should be copied instead of referenced.
     pub static ref COPY_TYPES: HashSet<&'static str> = HashSet::from([
+        "BigDecimal",
         "Blob",
         "Charfield",
         "HexString",
         "ID",
         "Identity",
         "Json",
+        "Option<BigDecimal>",
         "Option<Blob>",
         "Option<Charfield>",
         "Option<HexString>",
@@ -115,6 +117,7 @@ lazy_static! {
     pub static ref FUEL_PRIMITIVES: HashSet<&'static str> = HashSet::from([
         "BlockData",
         "Call",
+        "InputCoin",
         "Log",
         "LogData",
         "MessageOut",
@@ -133,6 +136,7 @@ lazy_static! {
         // Scalars.
         "Address",
         "AssetId",
+        "BigDecimal",
         "Blob",
         "BlockHeight",
         "BlockId",
@@ -163,6 +167,7 @@ lazy_static! {
         "UID",
         "UInt1",
         "UInt16",
+        "UInt32",
         "UInt4",
         "UInt8",
         "Virtual",
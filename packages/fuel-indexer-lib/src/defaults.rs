@@ -40,6 +40,12 @@ pub const MAX_BODY_SIZE: usize = 5242880;
 /// Size of web-API-to-executor-service channel.
 pub const SERVICE_REQUEST_CHANNEL_SIZE: usize = 100;
 
+/// Size of executor-to-web-API channel of committed entity changes.
+pub const ENTITY_CHANGES_CHANNEL_SIZE: usize = 1024;
+
+/// Size of executor-service-to-web-API channel of replaced indexer schemas.
+pub const SCHEMA_CHANGES_CHANNEL_SIZE: usize = 16;
+
 /// How long to wait if request to the Fuel GQL client returned no data.
 pub const IDLE_SERVICE_WAIT_SECS: u64 = 1;
 
@@ -134,3 +140,15 @@ pub const REMOVE_DATA: bool = false;
 
 /// Allow the web API to accept raw SQL queries.
 pub const ACCEPT_SQL: bool = false;
+
+/// How often the service checks whether a shadow indexer has caught up with the live indexer.
+pub const SHADOW_INDEXER_CHECK_SECS: u64 = 5;
+
+/// How long to wait for a stopped indexer to finish its current batch of blocks.
+pub const EXECUTOR_STOP_TIMEOUT_SECS: u64 = 30;
+
+/// How long the schema of a replaced indexer is kept after a shadow indexer takes its place.
+pub const RETIRED_SCHEMA_GRACE_PERIOD_SECS: u64 = 86400;
+
+/// How often the web API checks that a cached indexer schema is still the latest version.
+pub const SCHEMA_VERSION_CHECK_SECS: u64 = 5;
@@ -0,0 +1,436 @@
+//! # fuel_indexer_lib::graphql::enum_entities
+//!
+//! Derive GraphQL union entities from data-carrying enums in a contract JSON ABI.
+//!
+//! Each variant of an enum listed under `enum_entities` in an indexer manifest is
+//! mapped to a member object type, and the members are combined into a union entity,
+//! so that decoded enum values can be saved without hand-writing the schema types.
+
+use fuel_abi_types::abi::program::{ProgramABI, TypeApplication, TypeDeclaration};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Result type returned when deriving enum entities.
+pub type EnumEntityResult<T> = Result<T, EnumEntityError>;
+
+/// Error type returned when deriving enum entities.
+#[derive(Error, Debug)]
+pub enum EnumEntityError {
+    #[error("Enum '{0}' not found in the JSON ABI.")]
+    EnumNotFound(String),
+    #[error("Type '{0}' in the JSON ABI is not an enum.")]
+    NotAnEnum(String),
+    #[error("Type with TypeID({0}) not found in the JSON ABI.")]
+    TypeNotFound(usize),
+    #[error("Variant '{variant}' of enum '{name}' has unsupported type '{typ}'.")]
+    UnsupportedType {
+        name: String,
+        variant: String,
+        typ: String,
+    },
+    #[error("Field '{field}' of union '{union}' has conflicting types '{first}' and '{second}'.")]
+    ConflictingField {
+        union: String,
+        field: String,
+        first: String,
+        second: String,
+    },
+}
+
+/// The kind of value stored in a field of an enum entity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumEntityFieldKind {
+    U8,
+    U16,
+    U32,
+    U64,
+    U256,
+    Bool,
+    B256,
+    Address,
+    ContractId,
+    Identity,
+
+    /// A fixed-length string (e.g., `str[32]`).
+    Str,
+
+    /// An enum whose variants carry no data, stored as the name of the variant.
+    UnitEnum,
+}
+
+impl EnumEntityFieldKind {
+    /// Derive the field kind of an ABI type, if the type is supported.
+    fn from_abi(typ: &TypeDeclaration) -> Option<Self> {
+        let kind = match typ.type_field.as_str() {
+            "u8" => Self::U8,
+            "u16" => Self::U16,
+            "u32" => Self::U32,
+            "u64" => Self::U64,
+            "u256" | "struct U256" | "struct std::u256::U256" => Self::U256,
+            "bool" => Self::Bool,
+            "b256" => Self::B256,
+            "struct Address" => Self::Address,
+            "struct ContractId" => Self::ContractId,
+            "enum Identity" => Self::Identity,
+            s if s.starts_with("str[") => Self::Str,
+            _ => return None,
+        };
+
+        Some(kind)
+    }
+
+    /// The GraphQL scalar type used to store this field.
+    pub fn graphql_type(&self) -> &'static str {
+        match self {
+            Self::U8 => "UInt1",
+            Self::U16 | Self::U32 => "UInt4",
+            Self::U64 => "UInt8",
+            Self::U256 => "UInt32",
+            Self::Bool => "Boolean",
+            Self::B256 => "Bytes32",
+            Self::Address => "Address",
+            Self::ContractId => "ContractId",
+            Self::Identity => "Identity",
+            Self::Str | Self::UnitEnum => "Charfield",
+        }
+    }
+}
+
+/// A field of an enum entity member, derived from the data carried by a variant.
+#[derive(Debug, Clone)]
+pub struct EnumEntityField {
+    /// Name of the field in the GraphQL schema.
+    pub name: String,
+
+    /// Name of the field in the variant's struct, or `None` if the field holds the
+    /// variant's data itself.
+    pub abi_name: Option<String>,
+
+    /// The kind of value stored in this field.
+    pub kind: EnumEntityFieldKind,
+}
+
+/// A member object type of an enum entity, derived from a single enum variant.
+#[derive(Debug, Clone)]
+pub struct EnumEntityMember {
+    /// Name of the enum variant.
+    pub variant: String,
+
+    /// Name of the member object type.
+    pub name: String,
+
+    /// Whether the variant carries no data.
+    pub is_unit: bool,
+
+    /// Fields derived from the data carried by the variant.
+    pub fields: Vec<EnumEntityField>,
+}
+
+/// A GraphQL union entity derived from a data-carrying ABI enum.
+#[derive(Debug, Clone)]
+pub struct EnumEntity {
+    /// Name of the ABI enum.
+    pub enum_name: String,
+
+    /// Name of the union entity.
+    pub name: String,
+
+    /// Member object types, one for each enum variant.
+    pub members: Vec<EnumEntityMember>,
+}
+
+/// Field names reserved by enum entity members.
+///
+/// Variant fields with these names are stored with a `payload_` prefix.
+const RESERVED_FIELD_NAMES: [&str; 2] = ["id", "variant"];
+
+/// Convert a `PascalCase` variant name into a `snake_case` field name.
+fn to_snake_case(s: &str) -> String {
+    let mut result = String::new();
+    for (i, c) in s.chars().enumerate() {
+        if c.is_uppercase() {
+            if i > 0 {
+                result.push('_');
+            }
+            result.extend(c.to_lowercase());
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+/// Name of a field in the schema, prefixed if it would collide with a reserved field.
+fn field_name(name: &str) -> String {
+    if RESERVED_FIELD_NAMES.contains(&name) {
+        format!("payload_{name}")
+    } else {
+        name.to_string()
+    }
+}
+
+impl EnumEntity {
+    /// Derive the enum entity for the ABI enum with the given name.
+    pub fn from_abi(abi: &ProgramABI, enum_name: &str) -> EnumEntityResult<Self> {
+        let types = abi
+            .types
+            .iter()
+            .map(|t| (t.type_id, t))
+            .collect::<HashMap<usize, &TypeDeclaration>>();
+
+        let get = |app: &TypeApplication| {
+            types
+                .get(&app.type_id)
+                .copied()
+                .ok_or(EnumEntityError::TypeNotFound(app.type_id))
+        };
+
+        let typ = abi
+            .types
+            .iter()
+            .find(|t| t.type_field.split(' ').last() == Some(enum_name))
+            .ok_or_else(|| EnumEntityError::EnumNotFound(enum_name.to_string()))?;
+
+        if !typ.type_field.starts_with("enum ") {
+            return Err(EnumEntityError::NotAnEnum(enum_name.to_string()));
+        }
+
+        let unsupported =
+            |variant: &str, typ: &TypeDeclaration| EnumEntityError::UnsupportedType {
+                name: enum_name.to_string(),
+                variant: variant.to_string(),
+                typ: typ.type_field.clone(),
+            };
+
+        let mut members = Vec::new();
+        for component in typ.components.iter().flatten() {
+            let variant = component.name.clone();
+            let variant_typ = get(component)?;
+
+            let (is_unit, fields) = if variant_typ.type_field == "()" {
+                (true, vec![])
+            } else if variant_typ.type_field.starts_with("struct ")
+                && EnumEntityFieldKind::from_abi(variant_typ).is_none()
+            {
+                let mut fields = Vec::new();
+                for field in variant_typ.components.iter().flatten() {
+                    let field_typ = get(field)?;
+                    let kind = field_kind(field_typ, &types)
+                        .ok_or_else(|| unsupported(&variant, field_typ))?;
+                    fields.push(EnumEntityField {
+                        name: field_name(&field.name),
+                        abi_name: Some(field.name.clone()),
+                        kind,
+                    });
+                }
+                (false, fields)
+            } else {
+                let kind = field_kind(variant_typ, &types)
+                    .ok_or_else(|| unsupported(&variant, variant_typ))?;
+                let field = EnumEntityField {
+                    name: field_name(&to_snake_case(&variant)),
+                    abi_name: None,
+                    kind,
+                };
+                (false, vec![field])
+            };
+
+            members.push(EnumEntityMember {
+                name: format!("{enum_name}{variant}"),
+                variant,
+                is_unit,
+                fields,
+            });
+        }
+
+        let entity = Self {
+            enum_name: enum_name.to_string(),
+            name: format!("{enum_name}Entity"),
+            members,
+        };
+
+        entity.check_field_types()?;
+
+        Ok(entity)
+    }
+
+    /// Ensure that fields with the same name have the same type across all members,
+    /// since members share the columns of the union entity.
+    fn check_field_types(&self) -> EnumEntityResult<()> {
+        let mut seen = HashMap::new();
+        for field in self.members.iter().flat_map(|m| m.fields.iter()) {
+            let typ = field.kind.graphql_type();
+            if let Some(first) = seen.insert(field.name.clone(), typ) {
+                if first != typ {
+                    return Err(EnumEntityError::ConflictingField {
+                        union: self.name.clone(),
+                        field: field.name.clone(),
+                        first: first.to_string(),
+                        second: typ.to_string(),
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Return the GraphQL schema fragment declaring this union entity and its members.
+    pub fn schema_fragment(&self) -> String {
+        let mut fragment = String::new();
+
+        for member in &self.members {
+            fragment.push_str(&format!(
+                "\ntype {} @entity {{\n    id: ID!\n    variant: Charfield!\n",
+                member.name
+            ));
+            for field in &member.fields {
+                fragment.push_str(&format!(
+                    "    {}: {}!\n",
+                    field.name,
+                    field.kind.graphql_type()
+                ));
+            }
+            fragment.push_str("}\n");
+        }
+
+        let members = self
+            .members
+            .iter()
+            .map(|m| m.name.as_str())
+            .collect::<Vec<_>>()
+            .join(" | ");
+        fragment.push_str(&format!("\nunion {} = {}\n", self.name, members));
+
+        fragment
+    }
+}
+
+/// Derive the field kind of an ABI type, resolving whether enums carry data.
+fn field_kind(
+    typ: &TypeDeclaration,
+    types: &HashMap<usize, &TypeDeclaration>,
+) -> Option<EnumEntityFieldKind> {
+    if typ.type_field.starts_with("enum ") && typ.type_field != "enum Identity" {
+        let all_unit = typ.components.iter().flatten().all(|c| {
+            types
+                .get(&c.type_id)
+                .map(|t| t.type_field == "()")
+                .unwrap_or(false)
+        });
+        return all_unit.then_some(EnumEntityFieldKind::UnitEnum);
+    }
+
+    EnumEntityFieldKind::from_abi(typ)
+}
+
+/// Derive the enum entities with the given names from a contract JSON ABI.
+pub fn derive_enum_entities(
+    abi: &ProgramABI,
+    names: &[String],
+) -> EnumEntityResult<Vec<EnumEntity>> {
+    names
+        .iter()
+        .map(|name| EnumEntity::from_abi(abi, name))
+        .collect()
+}
+
+/// Append the schema fragments of the given enum entities to GraphQL schema content.
+pub fn schema_with_enum_entities(schema: &str, entities: &[EnumEntity]) -> String {
+    entities.iter().fold(schema.to_string(), |acc, entity| {
+        format!("{acc}{}", entity.schema_fragment())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn abi() -> ProgramABI {
+        serde_json::from_str(
+            r#"{
+    "types": [
+        { "typeId": 0, "type": "()", "components": [], "typeParameters": null },
+        { "typeId": 1, "type": "enum PoolEvent", "components": [
+            { "name": "Deposit", "type": 2, "typeArguments": null },
+            { "name": "Withdraw", "type": 3, "typeArguments": null },
+            { "name": "Paused", "type": 0, "typeArguments": null },
+            { "name": "Mode", "type": 5, "typeArguments": null }
+        ], "typeParameters": null },
+        { "typeId": 2, "type": "struct Deposit", "components": [
+            { "name": "id", "type": 3, "typeArguments": null },
+            { "name": "amount", "type": 3, "typeArguments": null },
+            { "name": "memo", "type": 4, "typeArguments": null }
+        ], "typeParameters": null },
+        { "typeId": 3, "type": "u64", "components": null, "typeParameters": null },
+        { "typeId": 4, "type": "str[8]", "components": null, "typeParameters": null },
+        { "typeId": 5, "type": "enum Mode", "components": [
+            { "name": "Fast", "type": 0, "typeArguments": null },
+            { "name": "Slow", "type": 0, "typeArguments": null }
+        ], "typeParameters": null },
+        { "typeId": 6, "type": "enum Bad", "components": [
+            { "name": "Pool", "type": 1, "typeArguments": null }
+        ], "typeParameters": null }
+    ],
+    "functions": [],
+    "loggedTypes": [],
+    "messagesTypes": [],
+    "configurables": []
+}"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_enum_entity_schema_fragment_includes_union_and_members() {
+        let entity = EnumEntity::from_abi(&abi(), "PoolEvent").unwrap();
+
+        assert_eq!(
+            entity.schema_fragment(),
+            r#"
+type PoolEventDeposit @entity {
+    id: ID!
+    variant: Charfield!
+    payload_id: UInt8!
+    amount: UInt8!
+    memo: Charfield!
+}
+
+type PoolEventWithdraw @entity {
+    id: ID!
+    variant: Charfield!
+    withdraw: UInt8!
+}
+
+type PoolEventPaused @entity {
+    id: ID!
+    variant: Charfield!
+}
+
+type PoolEventMode @entity {
+    id: ID!
+    variant: Charfield!
+    mode: Charfield!
+}
+
+union PoolEventEntity = PoolEventDeposit | PoolEventWithdraw | PoolEventPaused | PoolEventMode
+"#
+        );
+    }
+
+    #[test]
+    fn test_enum_entity_rejects_unsupported_variant_data() {
+        assert!(matches!(
+            EnumEntity::from_abi(&abi(), "Bad"),
+            Err(EnumEntityError::UnsupportedType { .. })
+        ));
+        assert!(matches!(
+            EnumEntity::from_abi(&abi(), "Deposit"),
+            Err(EnumEntityError::NotAnEnum(_))
+        ));
+        assert!(matches!(
+            EnumEntity::from_abi(&abi(), "Missing"),
+            Err(EnumEntityError::EnumNotFound(_))
+        ));
+    }
+}
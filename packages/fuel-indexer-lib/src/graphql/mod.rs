@@ -1,12 +1,17 @@
 pub mod constants;
+pub mod enum_entities;
 pub mod parser;
 pub mod types;
 pub mod validator;
 
-pub use parser::{JoinTableMeta, ParsedError, ParsedGraphQLSchema};
+pub use parser::{
+    CompositeIndexMeta, JoinTableMeta, OnDuplicate, ParsedError, ParsedGraphQLSchema,
+    SearchMeta,
+};
 pub use validator::GraphQLSchemaValidator;
 
 use async_graphql_parser::types::FieldDefinition;
+use async_graphql_value::ConstValue;
 use fuel_indexer_types::graphql::IndexMetadata;
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
@@ -140,6 +145,71 @@ pub fn is_list_type(f: &FieldDefinition) -> bool {
     f.ty.to_string().matches(['[', ']']).count() == 2
 }
 
+/// Return the name of the child field from which a `@derivedFrom` `FieldDefinition`
+/// is derived, if the `FieldDefinition` has a `@derivedFrom` directive.
+pub fn derived_from_field(f: &FieldDefinition) -> Option<String> {
+    f.directives
+        .iter()
+        .find(|d| d.node.name.to_string() == "derivedFrom")
+        .map(|d| {
+            d.node
+                .get_argument("field")
+                .map(|v| v.node.to_string().trim_matches('"').to_string())
+                .unwrap_or_else(|| {
+                    panic!(
+                        "FieldDefinition({}) has a `@derivedFrom` directive without a `field` argument.",
+                        f.name
+                    )
+                })
+        })
+}
+
+/// Return the value of the `@default` directive of a `FieldDefinition`, if any.
+pub fn field_default_value(f: &FieldDefinition) -> Option<ConstValue> {
+    f.directives
+        .iter()
+        .find(|d| d.node.name.to_string() == "default")
+        .map(|d| {
+            d.node
+                .get_argument("value")
+                .map(|v| v.node.clone())
+                .unwrap_or_else(|| {
+                    panic!(
+                        "FieldDefinition({}) has a `@default` directive without a `value` argument.",
+                        f.name
+                    )
+                })
+        })
+}
+
+/// Return the SQL expression from which a `@computed` `FieldDefinition` is generated,
+/// if the `FieldDefinition` has a `@computed` directive.
+pub fn computed_field_sql(f: &FieldDefinition) -> Option<String> {
+    f.directives
+        .iter()
+        .find(|d| d.node.name.to_string() == "computed")
+        .map(|d| match d.node.get_argument("sql").map(|v| &v.node) {
+            Some(ConstValue::String(s)) => s.to_owned(),
+            _ => panic!(
+                "FieldDefinition({}) has a `@computed` directive without a `sql` argument.",
+                f.name
+            ),
+        })
+}
+
+/// Return the index type of an `@indexed` `FieldDefinition`, if the `FieldDefinition`
+/// has an `@indexed` directive.
+pub fn field_index_type(f: &FieldDefinition) -> Option<String> {
+    f.directives
+        .iter()
+        .find(|d| d.node.name.to_string() == "indexed")
+        .map(|d| match d.node.get_argument("type").map(|v| &v.node) {
+            Some(ConstValue::Enum(name)) => name.to_string(),
+            Some(other) => other.to_string().trim_matches('"').to_string(),
+            None => CompositeIndexMeta::DEFAULT_METHOD.to_string(),
+        })
+}
+
 /// Return the simple field name for a given `FieldDefinition`.
 pub fn field_type_name(f: &FieldDefinition) -> String {
     f.ty.to_string().replace(['[', ']', '!'], "")
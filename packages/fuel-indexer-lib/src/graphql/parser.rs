@@ -6,7 +6,8 @@
 use crate::{
     fully_qualified_namespace,
     graphql::{
-        extract_foreign_key_info, field_id, field_type_name, is_list_type,
+        computed_field_sql, derived_from_field, extract_foreign_key_info,
+        field_default_value, field_id, field_index_type, field_type_name, is_list_type,
         list_field_type_name, GraphQLSchema, GraphQLSchemaValidator, IdCol, BASE_SCHEMA,
     },
     join_table_name, ExecutionSource,
@@ -14,12 +15,17 @@ use crate::{
 use async_graphql_parser::{
     parse_schema,
     types::{
-        EnumType, FieldDefinition, ObjectType, ServiceDocument, TypeDefinition, TypeKind,
-        TypeSystemDefinition, UnionType,
+        ConstDirective, EnumType, FieldDefinition, InterfaceType, ObjectType,
+        ServiceDocument, TypeDefinition, TypeKind, TypeSystemDefinition, UnionType,
     },
 };
+use async_graphql_value::ConstValue;
 
-use std::collections::{BTreeMap, HashMap, HashSet};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    str::FromStr,
+};
+use strum::{AsRefStr, EnumString};
 use thiserror::Error;
 
 /// Result type returned by parsing GraphQL schema.
@@ -135,6 +141,146 @@ impl JoinTableMeta {
     }
 }
 
+/// Represents the full-text search configuration of a `TypeDefinition` with a `@search` directive.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct SearchMeta {
+    /// Names of the `FieldDefinition`s whose text is searchable.
+    pub fields: Vec<String>,
+
+    /// Text search configuration used to parse the text (e.g., `english`).
+    pub language: String,
+}
+
+impl SearchMeta {
+    /// Default text search configuration, if the `@search` directive doesn't specify one.
+    pub const DEFAULT_LANGUAGE: &'static str = "english";
+
+    /// Create a new `SearchMeta` from the `@search` directive of a `TypeDefinition`, if any.
+    pub fn from_typedef(typ: &TypeDefinition) -> Option<Self> {
+        let directive = typ
+            .directives
+            .iter()
+            .find(|d| d.node.name.to_string() == "search")?;
+
+        let fields = directive_field_names(&directive.node, typ)?;
+        let language = match directive.node.get_argument("language").map(|v| &v.node) {
+            Some(ConstValue::String(s)) => s.to_owned(),
+            Some(other) => other.to_string(),
+            None => Self::DEFAULT_LANGUAGE.to_string(),
+        };
+
+        Some(Self { fields, language })
+    }
+}
+
+/// What happens when a record of a `TypeDefinition` with an `@immutable` directive is saved
+/// with the `ID` of a record that already exists.
+#[derive(Debug, Clone, Copy, Default, Eq, PartialEq, EnumString, AsRefStr)]
+pub enum OnDuplicate {
+    /// Saving the record fails.
+    #[default]
+    Error,
+
+    /// The record is not saved, and the existing record is kept.
+    Ignore,
+}
+
+impl OnDuplicate {
+    /// Create an `OnDuplicate` from the `@immutable` directive of a `TypeDefinition`, if any.
+    pub fn from_typedef(typ: &TypeDefinition) -> Option<Self> {
+        let directive = typ
+            .directives
+            .iter()
+            .find(|d| d.node.name.to_string() == "immutable")?;
+
+        match directive.node.get_argument("onDuplicate").map(|v| &v.node) {
+            Some(ConstValue::Enum(name)) => {
+                Some(Self::from_str(name.as_str()).unwrap_or_else(|_| {
+                    panic!(
+                        "TypeDefinition({}) has an unknown `onDuplicate` policy: {name}.",
+                        typ.name
+                    )
+                }))
+            }
+            Some(other) => panic!(
+                "TypeDefinition({}) has an unknown `onDuplicate` policy: {other}.",
+                typ.name
+            ),
+            None => Some(Self::default()),
+        }
+    }
+}
+
+/// Represents an index on multiple columns, declared with an `@index` or `@unique`
+/// directive on a `TypeDefinition`.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct CompositeIndexMeta {
+    /// Names of the indexed `FieldDefinition`s, in index order.
+    pub fields: Vec<String>,
+
+    /// Index method (e.g., `BTree`).
+    pub method: String,
+
+    /// Whether the combination of fields must be unique.
+    pub unique: bool,
+}
+
+impl CompositeIndexMeta {
+    /// Default index method, if the `@index` directive doesn't specify one.
+    pub const DEFAULT_METHOD: &'static str = "BTree";
+
+    /// Create a `CompositeIndexMeta` from each `@index` and `@unique` directive of a `TypeDefinition`.
+    pub fn from_typedef(typ: &TypeDefinition) -> Vec<Self> {
+        typ.directives
+            .iter()
+            .filter_map(|d| {
+                let unique = match d.node.name.node.as_str() {
+                    "index" => false,
+                    "unique" => true,
+                    _ => return None,
+                };
+
+                let fields = directive_field_names(&d.node, typ)?;
+                let method = match d.node.get_argument("type").map(|v| &v.node) {
+                    Some(ConstValue::Enum(name)) => name.to_string(),
+                    Some(other) => other.to_string().trim_matches('"').to_string(),
+                    None => Self::DEFAULT_METHOD.to_string(),
+                };
+
+                Some(Self {
+                    fields,
+                    method,
+                    unique,
+                })
+            })
+            .collect()
+    }
+}
+
+/// Return the names of the `FieldDefinition`s in the `fields` argument of a directive
+/// on a `TypeDefinition`.
+fn directive_field_names(
+    d: &ConstDirective,
+    typ: &TypeDefinition,
+) -> Option<Vec<String>> {
+    match d.get_argument("fields").map(|v| &v.node) {
+        Some(ConstValue::List(values)) => Some(
+            values
+                .iter()
+                .map(|v| match v {
+                    ConstValue::String(s) => s.to_owned(),
+                    other => other.to_string(),
+                })
+                .collect(),
+        ),
+        Some(ConstValue::String(s)) => Some(vec![s.to_owned()]),
+        _ => panic!(
+            "TypeDefinition({}) has a `@{}` directive without a `fields` argument.",
+            typ.name, d.name
+        ),
+    }
+}
+
 /// Given a GraphQL document, return a two `HashSet`s - one for each
 /// unique field type, and one for each unique directive.
 pub fn build_schema_types_set(
@@ -209,12 +355,30 @@ pub struct ParsedGraphQLSchema {
     /// All unique names of union types in the schema.
     union_names: HashSet<String>,
 
+    /// All unique names of interface types in the schema.
+    interface_names: HashSet<String>,
+
+    /// Mapping of interface names to the names of the objects that implement them.
+    interface_implementors: HashMap<String, BTreeSet<String>>,
+
     /// All objects and their field names and types, indexed by object name.
     object_field_mappings: HashMap<String, BTreeMap<String, String>>,
 
     /// All unique names of types for which tables should _not_ be created.
     virtual_type_names: HashSet<String>,
 
+    /// All unique names of types whose tables keep a version of each record per block height.
+    versioned_type_names: HashSet<String>,
+
+    /// Mapping of insert-only `TypeDefinition` names to what happens when a duplicate record is saved.
+    immutable_types: HashMap<String, OnDuplicate>,
+
+    /// Mapping of `TypeDefinition` names to their full-text search configuration.
+    search_meta: HashMap<String, SearchMeta>,
+
+    /// Mapping of `TypeDefinition` names to their multi-column indexes.
+    composite_indexes: HashMap<String, Vec<CompositeIndexMeta>>,
+
     /// All unique names of types that have already been parsed.
     parsed_typedef_names: HashSet<String>,
 
@@ -235,6 +399,19 @@ pub struct ParsedGraphQLSchema {
     /// All unique names of foreign key types in the schema.
     foreign_key_mappings: HashMap<String, HashMap<String, (String, String)>>,
 
+    /// Mapping of fully qualified names of `@derivedFrom` fields to the name of the child
+    /// `TypeDefinition`, and the name of its foreign key field, that they're derived from.
+    ///
+    /// Derived fields have no columns, and are left out of `type_defs`, `objects`, and
+    /// `object_ordered_fields`. They're resolved at query time.
+    derived_field_mappings: HashMap<String, (String, String)>,
+
+    /// Mapping of `TypeDefinition` names to the names of their `@computed` fields.
+    ///
+    /// Computed fields are generated by the database, and are left out of `type_defs`,
+    /// `objects`, and `object_ordered_fields`. Their columns are created with the table.
+    computed_fields: HashMap<String, Vec<String>>,
+
     /// All type definitions in the schema.
     type_defs: HashMap<String, TypeDefinition>,
 
@@ -273,8 +450,14 @@ impl Default for ParsedGraphQLSchema {
             typedef_names_to_types: HashMap::new(),
             enum_names: HashSet::new(),
             union_names: HashSet::new(),
+            interface_names: HashSet::new(),
+            interface_implementors: HashMap::new(),
             objects: HashMap::new(),
             virtual_type_names: HashSet::new(),
+            versioned_type_names: HashSet::new(),
+            immutable_types: HashMap::new(),
+            search_meta: HashMap::new(),
+            composite_indexes: HashMap::new(),
             parsed_typedef_names: HashSet::new(),
             field_type_mappings: HashMap::new(),
             object_field_mappings: HashMap::new(),
@@ -282,6 +465,8 @@ impl Default for ParsedGraphQLSchema {
             field_defs: HashMap::new(),
             field_type_optionality: HashMap::new(),
             foreign_key_mappings: HashMap::new(),
+            derived_field_mappings: HashMap::new(),
+            computed_fields: HashMap::new(),
             type_defs: HashMap::new(),
             list_field_types: HashSet::new(),
             list_type_defs: HashMap::new(),
@@ -364,6 +549,11 @@ impl ParsedGraphQLSchema {
         &self.type_defs
     }
 
+    /// All unique names of types in the schema (whether objects, enums, or scalars).
+    pub fn type_names(&self) -> &HashSet<String> {
+        &self.type_names
+    }
+
     /// Mapping of fully qualified field names to their `FieldDefinition` and `TypeDefinition` name.
     pub fn field_defs(&self) -> &HashMap<String, (FieldDefinition, String)> {
         &self.field_defs
@@ -376,6 +566,25 @@ impl ParsedGraphQLSchema {
         &self.foreign_key_mappings
     }
 
+    /// Mapping of fully qualified names of `@derivedFrom` fields to the name of the child
+    /// `TypeDefinition`, and the name of its foreign key field, that they're derived from.
+    pub fn derived_field_mappings(&self) -> &HashMap<String, (String, String)> {
+        &self.derived_field_mappings
+    }
+
+    /// Whether the given fully qualified field name is a `@derivedFrom` field.
+    pub fn is_derived_field(&self, field_id: &str) -> bool {
+        self.derived_field_mappings.contains_key(field_id)
+    }
+
+    /// Names of the `@computed` fields of the given `TypeDefinition`, in the order they're declared.
+    pub fn computed_fields(&self, name: &str) -> &[String] {
+        self.computed_fields
+            .get(name)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     /// All objects and their field names and types, indexed by object name.
     pub fn object_field_mappings(&self) -> &HashMap<String, BTreeMap<String, String>> {
         &self.object_field_mappings
@@ -432,10 +641,14 @@ impl ParsedGraphQLSchema {
     }
 
     /// Return a list of all non-enum type definitions.
+    ///
+    /// Interfaces are left out, since they're only implemented by other type definitions.
     pub fn non_enum_typdefs(&self) -> Vec<(&String, &TypeDefinition)> {
         self.type_defs
             .iter()
-            .filter(|(_, t)| !matches!(&t.kind, TypeKind::Enum(_)))
+            .filter(|(_, t)| {
+                !matches!(&t.kind, TypeKind::Enum(_) | TypeKind::Interface(_))
+            })
             .collect()
     }
 
@@ -452,6 +665,35 @@ impl ParsedGraphQLSchema {
         self.virtual_type_names.contains(name) && !self.is_enum_typedef(name)
     }
 
+    /// Whether the given field type name is a type marked with the `@versioned` directive.
+    pub fn is_versioned_typedef(&self, name: &str) -> bool {
+        self.versioned_type_names.contains(name)
+    }
+
+    /// Whether the given field type name is a type marked with the `@immutable` directive.
+    pub fn is_immutable_typedef(&self, name: &str) -> bool {
+        self.immutable_types.contains_key(name)
+    }
+
+    /// What happens when a duplicate record of the given `TypeDefinition` is saved, if it
+    /// has an `@immutable` directive.
+    pub fn on_duplicate(&self, name: &str) -> Option<OnDuplicate> {
+        self.immutable_types.get(name).copied()
+    }
+
+    /// Full-text search configuration of the given `TypeDefinition`, if it has a `@search` directive.
+    pub fn search_meta(&self, name: &str) -> Option<&SearchMeta> {
+        self.search_meta.get(name)
+    }
+
+    /// Multi-column indexes of the given `TypeDefinition`, declared with `@index` or `@unique` directives.
+    pub fn composite_indexes(&self, name: &str) -> &[CompositeIndexMeta] {
+        self.composite_indexes
+            .get(name)
+            .map(|v| v.as_slice())
+            .unwrap_or_default()
+    }
+
     /// Whether the given field type name is an enum type.
     pub fn is_enum_typedef(&self, name: &str) -> bool {
         self.enum_names.contains(name)
@@ -472,6 +714,19 @@ impl ParsedGraphQLSchema {
         self.union_names.contains(name)
     }
 
+    /// Whether the given field type name is an interface type.
+    pub fn is_interface_typedef(&self, name: &str) -> bool {
+        self.interface_names.contains(name)
+    }
+
+    /// Names of the objects that implement the given interface, in alphabetical order.
+    pub fn interface_implementors(&self, name: &str) -> Vec<&String> {
+        self.interface_implementors
+            .get(name)
+            .map(|names| names.iter().collect())
+            .unwrap_or_default()
+    }
+
     /// Return the GraphQL type for a given `FieldDefinition` name.
     fn field_type(&self, cond: &str, name: &str) -> Option<&String> {
         match self.object_field_mappings().get(cond) {
@@ -541,6 +796,83 @@ impl SchemaDecoder {
             self.decode_type_system_definifion(def)?;
         }
         self.build_typedef_names_to_types();
+
+        for (obj_name, o) in self.parsed_graphql_schema.objects.iter() {
+            for f in o.fields.iter() {
+                GraphQLSchemaValidator::field_is_not_interface_type(
+                    &f.node,
+                    obj_name,
+                    &self.parsed_graphql_schema.interface_names,
+                );
+            }
+        }
+
+        for (interface_name, implementors) in
+            self.parsed_graphql_schema.interface_implementors.iter()
+        {
+            for obj_name in implementors.iter() {
+                GraphQLSchemaValidator::object_implements_interface(
+                    obj_name,
+                    &self.parsed_graphql_schema.objects[obj_name],
+                    self.parsed_graphql_schema.is_virtual_typedef(obj_name),
+                    interface_name,
+                    self.parsed_graphql_schema.type_defs.get(interface_name),
+                );
+            }
+        }
+
+        for (fid, (child_name, child_field_name)) in
+            self.parsed_graphql_schema.derived_field_mappings.iter()
+        {
+            let parent_name = &self.parsed_graphql_schema.field_defs[fid].1;
+            GraphQLSchemaValidator::derived_field_references_parent(
+                fid,
+                parent_name,
+                &field_id(child_name, child_field_name),
+                self.parsed_graphql_schema
+                    .field_defs
+                    .get(&field_id(child_name, child_field_name))
+                    .map(|(f, _)| f),
+            );
+        }
+
+        for (f, obj_name) in self.parsed_graphql_schema.field_defs.values() {
+            if let Some(value) = field_default_value(f) {
+                let field_type = field_type_name(f);
+                GraphQLSchemaValidator::default_directive_is_well_formed(
+                    f,
+                    obj_name,
+                    self.parsed_graphql_schema.is_virtual_typedef(obj_name),
+                    &value,
+                    self.parsed_graphql_schema
+                        .is_enum_typedef(&field_type)
+                        .then(|| {
+                            &self.parsed_graphql_schema.object_field_mappings[&field_type]
+                        }),
+                );
+            }
+
+            if let Some(index_type) = field_index_type(f) {
+                GraphQLSchemaValidator::indexed_field_is_well_formed(
+                    f,
+                    obj_name,
+                    &index_type,
+                );
+            }
+
+            if computed_field_sql(f).is_some() {
+                GraphQLSchemaValidator::computed_field_is_scalar(
+                    f,
+                    obj_name,
+                    // Base scalars are only added once the schema has been parsed.
+                    self.parsed_graphql_schema
+                        .type_defs
+                        .get(&field_type_name(f))
+                        .map_or(true, |t| matches!(t.kind, TypeKind::Scalar)),
+                );
+            }
+        }
+
         Ok(())
     }
 
@@ -562,6 +894,7 @@ impl SchemaDecoder {
                 TypeKind::Object(o) => self.decode_object_type(name, node, o),
                 TypeKind::Enum(e) => self.decode_enum_type(name, e),
                 TypeKind::Union(u) => self.decode_union_type(name, node, u),
+                TypeKind::Interface(i) => self.decode_interface_type(name, i),
                 TypeKind::Scalar => {
                     self.parsed_graphql_schema.scalar_names.insert(name.clone());
                 }
@@ -594,6 +927,38 @@ impl SchemaDecoder {
         }
     }
 
+    fn decode_interface_type(&mut self, interface_name: String, i: &InterfaceType) {
+        GraphQLSchemaValidator::check_disallowed_graphql_typedef_name(&interface_name);
+
+        self.parsed_graphql_schema
+            .interface_names
+            .insert(interface_name.clone());
+
+        // Interface fields are cached so that interface-typed queries can be
+        // validated, but interfaces have no columns of their own.
+        let mut field_mapping = BTreeMap::new();
+        for field in i.fields.iter() {
+            let field_name = field.node.name.to_string();
+            let field_typ_name = field_type_name(&field.node);
+            let fid = field_id(&interface_name, &field_name);
+
+            field_mapping.insert(field_name, field_typ_name.clone());
+            self.parsed_graphql_schema
+                .field_type_optionality
+                .insert(fid.clone(), field.node.ty.node.nullable);
+            self.parsed_graphql_schema
+                .field_type_mappings
+                .insert(fid.clone(), field_typ_name);
+            self.parsed_graphql_schema
+                .field_defs
+                .insert(fid, (field.node.clone(), interface_name.clone()));
+        }
+
+        self.parsed_graphql_schema
+            .object_field_mappings
+            .insert(interface_name, field_mapping);
+    }
+
     fn decode_union_type(
         &mut self,
         union_name: String,
@@ -781,6 +1146,14 @@ impl SchemaDecoder {
             .parsed_typedef_names
             .insert(obj_name.clone());
 
+        for interface in o.implements.iter() {
+            self.parsed_graphql_schema
+                .interface_implementors
+                .entry(interface.node.to_string())
+                .or_default()
+                .insert(obj_name.clone());
+        }
+
         let is_virtual = node
             .directives
             .iter()
@@ -795,14 +1168,70 @@ impl SchemaDecoder {
             GraphQLSchemaValidator::virtual_type_has_no_id_field(o, &obj_name);
         }
 
+        let is_versioned = node
+            .directives
+            .iter()
+            .any(|d| d.node.name.to_string() == "versioned");
+
+        if is_versioned {
+            GraphQLSchemaValidator::versioned_type_is_not_virtual(is_virtual, &obj_name);
+            GraphQLSchemaValidator::versioned_type_has_no_unique_fields(o, &obj_name);
+
+            self.parsed_graphql_schema
+                .versioned_type_names
+                .insert(obj_name.clone());
+        }
+
+        if let Some(on_duplicate) = OnDuplicate::from_typedef(&node) {
+            GraphQLSchemaValidator::immutable_type_is_well_formed(
+                &obj_name,
+                is_virtual,
+                is_versioned,
+            );
+
+            self.parsed_graphql_schema
+                .immutable_types
+                .insert(obj_name.clone(), on_duplicate);
+        }
+
+        if let Some(meta) = SearchMeta::from_typedef(&node) {
+            GraphQLSchemaValidator::search_directive_is_well_formed(
+                o, &obj_name, is_virtual, &meta,
+            );
+
+            self.parsed_graphql_schema
+                .search_meta
+                .insert(obj_name.clone(), meta);
+        }
+
+        let composite_indexes = CompositeIndexMeta::from_typedef(&node);
+        if !composite_indexes.is_empty() {
+            for meta in composite_indexes.iter() {
+                GraphQLSchemaValidator::composite_index_is_well_formed(
+                    o,
+                    &obj_name,
+                    is_virtual,
+                    is_versioned,
+                    meta,
+                );
+            }
+
+            self.parsed_graphql_schema
+                .composite_indexes
+                .insert(obj_name.clone(), composite_indexes);
+        }
+
         // Since we have to use this manual `is_list_type` for each field, we might as well
         // keep track of how many m2m fields we have for this object here. We could also move this
         // logic to the `GraphQLSchemaValidator` itself, but that means we'd have to copy over the
         // `is_list_type` logic there as well.
         let mut m2m_field_count = 0;
 
+        // Position of each field amongst the fields that have columns.
+        let mut i = 0;
+
         let mut field_mapping = BTreeMap::new();
-        for (i, field) in o.fields.iter().enumerate() {
+        for field in o.fields.iter() {
             GraphQLSchemaValidator::id_field_is_type_id(&field.node, &obj_name);
 
             let field_name = field.node.name.to_string();
@@ -811,6 +1240,56 @@ impl SchemaDecoder {
 
             GraphQLSchemaValidator::ensure_fielddef_is_not_nested_list(&field.node);
 
+            // Derived fields are resolved from the foreign key on the child `TypeDefinition`,
+            // so they're only cached for the purpose of querying.
+            if let Some(child_field_name) = derived_from_field(&field.node) {
+                GraphQLSchemaValidator::derived_field_is_list(&field.node, &obj_name);
+
+                let field_typ_name = field_type_name(&field.node);
+                self.parsed_graphql_schema
+                    .derived_field_mappings
+                    .insert(fid.clone(), (field_typ_name.clone(), child_field_name));
+                field_mapping.insert(field_name, field_typ_name.clone());
+                self.parsed_graphql_schema
+                    .field_type_optionality
+                    .insert(fid.clone(), field.node.ty.node.nullable);
+                self.parsed_graphql_schema
+                    .field_type_mappings
+                    .insert(fid.clone(), field_typ_name);
+                self.parsed_graphql_schema
+                    .field_defs
+                    .insert(fid, (field.node.clone(), obj_name.clone()));
+                continue;
+            }
+
+            // Computed fields are generated by the database from the other columns of a row,
+            // so they're never written by the indexer.
+            if computed_field_sql(&field.node).is_some() {
+                GraphQLSchemaValidator::computed_field_is_well_formed(
+                    &field.node,
+                    &obj_name,
+                    is_virtual,
+                );
+
+                let field_typ_name = field_type_name(&field.node);
+                self.parsed_graphql_schema
+                    .computed_fields
+                    .entry(obj_name.clone())
+                    .or_default()
+                    .push(field_name.clone());
+                field_mapping.insert(field_name, field_typ_name.clone());
+                self.parsed_graphql_schema
+                    .field_type_optionality
+                    .insert(fid.clone(), field.node.ty.node.nullable);
+                self.parsed_graphql_schema
+                    .field_type_mappings
+                    .insert(fid.clone(), field_typ_name);
+                self.parsed_graphql_schema
+                    .field_defs
+                    .insert(fid, (field.node.clone(), obj_name.clone()));
+                continue;
+            }
+
             self.parsed_graphql_schema
                 .object_ordered_fields
                 .entry(obj_name.clone())
@@ -925,7 +1404,30 @@ impl SchemaDecoder {
             self.parsed_graphql_schema
                 .field_defs
                 .insert(fid, (field.node.clone(), obj_name.clone()));
+
+            i += 1;
+        }
+
+        // Leave derived and computed fields out of the cached `TypeDefinition`, since the
+        // indexer never writes to them.
+        let is_written = |f: &FieldDefinition| {
+            derived_from_field(f).is_none() && computed_field_sql(f).is_none()
+        };
+        if o.fields.iter().any(|f| !is_written(&f.node)) {
+            let mut obj = o.clone();
+            obj.fields.retain(|f| is_written(&f.node));
+
+            let mut node = node;
+            node.kind = TypeKind::Object(obj.clone());
+
+            self.parsed_graphql_schema
+                .objects
+                .insert(obj_name.clone(), obj);
+            self.parsed_graphql_schema
+                .type_defs
+                .insert(obj_name.clone(), node);
         }
+
         self.parsed_graphql_schema
             .object_field_mappings
             .insert(obj_name, field_mapping);
@@ -1329,6 +1831,574 @@ type Bar @entity {
     id: ID!
     foo: [Foo!]! @join(on:name)
 }
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_caches_derived_fields_without_columns() {
+        let schema = r#"
+type Wallet @entity {
+    id: ID!
+}
+
+type Account @entity {
+    id: ID!
+    positions: [Position!]! @derivedFrom(field: "account")
+    wallets: [Wallet!]!
+}
+
+type Position @entity {
+    id: ID!
+    account: Account!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert!(parsed.is_derived_field("Account.positions"));
+        assert_eq!(
+            parsed.derived_field_mappings().get("Account.positions"),
+            Some(&("Position".to_string(), "account".to_string()))
+        );
+        assert_eq!(
+            parsed.graphql_type(Some(&"Account".to_string()), "positions"),
+            Some(&"Position".to_string())
+        );
+
+        // Derived fields are left out of the cached object, and of the positions
+        // of the fields that follow them.
+        let fields = parsed.objects()["Account"]
+            .fields
+            .iter()
+            .map(|f| f.node.name.to_string())
+            .collect::<Vec<String>>();
+        assert_eq!(fields, vec!["id", "wallets"]);
+        assert_eq!(
+            parsed.join_table_meta()["Account"][0]
+                .parent()
+                .child_position,
+            Some(1)
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(Account.positions) is derived from FieldDefinition(Position.owner), which does not exist."
+    )]
+    fn test_schema_validator_derived_field_references_parent() {
+        let schema = r#"
+type Account @entity {
+    id: ID!
+    positions: [Position!]! @derivedFrom(field: "owner")
+}
+
+type Position @entity {
+    id: ID!
+    account: Account!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_caches_versioned_typedefs() {
+        let schema = r#"
+type Wallet @entity @versioned {
+    id: ID!
+    balance: UInt8!
+}
+
+type Account @entity {
+    id: ID!
+    wallet: Wallet!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert!(parsed.is_versioned_typedef("Wallet"));
+        assert!(!parsed.is_versioned_typedef("Account"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(address) on versioned TypeDefinition(Wallet) cannot contain a `@unique` directive."
+    )]
+    fn test_schema_validator_versioned_type_has_no_unique_fields() {
+        let schema = r#"
+type Wallet @entity @versioned {
+    id: ID!
+    address: Address! @unique
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_caches_immutable_typedefs() {
+        let schema = r#"
+type Swap @entity @immutable {
+    id: ID!
+    amount: UInt8!
+}
+
+type Deposit @entity @immutable(onDuplicate: Ignore) {
+    id: ID!
+    amount: UInt8!
+}
+
+type Account @entity {
+    id: ID!
+    balance: UInt8!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert!(parsed.is_immutable_typedef("Swap"));
+        assert_eq!(parsed.on_duplicate("Swap"), Some(OnDuplicate::Error));
+        assert_eq!(parsed.on_duplicate("Deposit"), Some(OnDuplicate::Ignore));
+        assert!(!parsed.is_immutable_typedef("Account"));
+        assert_eq!(parsed.on_duplicate("Account"), None);
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Versioned TypeDefinition(Swap) cannot contain an `@immutable` directive."
+    )]
+    fn test_schema_validator_immutable_type_is_not_versioned() {
+        let schema = r#"
+type Swap @entity @versioned @immutable {
+    id: ID!
+    amount: UInt8!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_caches_search_meta() {
+        let schema = r#"
+type Post @entity @search(fields: ["title", "body"], language: "simple") {
+    id: ID!
+    title: Charfield!
+    body: Blob
+}
+
+type Comment @entity @search(fields: ["body"]) {
+    id: ID!
+    body: Charfield!
+}
+
+type Account @entity {
+    id: ID!
+    name: Charfield!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.search_meta("Post"),
+            Some(&SearchMeta {
+                fields: vec!["title".to_string(), "body".to_string()],
+                language: "simple".to_string(),
+            })
+        );
+        assert_eq!(
+            parsed.search_meta("Comment").unwrap().language,
+            SearchMeta::DEFAULT_LANGUAGE
+        );
+        assert!(parsed.search_meta("Account").is_none());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(amount) on TypeDefinition(Post) cannot be searched, since it is not a text field."
+    )]
+    fn test_schema_validator_search_fields_are_text() {
+        let schema = r#"
+type Post @entity @search(fields: ["amount"]) {
+    id: ID!
+    amount: UInt8!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_caches_computed_fields() {
+        let schema = r#"
+type Person @entity {
+    id: ID!
+    name: Charfield!
+    initials: Charfield! @computed(sql: "left(name, 1)")
+    age: UInt1! @default(value: 18)
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert_eq!(parsed.computed_fields("Person"), &["initials".to_string()]);
+        assert!(parsed.computed_fields("Account").is_empty());
+
+        // Computed fields can be queried, but are never written by the indexer.
+        assert_eq!(
+            parsed.graphql_type(Some(&"Person".to_string()), "initials"),
+            Some(&"Charfield".to_string())
+        );
+        assert!(!parsed.object_ordered_fields()["Person"].iter().any(|f| f
+            .0
+            .name
+            .to_string()
+            == "initials"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(age) on TypeDefinition(Person) has a `@default` value of `\"old\"`, which is not a valid `UInt1`."
+    )]
+    fn test_schema_validator_default_value_matches_field_type() {
+        let schema = r#"
+type Person @entity {
+    id: ID!
+    age: UInt1! @default(value: "old")
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(age) on TypeDefinition(Person) has a `@default` value of `300`, which is out of range for `UInt1`."
+    )]
+    fn test_schema_validator_default_value_is_in_range() {
+        let schema = r#"
+type Person @entity {
+    id: ID!
+    age: UInt1! @default(value: 300)
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(offset) on TypeDefinition(Person) has a `@default` value of `-129`, which is out of range for `Int1`."
+    )]
+    fn test_schema_validator_negative_default_value_is_in_range() {
+        let schema = r#"
+type Person @entity {
+    id: ID!
+    offset: Int1! @default(value: -129)
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(owner) on TypeDefinition(Wallet) has a `@computed` directive, but is not a scalar type."
+    )]
+    fn test_schema_validator_computed_field_is_scalar() {
+        let schema = r#"
+type Person @entity {
+    id: ID!
+}
+
+type Wallet @entity {
+    id: ID!
+    owner: Person! @computed(sql: "id")
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_caches_composite_indexes() {
+        let schema = r#"
+type Position @entity @index(fields: ["owner", "pool"]) @unique(fields: ["pool", "nonce"]) @index(fields: ["owner", "nonce"], type: BTree) {
+    id: ID!
+    owner: Address!
+    pool: ContractId!
+    nonce: UInt8!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert_eq!(
+            parsed.composite_indexes("Position"),
+            &[
+                CompositeIndexMeta {
+                    fields: vec!["owner".to_string(), "pool".to_string()],
+                    method: "BTree".to_string(),
+                    unique: false,
+                },
+                CompositeIndexMeta {
+                    fields: vec!["pool".to_string(), "nonce".to_string()],
+                    method: "BTree".to_string(),
+                    unique: true,
+                },
+                CompositeIndexMeta {
+                    fields: vec!["owner".to_string(), "nonce".to_string()],
+                    method: "BTree".to_string(),
+                    unique: false,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "TypeDefinition(Position) has an `@index` directive on FieldDefinition(pool), which does not exist."
+    )]
+    fn test_schema_validator_composite_index_fields_exist() {
+        let schema = r#"
+type Position @entity @index(fields: ["owner", "pool"]) {
+    id: ID!
+    owner: Address!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "TypeDefinition(Position) has an `@index` directive with an unsupported type: Hash."
+    )]
+    fn test_schema_validator_composite_index_is_not_hash() {
+        let schema = r#"
+type Position @entity @index(fields: ["owner", "pool"], type: Hash) {
+    id: ID!
+    owner: Address!
+    pool: ContractId!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(amount) on TypeDefinition(Swap) of type `UInt8` cannot have a `Trigram` index."
+    )]
+    fn test_schema_validator_trigram_index_is_on_text_field() {
+        let schema = r#"
+type Swap @entity {
+    id: ID!
+    amount: UInt8! @indexed(type: Trigram)
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parser_caches_interface_implementors() {
+        let schema = r#"
+interface Event {
+    id: ID!
+    block: UInt4!
+}
+
+type Swap implements Event @entity {
+    id: ID!
+    block: UInt4!
+    amount: UInt8!
+}
+
+type Deposit implements Event @entity {
+    id: ID!
+    block: UInt4!
+}
+"#;
+
+        let parsed = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        assert!(parsed.is_interface_typedef("Event"));
+        assert!(!parsed.is_interface_typedef("Swap"));
+        assert_eq!(
+            parsed.interface_implementors("Event"),
+            vec!["Deposit", "Swap"]
+        );
+        assert_eq!(
+            parsed.graphql_type(Some(&"Event".to_string()), "block"),
+            Some(&"UInt4".to_string())
+        );
+
+        // Interfaces don't have tables.
+        assert!(parsed
+            .non_enum_typdefs()
+            .iter()
+            .all(|(name, _)| name.as_str() != "Event"));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "FieldDefinition(block) on TypeDefinition(Deposit) must be of type `UInt4!`, as declared by Interface(Event)."
+    )]
+    fn test_schema_validator_implementor_fields_match_interface() {
+        let schema = r#"
+interface Event {
+    id: ID!
+    block: UInt4!
+}
+
+type Deposit implements Event @entity {
+    id: ID!
+    block: UInt8!
+}
+"#;
+
+        let _ = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "TypeDefinition(Deposit) implements Interface(Event), but does not contain FieldDefinition(block)."
+    )]
+    fn test_schema_validator_implementor_contains_interface_fields() {
+        let schema = r#"
+interface Event {
+    id: ID!
+    block: UInt4!
+}
+
+type Deposit implements Event @entity {
+    id: ID!
+}
 "#;
 
         let _ = ParsedGraphQLSchema::new(
@@ -37,3 +37,39 @@ impl ObjectCol {
         "Object"
     }
 }
+
+/// Represents the block height range column of a `@versioned` `TypeDefinition`.
+pub struct BlockRangeCol;
+impl BlockRangeCol {
+    pub fn to_lowercase_string() -> String {
+        "_block_range".to_string()
+    }
+
+    pub fn to_lowercase_str() -> &'static str {
+        "_block_range"
+    }
+}
+
+/// Represents the block height column of an `@immutable` `TypeDefinition`.
+pub struct BlockHeightCol;
+impl BlockHeightCol {
+    pub fn to_lowercase_string() -> String {
+        "_block_height".to_string()
+    }
+
+    pub fn to_lowercase_str() -> &'static str {
+        "_block_height"
+    }
+}
+
+/// Represents the full-text search column of a `TypeDefinition` with a `@search` directive.
+pub struct SearchCol;
+impl SearchCol {
+    pub fn to_lowercase_string() -> String {
+        "_search".to_string()
+    }
+
+    pub fn to_lowercase_str() -> &'static str {
+        "_search"
+    }
+}
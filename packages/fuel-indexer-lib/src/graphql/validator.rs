@@ -1,8 +1,64 @@
-use crate::{constants::*, graphql::MAX_FOREIGN_KEY_LIST_FIELDS};
+use crate::{
+    constants::*,
+    graphql::{
+        computed_field_sql, field_default_value, types::IdCol, CompositeIndexMeta,
+        SearchMeta, MAX_FOREIGN_KEY_LIST_FIELDS,
+    },
+};
 use async_graphql_parser::types::{
     FieldDefinition, ObjectType, TypeDefinition, TypeKind,
 };
-use std::collections::HashSet;
+use async_graphql_value::ConstValue;
+use std::collections::{BTreeMap, HashSet};
+
+/// Scalar types whose values can be included in a full-text search document.
+const SEARCHABLE_FIELD_TYPES: [&str; 3] = ["Charfield", "Blob", "HexString"];
+
+/// Index methods that can be used by an `@index` directive.
+///
+/// Postgres hash indexes can only span a single column.
+const COMPOSITE_INDEX_METHODS: [&str; 1] = ["BTree"];
+
+/// Index methods that can be used by an `@indexed` directive.
+const FIELD_INDEX_METHODS: [&str; 3] = ["BTree", "Hash", "Trigram"];
+
+/// Scalar types whose fields are stored as text, and can thus have a trigram index.
+const TRIGRAM_INDEXABLE_FIELD_TYPES: [&str; 19] = [
+    "Address",
+    "AssetId",
+    "Blob",
+    "BlockId",
+    "Bytes32",
+    "Bytes4",
+    "Bytes64",
+    "Bytes8",
+    "Charfield",
+    "ContractId",
+    "HexString",
+    "ID",
+    "Identity",
+    "MessageId",
+    "Nonce",
+    "Salt",
+    "Signature",
+    "TxId",
+    "UID",
+];
+
+/// Scalar types whose fields can have a `@default` value, along with enum types.
+const DEFAULTABLE_FIELD_TYPES: [&str; 11] = [
+    "BlockHeight",
+    "Boolean",
+    "Charfield",
+    "Int1",
+    "Int16",
+    "Int4",
+    "Int8",
+    "UInt1",
+    "UInt16",
+    "UInt4",
+    "UInt8",
+];
 
 /// General container used to store a set of GraphQL schema validation functions.
 pub struct GraphQLSchemaValidator;
@@ -101,6 +157,191 @@ impl GraphQLSchemaValidator {
         }
     }
 
+    /// Ensure `TypeDefinition`s that are marked as versioned are not also virtual.
+    ///
+    /// Virtual types have no table in which to keep versions.
+    pub fn versioned_type_is_not_virtual(is_virtual: bool, obj_name: &str) {
+        if is_virtual {
+            panic!("Virtual TypeDefinition({obj_name}) cannot contain a `@versioned` directive.");
+        }
+    }
+
+    /// Ensure `TypeDefinition`s that are marked as immutable are neither virtual nor versioned.
+    ///
+    /// Immutable records are only ever inserted, so there's nothing to version.
+    pub fn immutable_type_is_well_formed(
+        obj_name: &str,
+        is_virtual: bool,
+        is_versioned: bool,
+    ) {
+        if is_virtual {
+            panic!("Virtual TypeDefinition({obj_name}) cannot contain an `@immutable` directive.");
+        }
+
+        if is_versioned {
+            panic!("Versioned TypeDefinition({obj_name}) cannot contain an `@immutable` directive.");
+        }
+    }
+
+    /// Ensure a `@search` directive is only used on non-virtual `TypeDefinition`s, with a valid
+    /// text search configuration, and only names text `FieldDefinition`s of the `TypeDefinition`.
+    pub fn search_directive_is_well_formed(
+        o: &ObjectType,
+        obj_name: &str,
+        is_virtual: bool,
+        meta: &SearchMeta,
+    ) {
+        if is_virtual {
+            panic!("Virtual TypeDefinition({obj_name}) cannot contain a `@search` directive.");
+        }
+
+        if meta.fields.is_empty() {
+            panic!("TypeDefinition({obj_name}) has a `@search` directive without any fields.");
+        }
+
+        // The text search configuration is interpolated into the table definition.
+        if !meta
+            .language
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_')
+        {
+            panic!(
+                "TypeDefinition({obj_name}) has a `@search` directive with an invalid language: {}.",
+                meta.language
+            );
+        }
+
+        for name in meta.fields.iter() {
+            let field = o
+                .fields
+                .iter()
+                .find(|f| &f.node.name.to_string() == name)
+                .unwrap_or_else(|| {
+                    panic!("TypeDefinition({obj_name}) has a `@search` directive on FieldDefinition({name}), which does not exist.")
+                });
+
+            let field_type = field.node.ty.node.to_string().replace('!', "");
+            if !SEARCHABLE_FIELD_TYPES.contains(&field_type.as_str()) {
+                panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) cannot be searched, since it is not a text field.");
+            }
+        }
+    }
+
+    /// Ensure an `@index` or `@unique` directive on a `TypeDefinition` names at least two
+    /// distinct, non-list `FieldDefinition`s of a non-virtual `TypeDefinition`.
+    pub fn composite_index_is_well_formed(
+        o: &ObjectType,
+        obj_name: &str,
+        is_virtual: bool,
+        is_versioned: bool,
+        meta: &CompositeIndexMeta,
+    ) {
+        let directive = if meta.unique { "@unique" } else { "@index" };
+
+        if is_virtual {
+            panic!("Virtual TypeDefinition({obj_name}) cannot contain an `{directive}` directive.");
+        }
+
+        // Each version of a record is a separate row, so values repeat across versions.
+        if is_versioned && meta.unique {
+            panic!("Versioned TypeDefinition({obj_name}) cannot contain a `@unique` directive.");
+        }
+
+        if !COMPOSITE_INDEX_METHODS.contains(&meta.method.as_str()) {
+            panic!(
+                "TypeDefinition({obj_name}) has an `{directive}` directive with an unsupported type: {}.",
+                meta.method
+            );
+        }
+
+        // Single field indexes are declared on the field itself.
+        let distinct = meta.fields.iter().collect::<HashSet<&String>>();
+        if meta.fields.len() < 2 || distinct.len() != meta.fields.len() {
+            panic!("TypeDefinition({obj_name}) has an `{directive}` directive that does not name at least two distinct fields.");
+        }
+
+        for name in meta.fields.iter() {
+            let field = o
+                .fields
+                .iter()
+                .find(|f| &f.node.name.to_string() == name)
+                .unwrap_or_else(|| {
+                    panic!("TypeDefinition({obj_name}) has an `{directive}` directive on FieldDefinition({name}), which does not exist.")
+                });
+
+            if field.node.ty.node.to_string().contains('[') {
+                panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) cannot be indexed, since it is a list type.");
+            }
+        }
+    }
+
+    /// Ensure `TypeDefinition`s that are marked as versioned don't contain any `@unique` fields.
+    ///
+    /// Each version of a record is a separate row, so values repeat across versions.
+    pub fn versioned_type_has_no_unique_fields(o: &ObjectType, obj_name: &str) {
+        if let Some(f) = o.fields.iter().find(|f| {
+            f.node
+                .directives
+                .iter()
+                .any(|d| d.node.name.to_string() == "unique")
+        }) {
+            let name = f.node.name.to_string();
+            panic!("FieldDefinition({name}) on versioned TypeDefinition({obj_name}) cannot contain a `@unique` directive.");
+        }
+    }
+
+    /// Ensure a `FieldDefinition` doesn't reference an interface, since interfaces have no
+    /// table that a foreign key could reference.
+    pub fn field_is_not_interface_type(
+        f: &FieldDefinition,
+        obj_name: &str,
+        interface_names: &HashSet<String>,
+    ) {
+        let name = f.name.to_string();
+        let field_type = f.ty.node.to_string().replace(['[', ']', '!'], "");
+        if interface_names.contains(&field_type) {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) cannot reference Interface({field_type}).");
+        }
+    }
+
+    /// Ensure an object that implements an interface is a non-virtual entity, and declares
+    /// each of the interface's `FieldDefinition`s with the same type.
+    pub fn object_implements_interface(
+        obj_name: &str,
+        o: &ObjectType,
+        is_virtual: bool,
+        interface_name: &str,
+        interface: Option<&TypeDefinition>,
+    ) {
+        let i = match interface.map(|t| &t.kind) {
+            Some(TypeKind::Interface(i)) => i,
+            _ => panic!("TypeDefinition({obj_name}) implements Interface({interface_name}), which does not exist."),
+        };
+
+        if is_virtual {
+            panic!("Virtual TypeDefinition({obj_name}) cannot implement Interface({interface_name}).");
+        }
+
+        for interface_field in i.fields.iter() {
+            let name = interface_field.node.name.to_string();
+            let interface_type = interface_field.node.ty.node.to_string();
+
+            let field = o.fields.iter().find(|f| f.node.name.to_string() == name);
+            match field {
+                Some(f) if f.node.ty.node.to_string() != interface_type => panic!(
+                    "FieldDefinition({name}) on TypeDefinition({obj_name}) must be of type `{interface_type}`, as declared by Interface({interface_name})."
+                ),
+                Some(f) if f.node.directives.iter().any(|d| d.node.name.to_string() == "derivedFrom") => panic!(
+                    "FieldDefinition({name}) on TypeDefinition({obj_name}) cannot be a derived field, since it is declared by Interface({interface_name})."
+                ),
+                Some(_) => {}
+                None => panic!(
+                    "TypeDefinition({obj_name}) implements Interface({interface_name}), but does not contain FieldDefinition({name})."
+                ),
+            }
+        }
+    }
+
     /// Ensure that any `FieldDefinition` that itself is a foreign relationship, does not contain
     /// a `@unique` directive.
     pub fn foreign_key_field_contains_no_unique_directive(
@@ -117,6 +358,175 @@ impl GraphQLSchemaValidator {
         }
     }
 
+    /// Ensure that a `FieldDefinition` with a `@derivedFrom` directive is a list type.
+    pub fn derived_field_is_list(f: &FieldDefinition, obj_name: &str) {
+        let name = f.name.to_string();
+        if f.ty.node.to_string().matches(['[', ']']).count() != 2 {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@derivedFrom` directive, but is not a list type.");
+        }
+    }
+
+    /// Ensure that a `FieldDefinition` with a `@default` directive is a non-nullable scalar or
+    /// enum field of a non-virtual `TypeDefinition`, and that its value is of the field's type.
+    pub fn default_directive_is_well_formed(
+        f: &FieldDefinition,
+        obj_name: &str,
+        is_virtual: bool,
+        value: &ConstValue,
+        enum_values: Option<&BTreeMap<String, String>>,
+    ) {
+        let name = f.name.to_string();
+        let field_type = f.ty.node.to_string().replace('!', "");
+
+        if is_virtual {
+            panic!("FieldDefinition({name}) on virtual TypeDefinition({obj_name}) cannot contain a `@default` directive.");
+        }
+
+        if f.ty.node.nullable {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@default` directive, but is nullable.");
+        }
+
+        if name == IdCol::to_lowercase_str()
+            || (enum_values.is_none()
+                && !DEFAULTABLE_FIELD_TYPES.contains(&field_type.as_str()))
+        {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) of type `{field_type}` cannot have a `@default` value.");
+        }
+
+        let is_valid = match (value, enum_values) {
+            (ConstValue::Enum(v), Some(values)) => values.contains_key(v.as_str()),
+            (_, Some(_)) => false,
+            (ConstValue::Boolean(_), None) => field_type == "Boolean",
+            (ConstValue::String(_), None) => field_type == "Charfield",
+            (ConstValue::Number(n), None) => match field_type.as_str() {
+                "BlockHeight" | "UInt1" | "UInt16" | "UInt4" | "UInt8" => n.is_u64(),
+                "Int1" | "Int16" | "Int4" | "Int8" => n.is_i64(),
+                _ => false,
+            },
+            _ => false,
+        };
+
+        if !is_valid {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@default` value of `{value}`, which is not a valid `{field_type}`.");
+        }
+
+        // Integers wider than 64 bits can't be written in a schema, so only the
+        // narrower types need their range checked.
+        if let ConstValue::Number(n) = value {
+            let in_range = match field_type.as_str() {
+                "Int1" => n.as_i64().map_or(false, |v| i8::try_from(v).is_ok()),
+                "Int4" => n.as_i64().map_or(false, |v| i32::try_from(v).is_ok()),
+                "UInt1" => n.as_u64().map_or(false, |v| u8::try_from(v).is_ok()),
+                "BlockHeight" | "UInt4" => {
+                    n.as_u64().map_or(false, |v| u32::try_from(v).is_ok())
+                }
+                _ => true,
+            };
+
+            if !in_range {
+                panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@default` value of `{value}`, which is out of range for `{field_type}`.");
+            }
+        }
+    }
+
+    /// Ensure that the type of an `@indexed` directive on a `FieldDefinition` is supported by the
+    /// field, and that only `BTree` indexes are combined with a `@unique` directive.
+    pub fn indexed_field_is_well_formed(
+        f: &FieldDefinition,
+        obj_name: &str,
+        index_type: &str,
+    ) {
+        let name = f.name.to_string();
+        let field_type = f.ty.node.to_string().replace('!', "");
+
+        if !FIELD_INDEX_METHODS.contains(&index_type) {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has an `@indexed` directive with an unsupported type: {index_type}.");
+        }
+
+        if index_type == "Trigram"
+            && !TRIGRAM_INDEXABLE_FIELD_TYPES.contains(&field_type.as_str())
+        {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) of type `{field_type}` cannot have a `Trigram` index.");
+        }
+
+        // Postgres can only enforce uniqueness with BTree indexes.
+        let is_unique = f
+            .directives
+            .iter()
+            .any(|d| d.node.name.to_string() == "unique");
+        if is_unique && index_type != CompositeIndexMeta::DEFAULT_METHOD {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) cannot contain both a `@unique` directive and a `{index_type}` index.");
+        }
+    }
+
+    /// Ensure that a `FieldDefinition` with a `@computed` directive is a single, non-`ID` field of a
+    /// non-virtual `TypeDefinition`, with a non-empty SQL expression.
+    pub fn computed_field_is_well_formed(
+        f: &FieldDefinition,
+        obj_name: &str,
+        is_virtual: bool,
+    ) {
+        let name = f.name.to_string();
+
+        if is_virtual {
+            panic!("FieldDefinition({name}) on virtual TypeDefinition({obj_name}) cannot contain a `@computed` directive.");
+        }
+
+        if name == IdCol::to_lowercase_str() {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) cannot be a computed field.");
+        }
+
+        if f.ty.node.to_string().matches(['[', ']']).count() != 0 {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@computed` directive, but is a list type.");
+        }
+
+        if field_default_value(f).is_some() {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) cannot contain both a `@computed` and a `@default` directive.");
+        }
+
+        if computed_field_sql(f).map_or(true, |sql| sql.trim().is_empty()) {
+            panic!("FieldDefinition({name}) on TypeDefinition({obj_name}) has a `@computed` directive with an empty `sql` argument.");
+        }
+    }
+
+    /// Ensure that a `FieldDefinition` with a `@computed` directive is of a scalar type, since
+    /// its value is generated by the database.
+    pub fn computed_field_is_scalar(
+        f: &FieldDefinition,
+        obj_name: &str,
+        is_scalar: bool,
+    ) {
+        if !is_scalar {
+            panic!(
+                "FieldDefinition({}) on TypeDefinition({obj_name}) has a `@computed` directive, but is not a scalar type.",
+                f.name
+            );
+        }
+    }
+
+    /// Ensure that the child field from which a `@derivedFrom` `FieldDefinition` is derived
+    /// exists, and is a single foreign key reference to the parent `TypeDefinition`.
+    pub fn derived_field_references_parent(
+        field_id: &str,
+        parent_name: &str,
+        child_field_id: &str,
+        child_field: Option<&FieldDefinition>,
+    ) {
+        match child_field {
+            Some(f) => {
+                let typ = f.ty.node.to_string();
+                if typ.contains('[') || typ.replace('!', "") != parent_name {
+                    panic!(
+                        "FieldDefinition({field_id}) is derived from FieldDefinition({child_field_id}) of type `{typ}`, which is not a foreign key reference to `{parent_name}`."
+                    );
+                }
+            }
+            None => panic!(
+                "FieldDefinition({field_id}) is derived from FieldDefinition({child_field_id}), which does not exist."
+            ),
+        }
+    }
+
     /// Ensure that a given `TypeDefiniton` does not contain more than `MAX_FOREIGN_KEY_LIST_FIELDS` many-to-many relationships.
     pub fn verify_m2m_relationship_count(obj_name: &str, m2m_field_count: usize) {
         if m2m_field_count > MAX_FOREIGN_KEY_LIST_FIELDS {
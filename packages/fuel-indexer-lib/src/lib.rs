@@ -100,6 +100,14 @@ pub fn fully_qualified_namespace(namespace: &str, identifier: &str) -> String {
     format!("{}_{}", namespace, identifier)
 }
 
+/// Return the identifier under which a shadow deployment of an indexer is stored.
+///
+/// A shadow deployment syncs a new version of an indexer into its own database
+/// schema, while the previous version keeps serving queries.
+pub fn shadow_identifier(identifier: &str) -> String {
+    format!("{identifier}__shadow")
+}
+
 /// Return the name of the join table for the given entities.
 pub fn join_table_name(a: &str, b: &str) -> String {
     format!("{}s_{}s", a, b)
@@ -1,5 +1,14 @@
-use crate::{graphql::GraphQLSchema, ExecutionSource};
+use crate::{
+    graphql::{
+        enum_entities::{
+            derive_enum_entities, schema_with_enum_entities, EnumEntity, EnumEntityError,
+        },
+        GraphQLSchema,
+    },
+    ExecutionSource,
+};
 use anyhow::Result;
+use fuel_abi_types::abi::program::ProgramABI;
 use serde::{Deserialize, Serialize};
 use std::{
     fs::File,
@@ -21,6 +30,12 @@ pub enum ManifestError {
     NativeModuleError,
     #[error("File IO error: {0} {1:?}.")]
     FileError(String, #[source] std::io::Error),
+    #[error("JSON ABI error: {0} {1:?}.")]
+    JsonAbiError(String, #[source] serde_json::Error),
+    #[error("Enum entities require a contract `abi` in the manifest.")]
+    MissingAbiError,
+    #[error("Enum entity error: {0}")]
+    EnumEntityError(#[from] EnumEntityError),
 }
 
 /// Specifies which type of module is used to create this indexer.
@@ -110,6 +125,18 @@ pub struct Manifest {
     /// When set to true, the indexer will resume from the block height at which it last stopped.
     #[serde(default)]
     resumable: Option<bool>,
+
+    /// Script program whose logs this indexer should decode.
+    #[serde(default)]
+    script: Option<ScriptProgram>,
+
+    /// Predicate program whose spends this indexer should decode.
+    #[serde(default)]
+    predicate: Option<PredicateProgram>,
+
+    /// Data-carrying contract ABI enums from which GraphQL union entities are derived.
+    #[serde(default)]
+    enum_entities: Option<Vec<String>>,
 }
 
 impl Manifest {
@@ -132,7 +159,27 @@ impl Manifest {
         let mut schema = String::new();
         file.read_to_string(&mut schema)
             .map_err(|err| ManifestError::FileError(self.graphql_schema.clone(), err))?;
-        Ok(GraphQLSchema::new(schema))
+        let entities = self.derive_enum_entities()?;
+        Ok(GraphQLSchema::new(schema_with_enum_entities(
+            &schema, &entities,
+        )))
+    }
+
+    /// Derive the GraphQL union entities for the `enum_entities` of this manifest.
+    pub fn derive_enum_entities(&self) -> ManifestResult<Vec<EnumEntity>> {
+        let names = match &self.enum_entities {
+            Some(names) if !names.is_empty() => names,
+            _ => return Ok(vec![]),
+        };
+        let path = self.abi.as_ref().ok_or(ManifestError::MissingAbiError)?;
+        let mut file = File::open(path)
+            .map_err(|err| ManifestError::FileError(path.clone(), err))?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)
+            .map_err(|err| ManifestError::FileError(path.clone(), err))?;
+        let abi: ProgramABI = serde_json::from_str(&content)
+            .map_err(|err| ManifestError::JsonAbiError(path.clone(), err))?;
+        Ok(derive_enum_entities(&abi, names)?)
     }
 
     /// Derive the unique identifier for a manifest.
@@ -202,6 +249,16 @@ impl Manifest {
         self.abi = Some(abi);
     }
 
+    /// Set the script program for this indexer.
+    pub fn set_script(&mut self, script: ScriptProgram) {
+        self.script = Some(script);
+    }
+
+    /// Set the predicate program for this indexer.
+    pub fn set_predicate(&mut self, predicate: PredicateProgram) {
+        self.predicate = Some(predicate);
+    }
+
     pub fn namespace(&self) -> &str {
         &self.namespace
     }
@@ -241,6 +298,18 @@ impl Manifest {
     pub fn resumable(&self) -> Option<bool> {
         self.resumable
     }
+
+    pub fn script(&self) -> Option<&ScriptProgram> {
+        self.script.as_ref()
+    }
+
+    pub fn predicate(&self) -> Option<&PredicateProgram> {
+        self.predicate.as_ref()
+    }
+
+    pub fn enum_entities(&self) -> Option<&[String]> {
+        self.enum_entities.as_deref()
+    }
 }
 
 impl TryFrom<&str> for Manifest {
@@ -267,6 +336,31 @@ impl TryFrom<&Vec<u8>> for Manifest {
     }
 }
 
+/// Represents a script program in a `Manifest` struct.
+///
+/// Script `Log` and `LogData` receipts are emitted under the zero contract ID, so
+/// rather than subscribing by contract ID, script transactions are matched using the
+/// root of their bytecode.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ScriptProgram {
+    /// Filepath to Sway script ABI.
+    pub abi: String,
+
+    /// Hex-encoded bytecode root of the script, computed the same way as the code
+    /// root of a contract. If not set, logs from all script transactions are decoded.
+    pub bytecode_root: Option<String>,
+}
+
+/// Represents a predicate program in a `Manifest` struct.
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct PredicateProgram {
+    /// Filepath to Sway predicate ABI.
+    pub abi: String,
+
+    /// Address (predicate root) of the predicate whose coin inputs should be decoded.
+    pub address: String,
+}
+
 /// Represents contract IDs in a `Manifest` struct.
 #[derive(Debug, Deserialize, Serialize, Clone)]
 #[serde(untagged)]
@@ -1,8 +1,10 @@
 use crate::{config::IndexerConfig, defaults};
 use anyhow::Result;
+use lazy_static::lazy_static;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::{
+    collections::{HashMap, HashSet},
     env,
     fs::canonicalize,
     future::Future,
@@ -10,7 +12,10 @@ use std::{
     path::Path,
     str::FromStr,
 };
-use tokio::time::{sleep, Duration};
+use tokio::{
+    sync::broadcast,
+    time::{sleep, Duration},
+};
 use tracing::{debug, info, warn};
 use tracing_subscriber::filter::EnvFilter;
 
@@ -97,11 +102,80 @@ pub struct StopRequest {
     pub identifier: String,
 }
 
+/// Request to start indexing into the shadow schema of the specified indexer.
+///
+/// Sent from API server to indexer service.
+#[derive(Debug)]
+pub struct ShadowRequest {
+    /// The namespace of the indexer being shadowed.
+    pub namespace: String,
+    /// The identifier of the indexer being shadowed.
+    pub identifier: String,
+}
+
 /// A general request sent from the API server to the indexer service.
 #[derive(Debug)]
 pub enum ServiceRequest {
     Reload(ReloadRequest),
     Stop(StopRequest),
+    Shadow(ShadowRequest),
+}
+
+/// The records that an indexer saved in a committed transaction.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct EntityChanges {
+    pub namespace: String,
+    pub identifier: String,
+
+    /// IDs of the saved records, by `TypeId`.
+    pub changes: HashMap<i64, HashSet<String>>,
+
+    /// Height of the block whose records were saved, if known.
+    pub block_height: Option<u32>,
+}
+
+/// An indexer whose GraphQL schema was replaced by the indexer service, e.g. when
+/// its shadow version was promoted.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SchemaChange {
+    pub namespace: String,
+    pub identifier: String,
+}
+
+lazy_static! {
+    /// Broadcast of the changes committed by every indexer running in this process,
+    /// from executors to the web API.
+    static ref ENTITY_CHANGES: broadcast::Sender<EntityChanges> =
+        broadcast::channel(defaults::ENTITY_CHANGES_CHANNEL_SIZE).0;
+
+    /// Broadcast of the schemas replaced by the indexer service running in this
+    /// process, to the web API.
+    static ref SCHEMA_CHANGES: broadcast::Sender<SchemaChange> =
+        broadcast::channel(defaults::SCHEMA_CHANGES_CHANNEL_SIZE).0;
+}
+
+/// Broadcast the changes committed by an indexer to all current subscribers.
+pub fn publish_entity_changes(changes: EntityChanges) {
+    // Sending only fails if there are no subscribers, in which case nobody
+    // needs to know about the changes.
+    let _ = ENTITY_CHANGES.send(changes);
+}
+
+/// Subscribe to the changes committed by indexers from now on.
+pub fn subscribe_to_entity_changes() -> broadcast::Receiver<EntityChanges> {
+    ENTITY_CHANGES.subscribe()
+}
+
+/// Broadcast that the schema of an indexer was replaced to all current subscribers.
+pub fn publish_schema_change(change: SchemaChange) {
+    // Sending only fails if there are no subscribers, e.g. when the web API isn't
+    // running in this process.
+    let _ = SCHEMA_CHANGES.send(change);
+}
+
+/// Subscribe to the schemas replaced by the indexer service from now on.
+pub fn subscribe_to_schema_changes() -> broadcast::Receiver<SchemaChange> {
+    SCHEMA_CHANGES.subscribe()
 }
 
 /// Returns the lower hex representation of a [`sha2::SHA256`] digest of the provided input.
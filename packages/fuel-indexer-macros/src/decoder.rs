@@ -5,7 +5,10 @@ use async_graphql_parser::types::{
 use async_graphql_parser::{Pos, Positioned};
 use async_graphql_value::Name;
 use fuel_indexer_lib::{
-    graphql::{field_id, types::IdCol, ParsedGraphQLSchema, MAX_FOREIGN_KEY_LIST_FIELDS},
+    graphql::{
+        field_default_value, field_id, field_type_name, types::IdCol,
+        ParsedGraphQLSchema, MAX_FOREIGN_KEY_LIST_FIELDS,
+    },
     ExecutionSource,
 };
 use fuel_indexer_types::type_id;
@@ -28,6 +31,9 @@ pub struct ImplementationDecoder {
     /// Token stream of params passed to `::new()`.
     parameters: proc_macro2::TokenStream,
 
+    /// Token stream that replaces omitted `@default` params with their default values.
+    defaults: proc_macro2::TokenStream,
+
     /// Token stream of hasher.
     hasher: proc_macro2::TokenStream,
 
@@ -51,6 +57,7 @@ impl Default for ImplementationDecoder {
     fn default() -> Self {
         Self {
             parameters: quote! {},
+            defaults: quote! {},
             hasher: quote! {},
             struct_fields: quote! {},
             exec_source: ExecutionSource::Wasm,
@@ -78,12 +85,23 @@ impl Decoder for ImplementationDecoder {
 
                 let mut struct_fields = quote! {};
                 let mut parameters = quote! {};
+                let mut defaults = quote! {};
                 let mut hasher = quote! { Sha256::new() };
 
                 let obj_field_names = parsed
                     .object_field_mappings()
                     .get(&obj_name)
-                    .unwrap_or_else(|| panic!("TypeDefinition '{obj_name}' not found in parsed GraphQL schema."))
+                    .unwrap_or_else(|| {
+                        proc_macro_error::abort!(
+                            manifest_span(),
+                            "TypeDefinition '{}' not found in parsed GraphQL schema.",
+                            obj_name;
+                            help =? did_you_mean(
+                                &obj_name,
+                                parsed.objects().keys().map(String::as_str)
+                            )
+                        )
+                    })
                     .iter()
                     .map(|(k, _v)| k.to_owned())
                     .collect::<HashSet<String>>();
@@ -118,11 +136,31 @@ impl Decoder for ImplementationDecoder {
                         to_bytes_tokens(field_typ_name, &processed_type_result);
 
                     if can_derive_id(&obj_field_names, field_name) {
-                        parameters = parameters_tokens(
-                            &parameters,
-                            &field_name_ident,
-                            field_type_tokens,
-                        );
+                        // Fields with a `@default` value can be omitted by passing `None`.
+                        match field_default_value(&field.node).filter(|_| !nullable) {
+                            Some(value) => {
+                                let value = default_value_tokens(
+                                    &field_type_name(&field.node),
+                                    &value,
+                                );
+                                parameters = parameters_tokens(
+                                    &parameters,
+                                    &field_name_ident,
+                                    &quote! { Option<#field_type_tokens> },
+                                );
+                                defaults = quote! {
+                                    #defaults
+                                    let #field_name_ident = #field_name_ident.unwrap_or_else(|| #value);
+                                };
+                            }
+                            None => {
+                                parameters = parameters_tokens(
+                                    &parameters,
+                                    &field_name_ident,
+                                    field_type_tokens,
+                                );
+                            }
+                        }
                         if let Some(tokens) = hasher_tokens(
                             field_typ_name,
                             field_name,
@@ -144,6 +182,7 @@ impl Decoder for ImplementationDecoder {
 
                 ImplementationDecoder {
                     parameters,
+                    defaults,
                     hasher,
                     struct_fields,
                     exec_source: parsed.exec_source().clone(),
@@ -169,7 +208,9 @@ impl Decoder for ImplementationDecoder {
                         let mut fields = parsed
                             .object_ordered_fields()
                             .get(&name)
-                            .expect("Could not find union member in parsed schema.")
+                            .unwrap_or_else(|| {
+                                abort_unknown_union_member(parsed, &union_name, &name)
+                            })
                             .to_owned();
 
                         fields.sort_by(|a, b| a.1.cmp(&b.1));
@@ -190,7 +231,14 @@ impl Decoder for ImplementationDecoder {
                         let f = &parsed
                             .field_defs()
                             .get(&field_id)
-                            .expect("FieldDefinition not found in parsed schema.");
+                            .unwrap_or_else(|| {
+                                proc_macro_error::abort!(
+                                    manifest_span(),
+                                    "Field `{}` of union `{}` not found in parsed GraphQL schema.",
+                                    field_name,
+                                    union_name
+                                )
+                            });
                         // All fields in a derived union type are nullable, except for the `ID` field.
                         let mut f = f.0.clone();
                         f.ty.node.nullable =
@@ -229,6 +277,7 @@ impl From<ImplementationDecoder> for TokenStream {
     fn from(decoder: ImplementationDecoder) -> Self {
         let ImplementationDecoder {
             parameters,
+            defaults,
             hasher,
             struct_fields,
             exec_source,
@@ -299,6 +348,7 @@ impl From<ImplementationDecoder> for TokenStream {
                     impl #ident {
                         #[allow(clippy::too_many_arguments)]
                         pub fn new(#parameters) -> Self {
+                            #defaults
                             let hashed = #hasher.chain_update(#typdef_name).finalize();
                             let id = UID::new(format!("{:x}", hashed)).expect("Bad ID.");
                             Self {
@@ -341,7 +391,9 @@ impl From<ImplementationDecoder> for TokenStream {
                         let mut fields = parsed
                             .object_ordered_fields()
                             .get(&name)
-                            .expect("Could not find union member in parsed schema.")
+                            .unwrap_or_else(|| {
+                                abort_unknown_union_member(&parsed, &union_name, &name)
+                            })
                             .to_owned();
 
                         fields.sort_by(|a, b| a.1.cmp(&b.1));
@@ -362,7 +414,14 @@ impl From<ImplementationDecoder> for TokenStream {
                         let f = &parsed
                             .field_defs()
                             .get(&field_id)
-                            .expect("FieldDefinition not found in parsed schema.");
+                            .unwrap_or_else(|| {
+                                proc_macro_error::abort!(
+                                    manifest_span(),
+                                    "Field `{}` of union `{}` not found in parsed GraphQL schema.",
+                                    field_name,
+                                    union_name
+                                )
+                            });
                         // All fields in a derived union type are nullable, except for the `ID` field.
                         let mut f = f.0.clone();
                         f.ty.node.nullable =
@@ -384,7 +443,13 @@ impl From<ImplementationDecoder> for TokenStream {
                     let member_fields = parsed
                         .object_field_mappings()
                         .get(m.to_string().as_str())
-                        .expect("Could not get field mappings for union member.")
+                        .unwrap_or_else(|| {
+                            abort_unknown_union_member(
+                                &parsed,
+                                &union_name,
+                                &m.to_string(),
+                            )
+                        })
                         .keys()
                         .map(|k| k.to_owned())
                         .collect::<HashSet<_>>();
@@ -598,7 +663,9 @@ impl Decoder for ObjectDecoder {
                         let mut fields = parsed
                             .object_ordered_fields()
                             .get(&name)
-                            .expect("Could not find union member in parsed schema.")
+                            .unwrap_or_else(|| {
+                                abort_unknown_union_member(parsed, &union_name, &name)
+                            })
                             .to_owned();
 
                         fields.sort_by(|a, b| a.1.cmp(&b.1));
@@ -619,7 +686,14 @@ impl Decoder for ObjectDecoder {
                         let f = &parsed
                             .field_defs()
                             .get(&field_id)
-                            .expect("FieldDefinition not found in parsed schema.");
+                            .unwrap_or_else(|| {
+                                proc_macro_error::abort!(
+                                    manifest_span(),
+                                    "Field `{}` of union `{}` not found in parsed GraphQL schema.",
+                                    field_name,
+                                    union_name
+                                )
+                            });
                         // All fields in a derived union type are nullable, except for the `ID` field.
                         let mut f = f.0.clone();
                         f.ty.node.nullable =
@@ -647,11 +721,30 @@ impl Decoder for ObjectDecoder {
 
                 Self::from_typedef(&typdef, parsed)
             }
-            _ => panic!("Expected `TypeKind::Union` or `TypeKind::Object."),
+            _ => proc_macro_error::abort!(
+                manifest_span(),
+                "Expected `TypeKind::Union` or `TypeKind::Object` for TypeDefinition '{}'.",
+                typ.name
+            ),
         }
     }
 }
 
+/// Abort with a diagnostic for a union member that isn't an object type in the schema.
+fn abort_unknown_union_member(
+    parsed: &ParsedGraphQLSchema,
+    union_name: &str,
+    member: &str,
+) -> ! {
+    proc_macro_error::abort!(
+        manifest_span(),
+        "Member '{}' of union '{}' is not an object type defined in the GraphQL schema.",
+        member,
+        union_name;
+        help =? did_you_mean(member, parsed.objects().keys().map(String::as_str))
+    )
+}
+
 /// A wrapper object used to process GraphQL `TypeKind::Enum` type definitions
 /// into a format from which Rust tokens can be generated.
 pub struct EnumDecoder {
@@ -720,7 +813,86 @@ impl Decoder for EnumDecoder {
                     type_id,
                 }
             }
-            _ => panic!("Expected `TypeKind::Enum`."),
+            _ => proc_macro_error::abort!(
+                manifest_span(),
+                "Expected `TypeKind::Enum` for TypeDefinition '{}'.",
+                typ.name
+            ),
+        }
+    }
+}
+
+/// A wrapper object used to process GraphQL `TypeKind::Interface` type definitions
+/// into a format from which Rust tokens can be generated.
+pub struct InterfaceDecoder {
+    /// The name of the GraphQL interface (as a `syn::Ident`).
+    ident: Ident,
+
+    /// Tokens used to declare the getter methods of the trait.
+    methods: Vec<TokenStream>,
+
+    /// Tokens used to implement the trait for each implementing object.
+    impls: Vec<TokenStream>,
+}
+
+impl Decoder for InterfaceDecoder {
+    /// Create a decoder from a GraphQL `TypeDefinition`.
+    fn from_typedef(typ: &TypeDefinition, parsed: &ParsedGraphQLSchema) -> Self {
+        match &typ.kind {
+            TypeKind::Interface(i) => {
+                let interface_name = typ.name.to_string();
+                let ident = format_ident!("{interface_name}");
+
+                let getters = i
+                    .fields
+                    .iter()
+                    .map(|f| {
+                        let field_name_ident =
+                            format_ident!("{}", f.node.name.to_string());
+                        let ProcessedFieldType {
+                            field_type_tokens, ..
+                        } = process_type(parsed, &f.node);
+                        (field_name_ident, field_type_tokens)
+                    })
+                    .collect::<Vec<_>>();
+
+                let methods = getters
+                    .iter()
+                    .map(|(name, typ)| quote! { fn #name(&self) -> &#typ; })
+                    .collect::<Vec<TokenStream>>();
+
+                let impls = parsed
+                    .interface_implementors(&interface_name)
+                    .iter()
+                    .map(|obj_name| {
+                        let obj_ident = format_ident!("{obj_name}");
+                        let getters = getters.iter().map(|(name, typ)| {
+                            quote! {
+                                fn #name(&self) -> &#typ {
+                                    &self.#name
+                                }
+                            }
+                        });
+
+                        quote! {
+                            impl #ident for #obj_ident {
+                                #(#getters)*
+                            }
+                        }
+                    })
+                    .collect::<Vec<TokenStream>>();
+
+                Self {
+                    ident,
+                    methods,
+                    impls,
+                }
+            }
+            _ => proc_macro_error::abort!(
+                manifest_span(),
+                "Expected `TypeKind::Interface` for TypeDefinition '{}'.",
+                typ.name
+            ),
         }
     }
 }
@@ -955,6 +1127,24 @@ impl From<EnumDecoder> for TokenStream {
     }
 }
 
+impl From<InterfaceDecoder> for TokenStream {
+    fn from(decoder: InterfaceDecoder) -> Self {
+        let InterfaceDecoder {
+            ident,
+            methods,
+            impls,
+        } = decoder;
+
+        quote! {
+            pub trait #ident {
+                #(#methods)*
+            }
+
+            #(#impls)*
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
 
@@ -1040,6 +1230,46 @@ type Person @entity {
         assert!(tokenstream.contains("fn to_row (& self) -> Vec < FtColumn >"));
     }
 
+    #[test]
+    fn test_can_create_object_decoder_with_default_and_computed_fields() {
+        let schema = r#"
+enum Tier {
+    Basic
+    Premium
+}
+
+type Person @entity {
+    id: ID!
+    name: Charfield!
+    age: UInt1! @default(value: 18)
+    tier: Tier! @default(value: Basic)
+    initials: Charfield! @computed(sql: "left(name, 1)")
+}"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typdef = schema.type_defs().get("Person").unwrap();
+        let decoder = ObjectDecoder::from_typedef(typdef, &schema);
+        let tokenstream = TokenStream::from(decoder).to_string();
+
+        assert!(tokenstream.contains(
+            "pub fn new (name : Charfield , age : Option < UInt1 > , tier : Option < Charfield > ,) -> Self"
+        ));
+        assert!(tokenstream.contains("let age = age . unwrap_or_else (|| 18) ;"));
+        assert!(tokenstream.contains(
+            "let tier = tier . unwrap_or_else (|| \"Tier::Basic\" . to_string ()) ;"
+        ));
+
+        // Computed fields are generated by the database, so they're not part of the entity.
+        assert!(!tokenstream.contains("initials"));
+    }
+
     #[test]
     fn test_can_create_object_decoder_containing_expected_tokens_from_object_typedef_containing_m2m_relationship(
     ) {
@@ -1137,4 +1367,45 @@ type Wallet @entity {
         // let's just assert that we have the main/primary method and function definitions.
         assert!(tokenstream.contains("const JOIN_METADATA : Option < [Option < JoinMetadata < 'a >> ; MAX_FOREIGN_KEY_LIST_FIELDS] > = Some ([Some (JoinMetadata { namespace : \"test_test\" , table_name : \"wallets_accounts\" , parent_column_name : \"id\" , child_column_name : \"id\" , child_position : 1usize , }) , None , None , None , None , None , None , None , None , None]) ;"));
     }
+
+    #[test]
+    fn test_can_create_interface_decoder_containing_expected_tokens_from_interface_typedef(
+    ) {
+        let schema = r#"
+interface Event {
+    id: ID!
+    block: UInt4!
+}
+
+type Swap implements Event @entity {
+    id: ID!
+    block: UInt4!
+    amount: UInt8!
+}
+
+type Deposit implements Event @entity {
+    id: ID!
+    block: UInt4!
+}
+"#;
+
+        let schema = ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap();
+
+        let typdef = schema.type_defs().get("Event").unwrap();
+        let decoder = InterfaceDecoder::from_typedef(typdef, &schema);
+        let tokenstream = TokenStream::from(decoder).to_string();
+
+        assert!(tokenstream.contains(
+            "pub trait Event { fn id (& self) -> & ID ; fn block (& self) -> & UInt4 ; }"
+        ));
+        assert!(tokenstream.contains("impl Event for Deposit"));
+        assert!(tokenstream.contains("impl Event for Swap"));
+        assert!(tokenstream.contains("fn block (& self) -> & UInt4 { & self . block }"));
+    }
 }
@@ -1,9 +1,12 @@
-use std::collections::{HashMap, HashSet};
+use std::{
+    cell::Cell,
+    collections::{HashMap, HashSet},
+};
 
 use async_graphql_parser::types::{BaseType, FieldDefinition, Type as AsyncGraphQLType};
-use async_graphql_value::Name;
+use async_graphql_value::{ConstValue, Name};
 use fuel_abi_types::abi::program::{
-    ABIFunction, LoggedType, ProgramABI, TypeDeclaration,
+    ABIFunction, LoggedType, MessageType, ProgramABI, TypeApplication, TypeDeclaration,
 };
 use fuel_indexer_lib::{
     constants::*,
@@ -11,10 +14,70 @@ use fuel_indexer_lib::{
 };
 use fuel_indexer_types::{type_id, FUEL_TYPES_NAMESPACE};
 use fuels_code_gen::utils::Source;
-use proc_macro2::TokenStream;
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use syn::{GenericArgument, Ident, PathArguments, Type, TypePath};
 
+thread_local! {
+    /// Span of the `manifest` argument of the `#[indexer]` currently being expanded.
+    static MANIFEST_SPAN: Cell<Option<Span>> = Cell::new(None);
+}
+
+/// Record the span of the `manifest` argument of the `#[indexer]` being expanded.
+///
+/// Errors found in the manifest, its JSON ABIs, or its GraphQL schema don't have a span
+/// in the indexer module itself, so diagnostics for them point at the manifest argument.
+pub fn set_manifest_span(span: Span) {
+    MANIFEST_SPAN.with(|s| s.set(Some(span)));
+}
+
+/// Span of the `manifest` argument of the `#[indexer]` being expanded.
+pub fn manifest_span() -> Span {
+    MANIFEST_SPAN
+        .with(|s| s.get())
+        .unwrap_or_else(Span::call_site)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b = b.chars().collect::<Vec<_>>();
+    let mut prev = (0..=b.len()).collect::<Vec<_>>();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let cost = usize::from(ca != *cb);
+            curr[j + 1] = (prev[j] + cost).min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+
+    prev[b.len()]
+}
+
+/// Build a "did you mean" suggestion from the candidate name closest to `name`.
+///
+/// Returns `None` if no candidate is close enough to plausibly be a typo of `name`.
+pub fn did_you_mean<'a>(
+    name: &str,
+    candidates: impl IntoIterator<Item = &'a str>,
+) -> Option<String> {
+    let max_distance = std::cmp::max(2, name.chars().count() / 3);
+
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| {
+            (
+                edit_distance(&name.to_lowercase(), &candidate.to_lowercase()),
+                candidate,
+            )
+        })
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min()
+        .map(|(_, candidate)| format!("did you mean `{candidate}`?"))
+}
+
 /// Provides a TokenStream to be used for unwrapping `Option`s for external types.
 ///
 /// This is done because traits cannot be implemented on external types due to the orphan rule.
@@ -34,7 +97,12 @@ pub fn unwrap_or_default_for_external_type(
                 .unwrap_or(Identity::Address(Address::zeroed()))
             }
         }
-        _ => panic!("Default is not implemented for {field_type_name}"),
+        _ => proc_macro_error::abort!(
+            manifest_span(),
+            "Default is not implemented for external type `{}`.",
+            field_type_name;
+            note = "Nullable fields of this type are not supported in the GraphQL schema."
+        ),
     }
 }
 
@@ -45,7 +113,8 @@ pub fn get_json_abi(abi_path: Option<String>) -> Option<ProgramABI> {
             let src = match Source::parse(abi) {
                 Ok(src) => src,
                 Err(e) => {
-                    proc_macro_error::abort_call_site!(
+                    proc_macro_error::abort!(
+                        manifest_span(),
                         "`abi` must be a file path to valid json abi: {:?}.",
                         e
                     )
@@ -55,9 +124,11 @@ pub fn get_json_abi(abi_path: Option<String>) -> Option<ProgramABI> {
             let source = match src.get() {
                 Ok(s) => s,
                 Err(e) => {
-                    proc_macro_error::abort_call_site!(
+                    proc_macro_error::abort!(
+                        manifest_span(),
                         "Could not fetch JSON ABI. {:?}",
-                        e
+                        e;
+                        note = "ABI paths in the manifest are resolved relative to the workspace root."
                     )
                 }
             };
@@ -65,7 +136,8 @@ pub fn get_json_abi(abi_path: Option<String>) -> Option<ProgramABI> {
             match serde_json::from_str(&source) {
                 Ok(parsed) => Some(parsed),
                 Err(e) => {
-                    proc_macro_error::abort_call_site!(
+                    proc_macro_error::abort!(
+                        manifest_span(),
                         "Invalid JSON from ABI spec: {:?}.",
                         e
                     )
@@ -76,6 +148,195 @@ pub fn get_json_abi(abi_path: Option<String>) -> Option<ProgramABI> {
     }
 }
 
+/// Merge the JSON ABI of a script or predicate program into the contract JSON ABI.
+///
+/// Type IDs are only unique within a single JSON ABI, so the types of `program` are
+/// re-keyed before being merged: a type whose whole declaration (including those of its
+/// components) is already in `base` re-uses the existing type ID, while new types are
+/// appended after the largest type ID in `base`. The re-keyed program is returned so that
+/// its functions and logged types can be processed separately from those of the contract.
+///
+/// Decoders are keyed by type name, so a struct or enum that is declared differently in
+/// `base` and in `program` is reported as an error, rather than silently merged.
+pub fn merge_program_abi(base: &mut ProgramABI, program: ProgramABI) -> ProgramABI {
+    let mut next_type_id = base
+        .types
+        .iter()
+        .map(|typ| typ.type_id + 1)
+        .max()
+        .unwrap_or_default();
+
+    let base_signatures = base
+        .types
+        .iter()
+        .map(|typ| (type_declaration_signature(&base.types, typ), typ))
+        .collect::<Vec<(String, &TypeDeclaration)>>();
+
+    let type_id_map = program
+        .types
+        .iter()
+        .map(|typ| {
+            let signature = type_declaration_signature(&program.types, typ);
+            let existing = base_signatures
+                .iter()
+                .find(|(base_signature, _)| *base_signature == signature);
+            let ty_id = match existing {
+                Some((_, existing)) => existing.type_id,
+                None => {
+                    let is_named = typ.type_field.starts_with("struct ")
+                        || typ.type_field.starts_with("enum ");
+                    if is_named
+                        && base.types.iter().any(|t| t.type_field == typ.type_field)
+                    {
+                        proc_macro_error::abort!(
+                            manifest_span(),
+                            "'{}' is declared differently in the contract ABI and in a script or predicate ABI.",
+                            typ.name();
+                            help = "Rename '{}' in one of the programs, or declare it the same way in both.", typ.name()
+                        )
+                    }
+
+                    next_type_id += 1;
+                    next_type_id - 1
+                }
+            };
+            (typ.type_id, ty_id)
+        })
+        .collect::<HashMap<usize, usize>>();
+
+    let remap_application =
+        |app: &TypeApplication| remap_type_application(app, &type_id_map);
+
+    let types = program
+        .types
+        .iter()
+        .map(|typ| TypeDeclaration {
+            type_id: type_id_map[&typ.type_id],
+            type_field: typ.type_field.clone(),
+            components: typ
+                .components
+                .as_ref()
+                .map(|c| c.iter().map(remap_application).collect()),
+            type_parameters: typ
+                .type_parameters
+                .as_ref()
+                .map(|p| p.iter().map(|ty_id| type_id_map[ty_id]).collect()),
+        })
+        .collect::<Vec<TypeDeclaration>>();
+
+    let functions = program
+        .functions
+        .iter()
+        .map(|f| ABIFunction {
+            inputs: f.inputs.iter().map(remap_application).collect(),
+            output: remap_application(&f.output),
+            ..f.clone()
+        })
+        .collect::<Vec<ABIFunction>>();
+
+    let logged_types = program.logged_types.as_ref().map(|logs| {
+        logs.iter()
+            .map(|log| LoggedType {
+                log_id: log.log_id,
+                application: remap_application(&log.application),
+            })
+            .collect::<Vec<LoggedType>>()
+    });
+
+    let messages_types = program.messages_types.as_ref().map(|msgs| {
+        msgs.iter()
+            .map(|msg| MessageType {
+                message_id: msg.message_id,
+                application: remap_application(&msg.application),
+            })
+            .collect::<Vec<MessageType>>()
+    });
+
+    for typ in types.iter() {
+        if !base.types.iter().any(|t| t.type_id == typ.type_id) {
+            base.types.push(typ.clone());
+        }
+    }
+
+    ProgramABI {
+        types,
+        functions,
+        logged_types,
+        messages_types,
+        configurables: None,
+    }
+}
+
+/// Describe a `TypeDeclaration` along with the declarations of its components and type
+/// parameters, so that declarations from different JSON ABIs can be compared regardless
+/// of their type IDs.
+fn type_declaration_signature(
+    types: &[TypeDeclaration],
+    typ: &TypeDeclaration,
+) -> String {
+    let declaration = |ty_id: usize| {
+        types
+            .iter()
+            .find(|t| t.type_id == ty_id)
+            .map(|t| type_declaration_signature(types, t))
+            .unwrap_or_default()
+    };
+
+    fn application_signature(
+        app: &TypeApplication,
+        declaration: &dyn Fn(usize) -> String,
+    ) -> String {
+        let arguments = app
+            .type_arguments
+            .iter()
+            .flatten()
+            .map(|arg| application_signature(arg, declaration))
+            .collect::<Vec<String>>();
+        format!(
+            "{}: {}<{}>",
+            app.name,
+            declaration(app.type_id),
+            arguments.join(", ")
+        )
+    }
+
+    let components = typ
+        .components
+        .iter()
+        .flatten()
+        .map(|app| application_signature(app, &declaration))
+        .collect::<Vec<String>>();
+    let parameters = typ
+        .type_parameters
+        .iter()
+        .flatten()
+        .map(|ty_id| declaration(*ty_id))
+        .collect::<Vec<String>>();
+
+    format!(
+        "{}<{}> {{ {} }}",
+        typ.type_field,
+        parameters.join(", "),
+        components.join(", ")
+    )
+}
+
+/// Re-key the type IDs of a `TypeApplication` (and its type arguments) using the given map.
+fn remap_type_application(
+    app: &TypeApplication,
+    type_id_map: &HashMap<usize, usize>,
+) -> TypeApplication {
+    TypeApplication {
+        name: app.name.clone(),
+        type_id: type_id_map[&app.type_id],
+        type_arguments: app.type_arguments.as_ref().map(|args| {
+            args.iter()
+                .map(|arg| remap_type_application(arg, type_id_map))
+                .collect()
+        }),
+    }
+}
+
 /// Whether a `TypeDeclaration` is tuple type
 pub fn is_tuple_type(typ: &TypeDeclaration) -> bool {
     let mut type_field_chars = typ.type_field.chars();
@@ -258,6 +519,7 @@ impl Codegen for TypeDeclaration {
                 "Call" => quote! { Call },
                 "generic T" => quote! {},
                 "Identity" => quote! { Identity },
+                "InputCoin" => quote! { InputCoin },
                 "Log" => quote! { Log },
                 "LogData" => quote! { LogData },
                 "MessageOut" => quote! { MessageOut },
@@ -269,6 +531,7 @@ impl Codegen for TypeDeclaration {
                 "Transfer" => quote! { Transfer },
                 "TransferOut" => quote! { TransferOut },
                 "u16" => quote! { u16 },
+                "u256" => quote! { U256 },
                 "u32" => quote! { u32 },
                 "u64" => quote! { u64 },
                 "u8" => quote! { u8 },
@@ -523,6 +786,30 @@ pub enum FieldBaseType {
     List,
 }
 
+/// Abort with a diagnostic for a `FieldDefinition` whose type isn't defined in the schema.
+fn abort_undefined_field_type(
+    parsed: &ParsedGraphQLSchema,
+    f: &FieldDefinition,
+    name: &str,
+) -> ! {
+    let field_name = f.name.to_string();
+    let parent = parsed.field_defs().iter().find_map(|(_, (def, parent))| {
+        (def.name.node == f.name.node && def.ty.node == f.ty.node).then_some(parent)
+    });
+    let field_name = match parent {
+        Some(parent) => format!("{parent}.{field_name}"),
+        None => field_name,
+    };
+
+    proc_macro_error::abort!(
+        manifest_span(),
+        "Type '{}' of field `{}` is not defined in the GraphQL schema.",
+        name,
+        field_name;
+        help =? did_you_mean(name, parsed.type_names().iter().map(String::as_str))
+    )
+}
+
 /// Process a named type into its type tokens, and the Ident for those type tokens.
 pub fn process_type(
     parsed: &ParsedGraphQLSchema,
@@ -536,7 +823,7 @@ pub fn process_type(
             // if the field is required.
             let name = t.to_string().replace('!', "");
             if !parsed.has_type(&name) {
-                panic!("Type '{name}' is not defined in the schema.");
+                abort_undefined_field_type(parsed, f, &name);
             }
 
             let field_type_name = parsed.scalar_type_for(f);
@@ -560,7 +847,7 @@ pub fn process_type(
         BaseType::List(t) => {
             let name = t.to_string().replace('!', "");
             if !parsed.has_type(&name) {
-                panic!("List type '{name}' is not defined in the schema.");
+                abort_undefined_field_type(parsed, f, &name);
             }
 
             let field_type_name = parsed.scalar_type_for(f);
@@ -632,8 +919,15 @@ pub fn to_bytes_tokens(
                 match field_typ_name {
                     "Identity" => quote! { .0 },
                     "Tai64Timestamp" => quote! { .0.to_le_bytes() },
-                    _ => panic!("From<{field_typ_name}> not implemented for AsRef<u8>."),
+                    _ => proc_macro_error::abort!(
+                        manifest_span(),
+                        "From<{}> not implemented for AsRef<u8>.",
+                        field_typ_name
+                    ),
                 }
+            } else if matches!(field_typ_name, "UInt32" | "BigDecimal") {
+                // Arbitrary-precision numbers are hashed by their decimal representation.
+                quote! { .to_string() }
             } else if !ASREF_BYTE_TYPES.contains(field_typ_name) {
                 quote! { .to_le_bytes() }
             } else {
@@ -684,6 +978,28 @@ pub fn parameters_tokens(
     quote! { #parameters #ident: #typ_tokens, }
 }
 
+/// Get tokens for the `@default` value of a field of GraphQL type `field_typ_name`.
+pub fn default_value_tokens(field_typ_name: &str, value: &ConstValue) -> TokenStream {
+    match value {
+        ConstValue::Boolean(b) => quote! { #b },
+        ConstValue::Number(n) => {
+            let lit = match (n.as_i64(), n.as_u64()) {
+                (Some(n), _) => proc_macro2::Literal::i64_unsuffixed(n),
+                (None, Some(n)) => proc_macro2::Literal::u64_unsuffixed(n),
+                _ => unreachable!("`@default` values of numeric fields are integers."),
+            };
+            quote! { #lit }
+        }
+        ConstValue::String(s) => quote! { #s.to_string() },
+        // Enum fields are stored as `Enum::VALUE` strings.
+        ConstValue::Enum(v) => {
+            let v = format!("{field_typ_name}::{v}");
+            quote! { #v.to_string() }
+        }
+        _ => unreachable!("`@default` values are validated when the schema is parsed."),
+    }
+}
+
 /// Get tokens for a field decoder.
 pub fn field_decoder_tokens(
     field_name: &Ident,
@@ -808,6 +1124,21 @@ impl From<GenericType> for TokenStream {
     }
 }
 
+/// Look up the `TypeDeclaration` of a generic type's inner type argument.
+fn inner_abi_type<'a>(
+    inner: &TypeApplication,
+    abi_types: &'a HashMap<usize, TypeDeclaration>,
+) -> &'a TypeDeclaration {
+    abi_types.get(&inner.type_id).unwrap_or_else(|| {
+        proc_macro_error::abort!(
+            manifest_span(),
+            "Inner type with TypeID({}) of a generic type is not declared in the JSON ABI.",
+            inner.type_id;
+            note = "The JSON ABI referenced by the manifest's `abi` key may be out of date."
+        )
+    })
+}
+
 /// Same as `derive_generic_inner_typedefs` but specifically for log types.
 ///
 /// Where as `derive_generic_inner_typedefs` can return multiple inner types, this
@@ -817,21 +1148,19 @@ pub fn derive_log_generic_inner_typedefs<'a>(
     abi: &ProgramABI,
     abi_types: &'a HashMap<usize, TypeDeclaration>,
 ) -> &'a TypeDeclaration {
-    let result =
-        abi.logged_types
-            .iter()
-            .flatten()
-            .filter_map(|log| {
-                if log.log_id == typ.log_id && log.application.type_arguments.is_some() {
-                    let args = log.application.type_arguments.as_ref().unwrap();
-                    let inner = args.first().expect("No type args found.");
-                    return Some(abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                        panic!("Inner type not in ABI: {:?}", inner)
-                    }));
-                }
-                None
-            })
-            .collect::<Vec<_>>();
+    let result = abi
+        .logged_types
+        .iter()
+        .flatten()
+        .filter_map(|log| {
+            if log.log_id == typ.log_id && log.application.type_arguments.is_some() {
+                let args = log.application.type_arguments.as_ref().unwrap();
+                let inner = args.first().expect("No type args found.");
+                return Some(inner_abi_type(inner, abi_types));
+            }
+            None
+        })
+        .collect::<Vec<_>>();
 
     result.first().expect("No inner type found.")
 }
@@ -869,11 +1198,7 @@ pub fn derive_generic_inner_typedefs<'a>(
                             if i.type_id == typ.type_id && i.type_arguments.is_some() {
                                 let args = i.type_arguments.as_ref().unwrap();
                                 let inner = args.first().expect("No type args found.");
-                                return Some(
-                                    abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                                        panic!("Inner type not in ABI: {:?}", inner)
-                                    }),
-                                );
+                                return Some(inner_abi_type(inner, abi_types));
                             }
                             None
                         })
@@ -889,9 +1214,7 @@ pub fn derive_generic_inner_typedefs<'a>(
                     {
                         let args = func.output.type_arguments.as_ref().unwrap();
                         let inner = args.first().expect("No type args found.");
-                        return Some(abi_types.get(&inner.type_id).unwrap_or_else(
-                            || panic!("Inner type not in ABI: {:?}", inner),
-                        ));
+                        return Some(inner_abi_type(inner, abi_types));
                     }
                     None
                 })
@@ -908,9 +1231,7 @@ pub fn derive_generic_inner_typedefs<'a>(
                     {
                         let args = log.application.type_arguments.as_ref().unwrap();
                         let inner = args.first().expect("No type args found.");
-                        return Some(abi_types.get(&inner.type_id).unwrap_or_else(
-                            || panic!("Inner type not in ABI: {:?}", inner),
-                        ));
+                        return Some(inner_abi_type(inner, abi_types));
                     }
                     None
                 })
@@ -979,11 +1300,20 @@ fn typed_path_string(p: &TypePath) -> String {
                         result.push_str(&name);
                         result.push('>');
                     }
-                    _ => panic!("Unsupported generic argument."),
+                    arg => proc_macro_error::abort!(
+                        arg,
+                        "Unsupported generic argument.";
+                        help = "Generic handler arguments must be written as a type path, e.g. `Vec<SomeEvent>`."
+                    ),
                 })
                 .collect::<Vec<_>>();
         }
-        _ => panic!("Unsupported generic argument."),
+        _ => proc_macro_error::abort!(
+            base,
+            "Missing generic argument for `{}`.",
+            base.ident;
+            help = "Generic handler arguments must be written as a type path, e.g. `Vec<SomeEvent>`."
+        ),
     }
     result
 }
@@ -1013,9 +1343,11 @@ pub fn function_output_type_id(
     abi_types: &HashMap<usize, TypeDeclaration>,
 ) -> usize {
     let outer_typ = abi_types.get(&f.output.type_id).unwrap_or_else(|| {
-        panic!(
-            "function_output_type_id: Type with TypeID({}) is missing from the JSON ABI",
-            f.output.type_id
+        proc_macro_error::abort!(
+            manifest_span(),
+            "Output type with TypeID({}) of function `{}` is missing from the JSON ABI.",
+            f.output.type_id,
+            f.name
         )
     });
     if is_generic_type(outer_typ) {
@@ -1031,9 +1363,7 @@ pub fn function_output_type_id(
 
         match gt {
             GenericType::Option | GenericType::Vec => {
-                let inner_typ = abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                    panic!("function_output_type_id: Generic inner type with TypeID({}) is missing from the JSON ABI", inner.type_id)
-                });
+                let inner_typ = inner_abi_type(inner, abi_types);
                 let (typ_name, _) =
                     typed_path_components(outer_typ, inner_typ, abi_types);
                 type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize
@@ -1070,7 +1400,11 @@ pub fn typed_path_components(
         match gt {
             GenericType::Option | GenericType::Vec => {
                 curr = abi_types.get(&inner.type_id).unwrap_or_else(|| {
-                    panic!("typed_path_components: Generic inner type with TypeID({}) is missing from the JSON ABI", inner.type_id)
+                    proc_macro_error::abort!(
+                        manifest_span(),
+                        "Generic inner type with TypeID({}) is missing from the JSON ABI.",
+                        inner.type_id
+                    )
                 });
                 let name = derive_type_name(curr);
                 let ident = format_ident! { "{}", name };
@@ -1102,3 +1436,95 @@ pub fn is_unsupported_type(type_name: &str) -> bool {
         _ => UNSUPPORTED_ABI_JSON_TYPES.contains(type_name),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn program_abi(types: serde_json::Value) -> ProgramABI {
+        serde_json::from_value(serde_json::json!({
+            "types": types,
+            "functions": [],
+            "loggedTypes": [],
+            "messagesTypes": [],
+            "configurables": [],
+        }))
+        .unwrap()
+    }
+
+    fn transfer_types(amount_type: &str) -> serde_json::Value {
+        serde_json::json!([
+            { "typeId": 0, "type": "b256", "components": null, "typeParameters": null },
+            { "typeId": 1, "type": amount_type, "components": null, "typeParameters": null },
+            {
+                "typeId": 2,
+                "type": "struct Transfer",
+                "components": [
+                    { "name": "to", "type": 0, "typeArguments": null },
+                    { "name": "amount", "type": 1, "typeArguments": null },
+                ],
+                "typeParameters": null,
+            },
+        ])
+    }
+
+    #[test]
+    fn test_merge_program_abi_reuses_identical_declarations() {
+        let mut base = program_abi(serde_json::json!([
+            { "typeId": 0, "type": "u64", "components": null, "typeParameters": null },
+            { "typeId": 1, "type": "b256", "components": null, "typeParameters": null },
+            {
+                "typeId": 2,
+                "type": "struct Transfer",
+                "components": [
+                    { "name": "to", "type": 1, "typeArguments": null },
+                    { "name": "amount", "type": 0, "typeArguments": null },
+                ],
+                "typeParameters": null,
+            },
+        ]));
+
+        let merged = merge_program_abi(&mut base, program_abi(transfer_types("u64")));
+
+        assert_eq!(base.types.len(), 3);
+        assert_eq!(
+            merged.types.iter().map(|t| t.type_id).collect::<Vec<_>>(),
+            vec![1, 0, 2]
+        );
+    }
+
+    #[test]
+    fn test_merge_program_abi_keeps_tuples_with_different_components_apart() {
+        let tuple = |a: usize, b: usize| {
+            serde_json::json!({
+                "typeId": 2,
+                "type": "(_, _)",
+                "components": [
+                    { "name": "__tuple_element", "type": a, "typeArguments": null },
+                    { "name": "__tuple_element", "type": b, "typeArguments": null },
+                ],
+                "typeParameters": null,
+            })
+        };
+        let scalars = |tuple: serde_json::Value| {
+            serde_json::json!([
+                { "typeId": 0, "type": "u64", "components": null, "typeParameters": null },
+                { "typeId": 1, "type": "bool", "components": null, "typeParameters": null },
+                tuple,
+            ])
+        };
+
+        let mut base = program_abi(scalars(tuple(0, 1)));
+        let merged = merge_program_abi(&mut base, program_abi(scalars(tuple(1, 0))));
+
+        assert_eq!(base.types.len(), 4);
+        assert_eq!(merged.types[2].type_id, 3);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_merge_program_abi_rejects_conflicting_declarations() {
+        let mut base = program_abi(transfer_types("u64"));
+        merge_program_abi(&mut base, program_abi(transfer_types("u32")));
+    }
+}
@@ -1,28 +1,40 @@
 use crate::{
     helpers::*,
     native::{handler_block_native, native_main},
-    parse::IndexerConfig,
-    schema::process_graphql_schema,
+    parse::{HandlerSchedule, IndexerConfig},
+    schema::{process_enum_entities, process_graphql_schema},
     wasm::handler_block_wasm,
 };
-use fuel_abi_types::abi::program::TypeDeclaration;
+use fuel_abi_types::abi::program::{
+    ABIFunction, LoggedType, ProgramABI, TypeDeclaration,
+};
 use fuel_indexer_lib::{
-    constants::*, manifest::ContractIds, manifest::Manifest,
-    utils::workspace_manifest_prefix, ExecutionSource,
+    constants::*,
+    graphql::enum_entities::{derive_enum_entities, EnumEntity},
+    manifest::ContractIds,
+    manifest::Manifest,
+    utils::workspace_manifest_prefix,
+    ExecutionSource,
 };
 use fuel_indexer_types::{type_id, FUEL_TYPES_NAMESPACE};
-use fuels::{core::codec::resolve_fn_selector, types::param_types::ParamType};
+use fuels::{
+    core::codec::resolve_fn_selector,
+    tx::Bytes32,
+    types::{bech32::Bech32Address, param_types::ParamType, Address},
+};
 use fuels_code_gen::{Abigen, AbigenTarget, ProgramType};
 use proc_macro::TokenStream;
-use quote::quote;
+use quote::{format_ident, quote};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use syn::{parse_macro_input, FnArg, Item, ItemMod, PatType, Type};
 
 fn process_fn_items(
     manifest: &Manifest,
     abi_path: Option<String>,
     indexer_module: ItemMod,
+    enum_entities: &[EnumEntity],
 ) -> (proc_macro2::TokenStream, proc_macro2::TokenStream) {
     if indexer_module.content.is_none()
         || indexer_module
@@ -32,12 +44,24 @@ fn process_fn_items(
             .1
             .is_empty()
     {
-        proc_macro_error::abort_call_site!(
+        proc_macro_error::abort!(
+            indexer_module.ident,
             "No module body, must specify at least one handler function."
         )
     }
 
-    let abi = get_json_abi(abi_path).unwrap_or_default();
+    let mut abi = get_json_abi(abi_path).unwrap_or_default();
+
+    // Script and predicate types share the decoders of the contract types, while
+    // their functions and logged types are processed separately.
+    let script_abi = manifest
+        .script()
+        .and_then(|script| get_json_abi(Some(prefix_abi_path(&script.abi))))
+        .map(|script_abi| merge_program_abi(&mut abi, script_abi));
+    let predicate_abi = manifest
+        .predicate()
+        .and_then(|predicate| get_json_abi(Some(prefix_abi_path(&predicate.abi))))
+        .map(|predicate_abi| merge_program_abi(&mut abi, predicate_abi));
 
     let mut decoded_type_snippets = HashSet::new();
     let mut decoded_log_match_arms = HashSet::new();
@@ -48,8 +72,32 @@ fn process_fn_items(
     let abi_types = abi.clone().types;
     let abi_log_types = abi.clone().logged_types.unwrap_or_default();
     let abi_msg_types = abi.clone().messages_types.unwrap_or_default();
+
+    // Functions and logged types of every program, used to derive the inner types of generics.
+    let program_funcs = funcs
+        .iter()
+        .chain(
+            script_abi
+                .iter()
+                .chain(predicate_abi.iter())
+                .flat_map(|program| program.functions.iter()),
+        )
+        .cloned()
+        .collect::<Vec<ABIFunction>>();
+    let program_log_types = abi_log_types
+        .iter()
+        .chain(
+            script_abi
+                .iter()
+                .flat_map(|program| program.logged_types.iter().flatten()),
+        )
+        .cloned()
+        .collect::<Vec<LoggedType>>();
+    // `InputCoin`s are only decoded for predicates, and are otherwise left out of the
+    // `Decoders` so as not to clash with user-defined types of the same name.
     let fuel_types = FUEL_PRIMITIVES
         .iter()
+        .filter(|x| **x != "InputCoin" || manifest.predicate().is_some())
         .map(|x| {
             let type_id = type_id(FUEL_TYPES_NAMESPACE, x) as usize;
             let typ = TypeDeclaration {
@@ -111,9 +159,11 @@ fn process_fn_items(
             }
 
             if is_fuel_primitive(typ) {
-                proc_macro_error::abort_call_site!(
+                proc_macro_error::abort!(
+                    manifest_span(),
                     "'{}' is a reserved Fuel type.",
-                    typ.name()
+                    typ.name();
+                    help = "Rename '{}' in the contract so that it doesn't collide with the Fuel type of the same name.", typ.name()
                 )
             }
 
@@ -124,8 +174,8 @@ fn process_fn_items(
                         let ab_types = abi_types_tyid.clone();
                         let inner_typs = derive_generic_inner_typedefs(
                             typ,
-                            &funcs,
-                            &abi_log_types,
+                            &program_funcs,
+                            &program_log_types,
                             &ab_types,
                         );
 
@@ -195,17 +245,19 @@ fn process_fn_items(
             }
 
             if is_fuel_primitive(typ) {
-                proc_macro_error::abort_call_site!(
+                proc_macro_error::abort!(
+                    manifest_span(),
                     "'{}' is a reserved Fuel type.",
-                    typ.name()
+                    typ.name();
+                    help = "Rename '{}' in the contract so that it doesn't collide with the Fuel type of the same name.", typ.name()
                 )
             }
 
             if is_generic_type(typ) {
                 let inner_typs = derive_generic_inner_typedefs(
                     typ,
-                    &funcs,
-                    &abi_log_types,
+                    &program_funcs,
+                    &program_log_types,
                     &abi_types_tyid,
                 );
 
@@ -279,54 +331,98 @@ fn process_fn_items(
 
     // Since log type decoders use `TypeDeclaration`s that were manually created specifically
     // for generics, we parsed log types after other ABI types.
-    let log_type_decoders = abi_log_types
-        .iter()
-        .filter_map(|log| {
-            let ty_id = log.application.type_id;
-            let log_id = log.log_id as usize;
-            let typ = abi_types_tyid.get(&log.application.type_id).unwrap();
-
-            if is_non_decodable_type(typ) {
-                return None;
-            }
+    let log_type_decoder = |log: &LoggedType, program: &ProgramABI| {
+        let ty_id = log.application.type_id;
+        let log_id = log.log_id as usize;
+        let typ = abi_types_tyid.get(&log.application.type_id).unwrap();
 
-            if is_generic_type(typ) {
-                let gt = GenericType::from(typ);
-                match gt {
-                    GenericType::Vec | GenericType::Option => {
-                        let inner_typ =
-                            derive_log_generic_inner_typedefs(log, &abi, &abi_types_tyid);
+        if is_non_decodable_type(typ) {
+            return None;
+        }
 
-                        let (typ_name, _) =
-                            typed_path_components(typ, inner_typ, &abi_types_tyid);
+        if is_generic_type(typ) {
+            let gt = GenericType::from(typ);
+            match gt {
+                GenericType::Vec | GenericType::Option => {
+                    let inner_typ =
+                        derive_log_generic_inner_typedefs(log, program, &abi_types_tyid);
 
-                        let ty_id = type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize;
-                        let _typ = abi_types_tyid.get(&ty_id).expect(
-                            "Could not get generic log type reference from ABI types.",
-                        );
+                    let (typ_name, _) =
+                        typed_path_components(typ, inner_typ, &abi_types_tyid);
 
-                        decoded_log_match_arms.insert(log_id);
+                    let ty_id = type_id(FUEL_TYPES_NAMESPACE, &typ_name) as usize;
+                    let _typ = abi_types_tyid.get(&ty_id).expect(
+                        "Could not get generic log type reference from ABI types.",
+                    );
 
-                        Some(quote! {
-                            #log_id => {
-                                self.decode_type(#ty_id, data);
-                            }
-                        })
-                    }
-                    _ => unimplemented!("Unsupported decoder generic type: {:?}", gt),
+                    Some(quote! {
+                        #log_id => {
+                            self.decode_type(#ty_id, data);
+                        }
+                    })
                 }
-            } else {
-                decoded_log_match_arms.insert(log_id);
-
-                Some(quote! {
-                    #log_id => {
-                        self.decode_type(#ty_id, data);
-                    }
-                })
+                _ => unimplemented!("Unsupported decoder generic type: {:?}", gt),
             }
+        } else {
+            Some(quote! {
+                #log_id => {
+                    self.decode_type(#ty_id, data);
+                }
+            })
+        }
+    };
+
+    let log_type_decoders = abi_log_types
+        .iter()
+        .filter_map(|log| {
+            let decoder = log_type_decoder(log, &abi)?;
+            decoded_log_match_arms.insert(log.log_id as usize);
+            Some(decoder)
+        })
+        .collect::<Vec<proc_macro2::TokenStream>>();
+
+    // Script logs are emitted under the zero contract ID, and their log IDs may overlap
+    // with those of the contract, so they are decoded separately.
+    let script_log_type_decoders = script_abi
+        .iter()
+        .flat_map(|program| {
+            program
+                .logged_types
+                .iter()
+                .flatten()
+                .filter_map(|log| log_type_decoder(log, program))
         })
         .collect::<Vec<proc_macro2::TokenStream>>();
 
+    // Predicate data is the encoded argument of the predicate's `main` function.
+    let predicate_data_decoder = predicate_abi.as_ref().map(|program| {
+        let main = match program.functions.iter().find(|f| f.name == "main") {
+            Some(main) => main,
+            None => proc_macro_error::abort!(
+                manifest_span(),
+                "Predicate ABI must contain a `main` function.";
+                note = "The predicate ABI is set by the manifest's `predicate.abi` key."
+            ),
+        };
+
+        match main.inputs.as_slice() {
+            [] => quote! {
+                let _ = data;
+            },
+            [input] => {
+                let ty_id = input.type_id;
+                quote! {
+                    self.decode_type(#ty_id, data);
+                }
+            }
+            _ => proc_macro_error::abort!(
+                manifest_span(),
+                "Predicate `main` functions with more than one argument are not supported.";
+                note = "The predicate ABI is set by the manifest's `predicate.abi` key."
+            ),
+        }
+    });
+
     let abi_selectors = funcs
         .iter()
         .map(|function| {
@@ -376,6 +472,10 @@ fn process_fn_items(
 
     let mut handler_fns = Vec::with_capacity(contents.len());
 
+    // Whether any handler is scheduled on block time, in which case the time of the
+    // last processed block needs to be tracked.
+    let mut has_time_schedule = false;
+
     let start_block = match manifest.start_block() {
         Some(start_block) => {
             quote! {
@@ -439,18 +539,143 @@ fn process_fn_items(
         }
     };
 
+    // Script transactions are matched on the root of their bytecode, if one is given.
+    let check_if_subscribed_to_script = match manifest.script() {
+        Some(script) => match &script.bytecode_root {
+            Some(bytecode_root) => {
+                let bytecode_root = Bytes32::from_str(bytecode_root).unwrap_or_else(|_| {
+                    proc_macro_error::abort!(
+                        manifest_span(),
+                        "Failed to parse manifest 'script.bytecode_root': '{}'.", bytecode_root;
+                        help = "The bytecode root should be a 32-byte hex string."
+                    )
+                });
+                let bytecode_root = bytecode_root.iter();
+                quote! {
+                    const SCRIPT_BYTECODE_ROOT: [u8; 32] = [#(#bytecode_root),*];
+
+                    let is_subscribed_script = match &tx.transaction {
+                        fuel::Transaction::Script(script) => {
+                            *fuel::ClientContract::root_from_code(&script.script) == SCRIPT_BYTECODE_ROOT
+                        }
+                        _ => false,
+                    };
+                }
+            }
+            None => quote! {
+                let is_subscribed_script = matches!(tx.transaction, fuel::Transaction::Script(_));
+            },
+        },
+        None => quote! {},
+    };
+
+    let decode_script_log = match manifest.script() {
+        Some(_) => quote! {
+            if is_subscribed_script && <[u8; 32]>::from(id) == [0u8; 32] {
+                let ty_id = Log::type_id();
+                let data = serialize(
+                    &Log {
+                        contract_id: ContractId::from(<[u8; 32]>::from(id)),
+                        ra,
+                        rb
+                    }
+                );
+                decoder.decode_type(ty_id, data);
+                continue;
+            }
+        },
+        None => quote! {},
+    };
+
+    let decode_script_logdata = match manifest.script() {
+        Some(_) => quote! {
+            if is_subscribed_script && <[u8; 32]>::from(id) == [0u8; 32] {
+                decoder.decode_script_logdata(rb as usize, data.unwrap_or(Vec::<u8>::new()));
+                continue;
+            }
+        },
+        None => quote! {},
+    };
+
+    // Coin inputs are matched on the owner address, which for a predicate is its root.
+    let decode_predicate_inputs = match manifest.predicate() {
+        Some(predicate) => {
+            let address = &predicate.address;
+            let predicate_address = Bech32Address::from_str(address)
+                .map(Address::from)
+                .or_else(|_| Address::from_str(address))
+                .unwrap_or_else(|_| {
+                    proc_macro_error::abort!(
+                        manifest_span(),
+                        "Failed to parse manifest 'predicate.address': '{}'.", address;
+                        help = "The predicate address should be a Bech32 address or a 32-byte hex string."
+                    )
+                });
+            let predicate_address = predicate_address.iter();
+            quote! {
+                const PREDICATE_ADDRESS: [u8; 32] = [#(#predicate_address),*];
+
+                let inputs = match &tx.transaction {
+                    fuel::Transaction::Script(fuel::Script { inputs, .. })
+                    | fuel::Transaction::Create(fuel::Create { inputs, .. }) => inputs.clone(),
+                    fuel::Transaction::Mint(_) => Vec::new(),
+                };
+
+                for input in inputs {
+                    if let fuel::Input::Coin(coin) = input {
+                        if coin.owner != Address::from(PREDICATE_ADDRESS) || coin.predicate.is_empty() {
+                            continue;
+                        }
+
+                        decoder.decode_predicate_data(coin.predicate_data.to_vec());
+
+                        let ty_id = InputCoin::type_id();
+                        let data = serialize(&coin);
+                        decoder.decode_type(ty_id, data);
+                    }
+                }
+            }
+        }
+        None => quote! {},
+    };
+
+    let script_decoder_fn = if manifest.script().is_some() {
+        quote! {
+            pub fn decode_script_logdata(&mut self, rb: usize, data: Vec<u8>) {
+                match rb {
+                    #(#script_log_type_decoders),*
+                    _ => debug!("Unknown script logged type ID; check script ABI to make sure that logged types are correct.")
+                }
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let predicate_decoder_fn = match predicate_data_decoder {
+        Some(predicate_data_decoder) => quote! {
+            pub fn decode_predicate_data(&mut self, data: Vec<u8>) {
+                #predicate_data_decoder
+            }
+        },
+        None => quote! {},
+    };
+
     let (asyncness, awaitness) = manifest.execution_source().async_awaitness();
 
     for item in contents {
         match item {
-            Item::Fn(fn_item) => {
+            Item::Fn(mut fn_item) => {
                 let mut input_checks = Vec::new();
                 let mut arg_list = Vec::new();
 
+                let schedule = HandlerSchedule::take_from(&mut fn_item.attrs);
+
                 for inp in &fn_item.sig.inputs {
                     match inp {
-                        FnArg::Receiver(_) => {
-                            proc_macro_error::abort_call_site!(
+                        FnArg::Receiver(receiver) => {
+                            proc_macro_error::abort!(
+                                receiver,
                                 "`self` argument not allowed in handler function."
                             )
                         }
@@ -465,16 +690,22 @@ fn process_fn_items(
                                 let path_type_name = typed_path_name(path);
 
                                 if is_unsupported_type(&path_type_name) {
-                                    proc_macro_error::abort_call_site!(
-                                        "Type with ident '{:?}' is not currently supported.",
+                                    proc_macro_error::abort!(
+                                        path,
+                                        "Type with ident '{}' is not currently supported.",
                                         path_seg.ident
                                     )
                                 }
 
                                 if !type_ids.contains_key(&path_type_name) {
-                                    proc_macro_error::abort_call_site!(
-                                        "Type with ident '{:?}' not defined in the ABI.",
-                                        path_seg.ident
+                                    proc_macro_error::abort!(
+                                        path,
+                                        "Type with ident '{}' not defined in the ABI.",
+                                        path_seg.ident;
+                                        help =? did_you_mean(
+                                            &path_type_name,
+                                            type_ids.keys().map(String::as_str)
+                                        )
                                     );
                                 };
 
@@ -492,7 +723,8 @@ fn process_fn_items(
                                 arg_list
                                     .push(quote! { self.#dispatcher_name[0].clone() });
                             } else {
-                                proc_macro_error::abort_call_site!(
+                                proc_macro_error::abort!(
+                                    ty,
                                     "Arguments must be types defined in the ABI."
                                 )
                             }
@@ -503,12 +735,36 @@ fn process_fn_items(
                 let fn_name = &fn_item.sig.ident;
 
                 if arg_list.is_empty() {
-                    proc_macro_error::abort_call_site!(
+                    proc_macro_error::abort!(
+                        fn_item.sig,
                         "Handler function '{}' must have at least one argument.",
-                        fn_name.to_string(),
+                        fn_name;
+                        help = "Add an argument whose type is defined in the ABI, e.g. `fn {}(event: SomeEvent)`.", fn_name
                     );
                 }
 
+                // Scheduled handlers are called at their block height or block time boundaries,
+                // using the `BlockData` of the block that was just decoded.
+                match schedule {
+                    Some(HandlerSchedule::Blocks(n)) => {
+                        input_checks.push(quote! {
+                            self.blockdata_decoded[0].height as u64 % #n == 0
+                        });
+                    }
+                    Some(HandlerSchedule::Seconds(n)) => {
+                        has_time_schedule = true;
+                        input_checks.push(quote! {
+                            {
+                                let time = self.blockdata_decoded[0].header.time;
+                                self.previous_block_time.map_or(true, |previous| {
+                                    previous.div_euclid(#n) != time.div_euclid(#n)
+                                })
+                            }
+                        });
+                    }
+                    None => {}
+                }
+
                 abi_dispatchers.push(quote! {
                     if ( #(#input_checks)&&* ) {
                         #fn_name(#(#arg_list),*)#awaitness;
@@ -518,17 +774,90 @@ fn process_fn_items(
                 handler_fns.push(fn_item);
             }
             i => {
-                proc_macro_error::abort_call_site!(
-                    "Unsupported item in indexer module '{:?}'.",
-                    i
+                proc_macro_error::abort!(
+                    i,
+                    "Unsupported item in indexer module.";
+                    help = "Only handler functions may be defined in an indexer module."
                 )
             }
         }
     }
 
+    // Time-based schedules compare the time of each block with that of the previously
+    // processed block. That time lives outside of `Decoders`, which are created anew for
+    // each block. It is unset when the indexer starts, in which case the first processed
+    // block starts a new window.
+    let (last_block_time, previous_block_time_field, load_previous_block_time) =
+        if has_time_schedule {
+            (
+                quote! {
+                    static LAST_BLOCK_TIME: std::sync::atomic::AtomicI64 = std::sync::atomic::AtomicI64::new(i64::MIN);
+                },
+                quote! {
+                    previous_block_time: Option<i64>,
+                },
+                quote! {
+                    let last = LAST_BLOCK_TIME.swap(block.header.time, std::sync::atomic::Ordering::SeqCst);
+                    decoder.previous_block_time = (last != i64::MIN).then_some(last);
+                },
+            )
+        } else {
+            (quote! {}, quote! {}, quote! {})
+        };
+
+    // ABI enums that are saved as union entities are identified by the receipts that they
+    // were decoded from, so the receipt of each decoded enum is recorded.
+    let (enum_idents, enum_fields): (Vec<_>, Vec<_>) = enum_entities
+        .iter()
+        .filter_map(|entity| {
+            abi_types
+                .iter()
+                .find(|typ| {
+                    typ.type_field.starts_with("enum ") && typ.name() == entity.enum_name
+                })
+                .map(|typ| {
+                    (
+                        format_ident!("{}", entity.enum_name),
+                        typ.decoder_field_ident(),
+                    )
+                })
+        })
+        .unzip();
+    let enum_field_indices = 0..enum_fields.len();
+
+    let (receipts, clear_enum_events, count_enum_events, record_enum_events) =
+        if enum_fields.is_empty() {
+            (
+                quote! { receipt in tx.receipts },
+                quote! {},
+                quote! {},
+                quote! {},
+            )
+        } else {
+            (
+                quote! { (receipt_index, receipt) in tx.receipts.into_iter().enumerate() },
+                quote! {
+                    #(#enum_idents::clear_decoded();)*
+                },
+                quote! {
+                    let enum_events_decoded = [#(decoder.#enum_fields.len()),*];
+                },
+                quote! {
+                    #(
+                        for event in &decoder.#enum_fields[enum_events_decoded[#enum_field_indices]..] {
+                            #enum_idents::record_decoded(event, &tx.id, receipt_index);
+                        }
+                    )*
+                },
+            )
+        };
+
     let decoder_struct = quote! {
+        #last_block_time
+
         #[derive(Default)]
         struct Decoders {
+            #previous_block_time_field
             #(#decoder_struct_fields),*
         }
 
@@ -606,6 +935,10 @@ fn process_fn_items(
                 }
             }
 
+            #script_decoder_fn
+
+            #predicate_decoder_fn
+
             pub #asyncness fn dispatch(&self) {
                 #(#abi_dispatchers)*
             }
@@ -619,8 +952,16 @@ fn process_fn_items(
 
                 #start_block
 
+                // Versioned and insert-only records are saved at the height of the
+                // block that they're saved in.
+                set_block_height(block.height)#awaitness;
+
                 let mut decoder = Decoders::default();
 
+                #load_previous_block_time
+
+                #clear_enum_events
+
                 let ty_id = BlockData::type_id();
                 let data = serialize(&block);
                 decoder.decode_type(ty_id, data);
@@ -630,7 +971,13 @@ fn process_fn_items(
                     let mut return_types = Vec::new();
                     let mut callees = HashSet::new();
 
-                    for receipt in tx.receipts {
+                    #check_if_subscribed_to_script
+
+                    #decode_predicate_inputs
+
+                    for #receipts {
+                        #count_enum_events
+
                         match receipt {
                             fuel::Receipt::Call { id: contract_id, amount, asset_id, gas, param1, to: id, .. } => {
                                 #check_if_subscribed_to_contract
@@ -653,6 +1000,7 @@ fn process_fn_items(
                                 decoder.decode_type(ty_id, data);
                             }
                             fuel::Receipt::Log { id, ra, rb, .. } => {
+                                #decode_script_log
                                 #check_if_subscribed_to_contract
                                 let ty_id = Log::type_id();
                                 let data = serialize(
@@ -665,6 +1013,7 @@ fn process_fn_items(
                                 decoder.decode_type(ty_id, data);
                             }
                             fuel::Receipt::LogData { rb, data, ptr, len, id, .. } => {
+                                #decode_script_logdata
                                 #check_if_subscribed_to_contract
                                 decoder.decode_logdata(rb as usize, data.unwrap_or(Vec::<u8>::new()));
                             }
@@ -819,6 +1168,8 @@ fn process_fn_items(
                                 info!("This type is not handled yet. (>'.')>");
                             }
                         }
+
+                        #record_enum_events
                     }
                 }
                 decoder.dispatch()#awaitness;
@@ -866,27 +1217,37 @@ pub fn prefix_abi_and_schema_paths(
     (None, schema.to_string())
 }
 
+/// Prefix a script or predicate ABI path in the same way as `prefix_abi_and_schema_paths`.
+pub fn prefix_abi_path(abi: &str) -> String {
+    match std::env::var("COMPILE_TEST_PREFIX") {
+        Ok(prefix) => std::path::Path::new(&prefix)
+            .join(abi)
+            .into_os_string()
+            .to_str()
+            .expect("Could not parse prefixed ABI path.")
+            .to_string(),
+        Err(_) => abi.to_string(),
+    }
+}
+
 pub fn get_abi_tokens(
-    namespace: &str,
-    abi: &str,
+    targets: Vec<AbigenTarget>,
     exec_source: ExecutionSource,
 ) -> proc_macro2::TokenStream {
+    if targets.is_empty() {
+        return proc_macro2::TokenStream::new();
+    }
+
     let no_std = match exec_source {
         ExecutionSource::Native => false,
         ExecutionSource::Wasm => true,
     };
 
-    match Abigen::generate(
-        vec![AbigenTarget {
-            name: namespace.to_string(),
-            abi: abi.to_owned(),
-            program_type: ProgramType::Contract,
-        }],
-        no_std,
-    ) {
+    match Abigen::generate(targets, no_std) {
         Ok(tokens) => tokens,
         Err(e) => {
-            proc_macro_error::abort_call_site!(
+            proc_macro_error::abort!(
+                manifest_span(),
                 "Could not generate tokens for abi: {:?}.",
                 e
             )
@@ -897,24 +1258,80 @@ pub fn get_abi_tokens(
 pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStream {
     let config = parse_macro_input!(attrs as IndexerConfig);
 
-    let IndexerConfig { manifest } = config;
+    let IndexerConfig {
+        manifest,
+        manifest_span,
+    } = config;
+
+    set_manifest_span(manifest_span);
 
     let path = workspace_manifest_prefix()
         .map(|x| Path::new(&x).join(&manifest))
         .unwrap_or_else(|| PathBuf::from(&manifest));
 
-    let manifest = Manifest::from_file(path).expect("Could not parse manifest.");
+    let manifest = Manifest::from_file(path).unwrap_or_else(|e| {
+        proc_macro_error::abort!(
+            manifest_span,
+            "Could not parse manifest: {}",
+            e;
+            note = "Manifest paths are resolved relative to the workspace root."
+        )
+    });
 
     let indexer_module = parse_macro_input!(item as ItemMod);
 
     let (abi, schema_string) =
         prefix_abi_and_schema_paths(manifest.abi(), manifest.graphql_schema());
 
-    let abi_tokens = match abi {
-        Some(ref abi_path) => {
-            get_abi_tokens(manifest.namespace(), abi_path, manifest.execution_source())
+    let abi_targets = [
+        (
+            abi.clone(),
+            manifest.namespace().to_string(),
+            ProgramType::Contract,
+        ),
+        (
+            manifest.script().map(|script| prefix_abi_path(&script.abi)),
+            format!("{}_script", manifest.namespace()),
+            ProgramType::Script,
+        ),
+        (
+            manifest
+                .predicate()
+                .map(|predicate| prefix_abi_path(&predicate.abi)),
+            format!("{}_predicate", manifest.namespace()),
+            ProgramType::Predicate,
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(abi, name, program_type)| {
+        abi.map(|abi| AbigenTarget {
+            name,
+            abi,
+            program_type,
+        })
+    })
+    .collect::<Vec<AbigenTarget>>();
+
+    let abi_tokens = get_abi_tokens(abi_targets, manifest.execution_source());
+
+    let enum_entities = match manifest.enum_entities() {
+        Some(names) if !names.is_empty() => {
+            let json_abi = get_json_abi(abi.clone()).unwrap_or_else(|| {
+                proc_macro_error::abort!(
+                    manifest_span,
+                    "Enum entities require a contract `abi` in the manifest."
+                )
+            });
+            derive_enum_entities(&json_abi, names).unwrap_or_else(|e| {
+                proc_macro_error::abort!(
+                    manifest_span,
+                    "Could not derive enum entities: {}",
+                    e;
+                    note = "Enum entities are set by the manifest's `enum_entities` key."
+                )
+            })
         }
-        None => proc_macro2::TokenStream::new(),
+        _ => vec![],
     };
 
     // NOTE: https://nickb.dev/blog/cargo-workspace-and-the-feature-unification-pitfall/
@@ -923,12 +1340,16 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
         manifest.identifier(),
         &schema_string,
         manifest.execution_source(),
+        &enum_entities,
     );
 
+    let enum_entity_tokens =
+        process_enum_entities(&enum_entities, manifest.execution_source());
+
     let output = match manifest.execution_source() {
         ExecutionSource::Native => {
             let (handler_block, fn_items) =
-                process_fn_items(&manifest, abi, indexer_module);
+                process_fn_items(&manifest, abi, indexer_module, &enum_entities);
             let handler_block = handler_block_native(handler_block);
             let naitve_main_tokens = native_main();
 
@@ -938,6 +1359,8 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
 
                 #graphql_tokens
 
+                #enum_entity_tokens
+
                 #handler_block
 
                 #fn_items
@@ -947,7 +1370,7 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
         }
         ExecutionSource::Wasm => {
             let (handler_block, fn_items) =
-                process_fn_items(&manifest, abi, indexer_module);
+                process_fn_items(&manifest, abi, indexer_module, &enum_entities);
             let handler_block = handler_block_wasm(handler_block);
             quote! {
 
@@ -955,6 +1378,8 @@ pub fn process_indexer_module(attrs: TokenStream, item: TokenStream) -> TokenStr
 
                 #graphql_tokens
 
+                #enum_entity_tokens
+
                 #handler_block
 
                 #fn_items
@@ -12,10 +12,10 @@ pub fn handler_block_native(
 
         async fn handle_events(blocks: Vec<BlockData>, db_conn: Arc<Mutex<Database>>) -> IndexerResult<()> {
 
+            // An executor that is restarted in the same process hands in a new
+            // database, which has to replace the one of the previous executor.
             unsafe {
-                if db.is_none() {
-                    db = Some(db_conn);
-                }
+                db = Some(db_conn);
             }
 
             #handler_block
@@ -37,6 +37,14 @@ fn native_prelude() -> proc_macro2::TokenStream {
 
         static mut db: Option<Arc<Mutex<Database>>> = None;
 
+        async fn set_block_height(height: u32) {
+            unsafe {
+                if let Some(d) = &db {
+                    d.lock().await.set_block_height(height);
+                }
+            }
+        }
+
         use fuel_indexer_utils::plugin::types::*;
         use fuel_indexer_utils::plugin::native::*;
         use fuel_indexer_utils::plugin::{serde_json, serialize, deserialize, bincode};
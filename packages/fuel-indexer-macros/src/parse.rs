@@ -1,6 +1,7 @@
+use proc_macro2::Span;
 use syn::parse::{Parse, ParseStream};
 use syn::punctuated::Punctuated;
-use syn::{LitStr, Token};
+use syn::{Attribute, Ident, LitInt, LitStr, Token};
 
 mod kw {
     syn::custom_keyword!(manifest);
@@ -8,6 +9,9 @@ mod kw {
 
 pub(crate) struct IndexerConfig {
     pub(crate) manifest: String,
+
+    /// Span of the `manifest` literal, used to point diagnostics at the manifest.
+    pub(crate) manifest_span: Span,
 }
 
 struct ConfigBuilder {
@@ -23,21 +27,27 @@ impl ConfigBuilder {
         self.manifest = Some(manifest)
     }
 
-    fn build(self) -> IndexerConfig {
+    fn build(self, span: Span) -> syn::Result<IndexerConfig> {
         let ConfigBuilder { manifest } = self;
 
-        IndexerConfig {
-            manifest: manifest
-                .expect("`manifest` specification is required in indexer definition.")
-                .name
-                .value(),
-        }
+        let Manifest { name } = manifest.ok_or_else(|| {
+            syn::Error::new(
+                span,
+                "`manifest` specification is required in indexer definition, e.g. `#[indexer(manifest = \"my_indexer.manifest.yaml\")]`.",
+            )
+        })?;
+
+        Ok(IndexerConfig {
+            manifest: name.value(),
+            manifest_span: name.span(),
+        })
     }
 }
 
 impl Parse for IndexerConfig {
     fn parse(input: ParseStream) -> syn::Result<IndexerConfig> {
         let mut config = ConfigBuilder::new();
+        let span = input.span();
 
         let items = Punctuated::<ConfigItem, Token![,]>::parse_terminated(input)?;
 
@@ -47,7 +57,7 @@ impl Parse for IndexerConfig {
             }
         }
 
-        Ok(config.build())
+        config.build(span)
     }
 }
 
@@ -80,3 +90,101 @@ impl Parse for Manifest {
         Ok(Manifest { name })
     }
 }
+
+/// Schedule on which a handler is called, set by a `#[every_n_blocks(..)]` or
+/// `#[every(..)]` attribute on the handler function.
+pub(crate) enum HandlerSchedule {
+    /// Call the handler on blocks whose height is a multiple of this interval.
+    Blocks(u64),
+
+    /// Call the handler on the first block of each window of this many seconds,
+    /// based on the block header time.
+    Seconds(i64),
+}
+
+impl HandlerSchedule {
+    /// Remove any schedule attribute from the given handler attributes, returning
+    /// the schedule it specifies.
+    pub(crate) fn take_from(attrs: &mut Vec<Attribute>) -> Option<HandlerSchedule> {
+        let mut schedule = None;
+        let mut result = Ok(());
+
+        attrs.retain(|attr| {
+            let parsed = if attr.path().is_ident("every_n_blocks") {
+                attr.parse_args::<LitInt>()
+                    .and_then(|n| Ok(HandlerSchedule::Blocks(positive_interval(&n)?)))
+            } else if attr.path().is_ident("every") {
+                attr.parse_args::<Every>().map(|every| every.schedule)
+            } else {
+                return true;
+            };
+
+            match parsed {
+                Ok(_) if schedule.is_some() => {
+                    result = Err(syn::Error::new_spanned(
+                        attr,
+                        "A handler function can only have one schedule attribute.",
+                    ));
+                }
+                Ok(parsed) => schedule = Some(parsed),
+                Err(e) => result = Err(e),
+            }
+
+            false
+        });
+
+        if let Err(e) = result {
+            proc_macro_error::abort!(e.span(), "{}", e);
+        }
+
+        schedule
+    }
+}
+
+/// Parse a schedule interval, which must be a positive integer.
+fn positive_interval<N>(lit: &LitInt) -> syn::Result<N>
+where
+    N: std::str::FromStr + Default + PartialOrd,
+    N::Err: std::fmt::Display,
+{
+    let n = lit.base10_parse::<N>()?;
+    if n <= N::default() {
+        return Err(syn::Error::new(
+            lit.span(),
+            "Schedule interval must be greater than zero.",
+        ));
+    }
+
+    Ok(n)
+}
+
+/// Arguments of an `#[every(..)]` attribute, e.g. `#[every(seconds = 3600)]`.
+struct Every {
+    schedule: HandlerSchedule,
+}
+
+impl Parse for Every {
+    fn parse(input: ParseStream) -> syn::Result<Every> {
+        let unit: Ident = input.parse()?;
+        let _: Token![=] = input.parse()?;
+        let n: LitInt = input.parse()?;
+        let n = positive_interval::<i64>(&n)?;
+
+        let seconds = match unit.to_string().as_str() {
+            "seconds" => Some(n),
+            "minutes" => n.checked_mul(60),
+            "hours" => n.checked_mul(3600),
+            _ => {
+                return Err(syn::Error::new(
+                    unit.span(),
+                    "Expected one of `seconds`, `minutes`, or `hours`.",
+                ))
+            }
+        }
+        .ok_or_else(|| syn::Error::new(unit.span(), "Schedule interval is too large."))?;
+
+        Ok(Every {
+            schedule: HandlerSchedule::Seconds(seconds),
+        })
+    }
+}
@@ -1,11 +1,14 @@
 /// Macros used to convert GraphQL tokens into Rust tokens.
 use crate::{decoder::*, helpers::*};
 use async_graphql_parser::types::{TypeDefinition, TypeKind};
-use fuel_indexer_lib::graphql::GraphQLSchema;
+use fuel_indexer_lib::graphql::{
+    enum_entities::{schema_with_enum_entities, EnumEntity, EnumEntityFieldKind},
+    GraphQLSchema,
+};
 use fuel_indexer_lib::{
     graphql::ParsedGraphQLSchema, utils::workspace_manifest_prefix, ExecutionSource,
 };
-use quote::quote;
+use quote::{format_ident, quote};
 use std::fs::File;
 use std::io::Read;
 use std::path::{Path, PathBuf};
@@ -19,8 +22,11 @@ fn process_type_def(
         TypeKind::Object(_o) => ObjectDecoder::from_typedef(typ, parsed).into(),
         TypeKind::Enum(_e) => EnumDecoder::from_typedef(typ, parsed).into(),
         TypeKind::Union(_u) => ObjectDecoder::from_typedef(typ, parsed).into(),
-        _ => proc_macro_error::abort_call_site!(
-            "Unrecognized TypeKind in GraphQL schema: {:?}",
+        TypeKind::Interface(_i) => InterfaceDecoder::from_typedef(typ, parsed).into(),
+        _ => proc_macro_error::abort!(
+            manifest_span(),
+            "Unrecognized TypeKind of '{}' in GraphQL schema: {:?}",
+            typ.name,
             typ.kind
         ),
     };
@@ -34,6 +40,7 @@ pub(crate) fn process_graphql_schema(
     identifier: &str,
     schema_path: &str,
     exec_source: ExecutionSource,
+    enum_entities: &[EnumEntity],
 ) -> proc_macro2::TokenStream {
     let namespace_tokens = const_item("NAMESPACE", namespace);
     let identifer_tokens = const_item("IDENTIFIER", identifier);
@@ -42,20 +49,29 @@ pub(crate) fn process_graphql_schema(
         .map(|p| Path::new(&p).join(schema_path))
         .unwrap_or_else(|| PathBuf::from(schema_path));
 
-    let mut file = File::open(&path)
-        .map_err(|e| {
-            proc_macro_error::abort_call_site!(
-                "Could not open schema file {:?} {:?}",
+    let mut file = File::open(&path).unwrap_or_else(|e| {
+        proc_macro_error::abort!(
+            manifest_span(),
+            "Could not open schema file {:?} {:?}",
+            path,
+            e;
+            note = "The schema file is set by the manifest's `graphql_schema` key."
+        )
+    });
+
+    let mut schema_content = String::new();
+    file.read_to_string(&mut schema_content)
+        .unwrap_or_else(|e| {
+            proc_macro_error::abort!(
+                manifest_span(),
+                "Could not read schema file {:?} {:?}",
                 path,
                 e
             )
-        })
-        .unwrap();
-
-    let mut schema_content = String::new();
-    file.read_to_string(&mut schema_content).expect("IO error");
+        });
 
-    let schema = GraphQLSchema::new(schema_content);
+    let schema =
+        GraphQLSchema::new(schema_with_enum_entities(&schema_content, enum_entities));
 
     let version_tokens = const_item("VERSION", schema.version());
 
@@ -67,7 +83,14 @@ pub(crate) fn process_graphql_schema(
 
     let schema =
         ParsedGraphQLSchema::new(namespace, identifier, exec_source, Some(&schema))
-            .expect("Failed to parse GraphQL schema.");
+            .unwrap_or_else(|e| {
+                proc_macro_error::abort!(
+                    manifest_span(),
+                    "Failed to parse GraphQL schema {:?}: {}",
+                    path,
+                    e
+                )
+            });
 
     for (_, type_def) in schema.type_defs().iter() {
         if let Some(def) = process_type_def(&schema, type_def) {
@@ -80,3 +103,124 @@ pub(crate) fn process_graphql_schema(
 
     output
 }
+
+/// Process enum entities into conversions from their ABI enums into union entities,
+/// and a `save` method on each ABI enum.
+///
+/// Saved events are identified by the transaction and receipt that they were decoded
+/// from, which the indexer module records as it decodes each block.
+pub(crate) fn process_enum_entities(
+    enum_entities: &[EnumEntity],
+    exec_source: ExecutionSource,
+) -> proc_macro2::TokenStream {
+    let (asyncness, awaitness) = match exec_source {
+        ExecutionSource::Native => (quote! { async }, quote! { .await }),
+        ExecutionSource::Wasm => (quote! {}, quote! {}),
+    };
+
+    let mut output = quote! {};
+
+    for entity in enum_entities {
+        let enum_ident = format_ident!("{}", entity.enum_name);
+        let union_ident = format_ident!("{}", entity.name);
+
+        let arms = entity.members.iter().map(|member| {
+            let variant_ident = format_ident!("{}", member.variant);
+            let member_ident = format_ident!("{}", member.name);
+            let variant = &member.variant;
+
+            if member.is_unit {
+                return quote! {
+                    #enum_ident::#variant_ident => #member_ident::new(#variant.to_string()).into(),
+                };
+            }
+
+            let args = member.fields.iter().map(|field| {
+                let value = match &field.abi_name {
+                    Some(name) => {
+                        let ident = format_ident!("{}", name);
+                        quote! { v.#ident }
+                    }
+                    None => quote! { v },
+                };
+
+                match field.kind {
+                    EnumEntityFieldKind::U16 => quote! { #value as u32 },
+                    EnumEntityFieldKind::B256 => quote! { Bytes32::from(#value.0) },
+                    EnumEntityFieldKind::Str => {
+                        quote! { #value.to_right_trimmed_str().to_string() }
+                    }
+                    EnumEntityFieldKind::UnitEnum => quote! { format!("{:?}", #value) },
+                    _ => value,
+                }
+            });
+
+            quote! {
+                #enum_ident::#variant_ident(v) => #member_ident::new(#variant.to_string(), #(#args),*).into(),
+            }
+        });
+
+        output = quote! {
+            #output
+
+            impl From<#enum_ident> for #union_ident {
+                fn from(value: #enum_ident) -> Self {
+                    match value {
+                        #(#arms)*
+                    }
+                }
+            }
+
+            impl #enum_ident {
+                /// Events of this type decoded in the current block, along with IDs that are
+                /// unique to the receipts that they were decoded from.
+                fn decoded_events() -> &'static std::sync::Mutex<Vec<(#enum_ident, UID)>> {
+                    static DECODED_EVENTS: std::sync::Mutex<Vec<(#enum_ident, UID)>> =
+                        std::sync::Mutex::new(Vec::new());
+                    &DECODED_EVENTS
+                }
+
+                /// Record an event decoded from the receipt at `receipt_index` in a transaction.
+                fn record_decoded(event: &Self, tx_id: &TxId, receipt_index: usize) {
+                    let hashed = Sha256::new()
+                        .chain_update(stringify!(#enum_ident))
+                        .chain_update(tx_id)
+                        .chain_update((receipt_index as u64).to_le_bytes())
+                        .finalize();
+                    let id = UID::new(format!("{:x}", hashed)).expect("Bad ID.");
+                    Self::decoded_events()
+                        .lock()
+                        .expect("Decoded events lock poisoned.")
+                        .push((event.clone(), id));
+                }
+
+                /// Forget the events decoded in the previous block.
+                fn clear_decoded() {
+                    Self::decoded_events()
+                        .lock()
+                        .expect("Decoded events lock poisoned.")
+                        .clear();
+                }
+
+                pub #asyncness fn save(&self) {
+                    let mut entity = #union_ident::from(self.clone());
+
+                    // Events with the same value, such as unit variants, are told apart
+                    // by the receipts that they were decoded from.
+                    {
+                        let mut decoded = Self::decoded_events()
+                            .lock()
+                            .expect("Decoded events lock poisoned.");
+                        if let Some(index) = decoded.iter().position(|(event, _)| event == self) {
+                            entity.id = decoded.remove(index).1;
+                        }
+                    }
+
+                    entity.save()#awaitness
+                }
+            }
+        };
+    }
+
+    output
+}
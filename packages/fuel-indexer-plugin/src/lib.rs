@@ -7,7 +7,7 @@ pub mod types {
         join::{JoinMetadata, RawQuery},
         FtColumn,
     };
-    pub use fuel_indexer_types::fuel::{BlockData, TxId};
+    pub use fuel_indexer_types::fuel::{BlockData, InputCoin, TxId};
     pub use fuel_indexer_types::scalar::UID;
 
     // For use with `early_exit` function to terminate execution on error.
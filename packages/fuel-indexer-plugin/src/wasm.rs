@@ -25,6 +25,7 @@ extern "C" {
     fn ff_log_data(ptr: *const u8, len: u32, log_level: u32);
     fn ff_put_object(type_id: i64, ptr: *const u8, len: u32);
     fn ff_put_many_to_many_record(ptr: *const u8, len: u32);
+    fn ff_set_block_height(height: u32);
     fn ff_early_exit(err_code: u32);
 }
 
@@ -53,6 +54,12 @@ impl Logger {
     }
 }
 
+/// Set the height of the block being indexed, at which versioned and insert-only
+/// records are saved.
+pub fn set_block_height(height: u32) {
+    unsafe { ff_set_block_height(height) }
+}
+
 /// Trait for a type entity.
 ///
 /// Any entity type that will be processed through a WASM indexer is required to implement this trait.
@@ -112,7 +119,7 @@ pub trait Entity<'a>: Sized + PartialEq + Eq + std::fmt::Debug {
             if !ptr.is_null() {
                 let len = u32::from_le_bytes(bufflen) as usize;
                 let bytes = Vec::from_raw_parts(ptr, len, len);
-                match deserialize(&bytes) {
+                return match deserialize(&bytes) {
                     Ok(vec) => Some(Self::from_row(vec)),
                     Err(_) => {
                         early_exit(WasmIndexerError::DeserializationError);
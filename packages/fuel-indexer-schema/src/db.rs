@@ -8,6 +8,7 @@
 //! to WebAssembly (WASM).
 
 pub mod manager;
+pub mod migration;
 pub mod tables;
 
 use fuel_indexer_database::IndexerDatabaseError;
@@ -43,4 +44,8 @@ pub enum IndexerSchemaDbError {
     ParsedError(#[from] fuel_indexer_lib::graphql::ParsedError),
     #[error("Manifest Error: {0:?}")]
     ManifestError(#[from] fuel_indexer_lib::manifest::ManifestError),
+    #[error("Schema changes cannot be applied to existing data:\n{0}")]
+    BreakingSchemaChange(String),
+    #[error("Schema migration error: {0}")]
+    MigrationError(String),
 }
@@ -3,9 +3,14 @@
 //! A wrapper object that encapsulates `IndexerSchema` and provides stateful database
 //! connectivity.
 
-use crate::db::{tables::IndexerSchema, IndexerSchemaDbResult};
+use crate::db::{migration::SchemaDiff, tables::IndexerSchema, IndexerSchemaDbResult};
 use fuel_indexer_database::{queries, IndexerConnection, IndexerConnectionPool};
-use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+use fuel_indexer_lib::{
+    fully_qualified_namespace,
+    graphql::{GraphQLSchema, ParsedGraphQLSchema},
+    shadow_identifier, ExecutionSource,
+};
+use std::time::{SystemTime, UNIX_EPOCH};
 use tracing::info;
 
 /// `SchemaManager` is a wrapper for `IndexerSchema` that also provides
@@ -31,21 +36,145 @@ impl SchemaManager {
     ) -> IndexerSchemaDbResult<()> {
         let version = schema.version();
 
-        if !queries::schema_exists(conn, namespace, identifier, version).await? {
-            info!("SchemaManager creating schema for Indexer({namespace}.{identifier}) with Version({version}).");
-            let _ = IndexerSchema::new(
-                namespace,
-                identifier,
-                &schema,
-                self.pool.database_type(),
-                exec_source.clone(),
-            )?
-            .commit(&schema, exec_source, conn)
-            .await?;
+        if queries::schema_exists(conn, namespace, identifier, version).await? {
+            return Ok(());
+        }
+
+        let indexer_schema = IndexerSchema::new(
+            namespace,
+            identifier,
+            &schema,
+            self.pool.database_type(),
+            exec_source.clone(),
+        )?;
+
+        // If a previous version of this schema has been committed, the indexer is
+        // being redeployed, and its existing tables are migrated instead.
+        match queries::graph_root_latest(conn, namespace, identifier).await {
+            Ok(root) => {
+                info!("SchemaManager migrating schema for Indexer({namespace}.{identifier}) from Version({}) to Version({version}).", root.version);
+                let prev_schema = GraphQLSchema::new(root.schema);
+                let _ = indexer_schema
+                    .migrate(&prev_schema, &schema, exec_source, conn)
+                    .await?;
+            }
+            Err(sqlx::Error::RowNotFound) => {
+                info!("SchemaManager creating schema for Indexer({namespace}.{identifier}) with Version({version}).");
+                let _ = indexer_schema.commit(&schema, exec_source, conn).await?;
+            }
+            Err(e) => return Err(e.into()),
         }
+
+        Ok(())
+    }
+
+    /// Compare the latest committed schema of the given indexer with a new schema.
+    ///
+    /// Returns `None` if no schema has been committed for the indexer.
+    pub async fn schema_diff(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        schema: &GraphQLSchema,
+        exec_source: ExecutionSource,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<Option<SchemaDiff>> {
+        let root = match queries::graph_root_latest(conn, namespace, identifier).await {
+            Ok(root) => root,
+            Err(sqlx::Error::RowNotFound) => return Ok(None),
+            Err(e) => return Err(e.into()),
+        };
+
+        let prev = ParsedGraphQLSchema::new(
+            namespace,
+            identifier,
+            exec_source.clone(),
+            Some(&GraphQLSchema::new(root.schema)),
+        )?;
+        let next =
+            ParsedGraphQLSchema::new(namespace, identifier, exec_source, Some(schema))?;
+
+        Ok(Some(SchemaDiff::new(&prev, &next)))
+    }
+
+    /// Create the tables of a new version of the given indexer in its shadow
+    /// schema, replacing any previous shadow schema.
+    ///
+    /// Nothing is persisted to the graph registry until the shadow schema is
+    /// promoted, so the current version of the indexer keeps serving queries.
+    pub async fn new_shadow_schema(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        schema: &GraphQLSchema,
+        exec_source: ExecutionSource,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<()> {
+        let shadow_identifier = shadow_identifier(identifier);
+
+        info!("SchemaManager creating shadow schema for Indexer({namespace}.{identifier}) with Version({}).", schema.version());
+
+        queries::remove_shadow_indexer(conn, namespace, identifier).await?;
+
+        IndexerSchema::new(
+            namespace,
+            &shadow_identifier,
+            schema,
+            self.pool.database_type(),
+            exec_source,
+        )?
+        .create_tables(conn)
+        .await?;
+
         Ok(())
     }
 
+    /// Replace the schema of the given indexer with its shadow schema.
+    ///
+    /// The current schema is renamed, rather than dropped, and its new name is
+    /// returned. The shadow schema takes its place, and the graph registry is
+    /// updated to the new version. This should be run in a transaction, so that
+    /// queries switch from one version to the other atomically.
+    pub async fn promote_shadow_schema(
+        &self,
+        namespace: &str,
+        identifier: &str,
+        schema: &GraphQLSchema,
+        exec_source: ExecutionSource,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<String> {
+        let live = fully_qualified_namespace(namespace, identifier);
+        let shadow = fully_qualified_namespace(namespace, &shadow_identifier(identifier));
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let retired = format!("{live}__retired_{now}");
+
+        info!("SchemaManager promoting shadow schema for Indexer({namespace}.{identifier}) with Version({}).", schema.version());
+
+        queries::execute_query(conn, format!("ALTER SCHEMA {live} RENAME TO {retired}"))
+            .await?;
+        queries::execute_query(conn, format!("ALTER SCHEMA {shadow} RENAME TO {live}"))
+            .await?;
+
+        queries::remove_type_ids(conn, namespace, identifier).await?;
+        queries::remove_graph_root(conn, namespace, identifier, schema.version()).await?;
+
+        IndexerSchema::new(
+            namespace,
+            identifier,
+            schema,
+            self.pool.database_type(),
+            exec_source,
+        )?
+        .register(conn)
+        .await?;
+
+        Ok(retired)
+    }
+
     /// Load an existing schema for the given indexer.
     pub async fn load_schema(
         &self,
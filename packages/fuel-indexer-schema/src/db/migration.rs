@@ -0,0 +1,746 @@
+//! # fuel_indexer_schema::db::migration
+//!
+//! Logic used to compare two versions of an indexer's GraphQL schema, so that
+//! additive changes can be applied to an existing indexer when it is redeployed,
+//! without removing its data.
+
+use async_graphql_parser::types::TypeKind;
+use fuel_indexer_database::types::*;
+use fuel_indexer_lib::graphql::{types::ObjectCol, ParsedGraphQLSchema};
+use std::{
+    collections::{BTreeSet, HashMap},
+    fmt,
+};
+
+/// A change to a GraphQL schema that can be applied to existing data.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum SchemaChange {
+    /// A new object or union type, stored in a new table.
+    NewType(String),
+
+    /// A new enum type.
+    NewEnum(String),
+
+    /// A new field on an existing type, that is nullable, has a `@default` value,
+    /// or is `@computed`.
+    NewField { typ: String, field: String },
+
+    /// A required field on an existing type that is now nullable.
+    RelaxedNullability { typ: String, field: String },
+
+    /// A new `@indexed` directive on an existing field.
+    NewIndex { typ: String, field: String },
+
+    /// A new value on an existing enum type.
+    NewEnumValue { typ: String, value: String },
+
+    /// A new join table, created for a new many-to-many field.
+    NewJoinTable(String),
+}
+
+impl fmt::Display for SchemaChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NewType(typ) => write!(f, "New type `{typ}`."),
+            Self::NewEnum(typ) => write!(f, "New enum `{typ}`."),
+            Self::NewField { typ, field } => {
+                write!(f, "New field `{typ}.{field}`.")
+            }
+            Self::RelaxedNullability { typ, field } => {
+                write!(f, "Field `{typ}.{field}` is now nullable.")
+            }
+            Self::NewIndex { typ, field } => {
+                write!(f, "New index on field `{typ}.{field}`.")
+            }
+            Self::NewEnumValue { typ, value } => {
+                write!(f, "New value `{value}` on enum `{typ}`.")
+            }
+            Self::NewJoinTable(table) => write!(f, "New join table `{table}`."),
+        }
+    }
+}
+
+/// A change to a GraphQL schema that cannot be applied to existing data.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum BreakingChange {
+    /// A type was removed.
+    DroppedType(String),
+
+    /// A type changed from an enum to an object or union, or vice versa.
+    ChangedTypeKind(String),
+
+    /// A field was removed.
+    DroppedField { typ: String, field: String },
+
+    /// The type of a field changed.
+    ChangedFieldType {
+        typ: String,
+        field: String,
+        from: String,
+        to: String,
+    },
+
+    /// A nullable field is now required.
+    TightenedNullability { typ: String, field: String },
+
+    /// A new field is required, and so has no value for existing rows.
+    NewRequiredField { typ: String, field: String },
+
+    /// A `@unique` directive was added to, or removed from, a field.
+    ChangedUniqueness { typ: String, field: String },
+
+    /// An `@indexed` directive was removed from a field.
+    DroppedIndex { typ: String, field: String },
+
+    /// A value was removed from an enum.
+    DroppedEnumValue { typ: String, value: String },
+
+    /// A `@versioned` directive was added to, or removed from, a type.
+    ChangedVersioning(String),
+
+    /// An `@immutable` directive was added to, or removed from, a type.
+    ChangedImmutability(String),
+
+    /// A `@search` directive was added to, removed from, or changed on a type.
+    ChangedSearch(String),
+
+    /// A `@computed` directive was added to, removed from, or changed on a field.
+    ChangedComputedField { typ: String, field: String },
+}
+
+impl fmt::Display for BreakingChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::DroppedType(typ) => write!(f, "Type `{typ}` was removed."),
+            Self::ChangedTypeKind(typ) => write!(f, "Type `{typ}` changed kind."),
+            Self::DroppedField { typ, field } => {
+                write!(f, "Field `{typ}.{field}` was removed.")
+            }
+            Self::ChangedFieldType {
+                typ,
+                field,
+                from,
+                to,
+            } => write!(
+                f,
+                "Field `{typ}.{field}` changed type from `{from}` to `{to}`."
+            ),
+            Self::TightenedNullability { typ, field } => {
+                write!(f, "Field `{typ}.{field}` changed from nullable to required.")
+            }
+            Self::NewRequiredField { typ, field } => write!(
+                f,
+                "New field `{typ}.{field}` is required, but existing rows have no value for it."
+            ),
+            Self::ChangedUniqueness { typ, field } => {
+                write!(f, "Field `{typ}.{field}` changed its `@unique` directive.")
+            }
+            Self::DroppedIndex { typ, field } => {
+                write!(f, "Field `{typ}.{field}` is no longer indexed.")
+            }
+            Self::DroppedEnumValue { typ, value } => {
+                write!(f, "Value `{value}` was removed from enum `{typ}`.")
+            }
+            Self::ChangedVersioning(typ) => {
+                write!(f, "Type `{typ}` changed its `@versioned` directive.")
+            }
+            Self::ChangedImmutability(typ) => {
+                write!(f, "Type `{typ}` changed its `@immutable` directive.")
+            }
+            Self::ChangedSearch(typ) => {
+                write!(f, "Type `{typ}` changed its `@search` directive.")
+            }
+            Self::ChangedComputedField { typ, field } => {
+                write!(f, "Field `{typ}.{field}` changed its `@computed` directive.")
+            }
+        }
+    }
+}
+
+/// The set of differences between two versions of an indexer's GraphQL schema.
+#[derive(Debug, Default)]
+pub struct SchemaDiff {
+    /// Changes that can be applied to existing data.
+    changes: Vec<SchemaChange>,
+
+    /// Changes that cannot be applied to existing data.
+    breaking: Vec<BreakingChange>,
+}
+
+impl SchemaDiff {
+    /// Compare a previously deployed schema with a new schema.
+    pub fn new(prev: &ParsedGraphQLSchema, next: &ParsedGraphQLSchema) -> Self {
+        let mut diff = SchemaDiff::default();
+
+        let prev_tables = tables_by_name(prev);
+        let next_tables = tables_by_name(next);
+
+        let names = prev
+            .type_defs()
+            .keys()
+            .chain(next.type_defs().keys())
+            .collect::<BTreeSet<&String>>();
+
+        for name in names {
+            let prev_typ = prev.type_defs().get(name);
+            let next_typ = next.type_defs().get(name);
+
+            match (prev_typ, next_typ) {
+                (Some(_), None) => diff
+                    .breaking
+                    .push(BreakingChange::DroppedType(name.to_owned())),
+                (None, Some(typ)) => match typ.kind {
+                    TypeKind::Enum(_) => {
+                        diff.changes.push(SchemaChange::NewEnum(name.to_owned()))
+                    }
+                    _ => diff.changes.push(SchemaChange::NewType(name.to_owned())),
+                },
+                (Some(prev_typ), Some(next_typ)) => {
+                    match (&prev_typ.kind, &next_typ.kind) {
+                        (TypeKind::Enum(prev_enum), TypeKind::Enum(next_enum)) => {
+                            let values = |e: &async_graphql_parser::types::EnumType| {
+                                e.values
+                                    .iter()
+                                    .map(|v| v.node.value.to_string())
+                                    .collect::<Vec<String>>()
+                            };
+                            let prev_values = values(prev_enum);
+                            let next_values = values(next_enum);
+
+                            for value in prev_values.iter() {
+                                if !next_values.contains(value) {
+                                    diff.breaking.push(
+                                        BreakingChange::DroppedEnumValue {
+                                            typ: name.to_owned(),
+                                            value: value.to_owned(),
+                                        },
+                                    );
+                                }
+                            }
+
+                            for value in next_values.iter() {
+                                if !prev_values.contains(value) {
+                                    diff.changes.push(SchemaChange::NewEnumValue {
+                                        typ: name.to_owned(),
+                                        value: value.to_owned(),
+                                    });
+                                }
+                            }
+                        }
+                        (TypeKind::Enum(_), _) | (_, TypeKind::Enum(_)) => diff
+                            .breaking
+                            .push(BreakingChange::ChangedTypeKind(name.to_owned())),
+                        _ => {
+                            let table_name = name.to_lowercase();
+                            if let (Some(prev_table), Some(next_table)) = (
+                                prev_tables.get(&table_name),
+                                next_tables.get(&table_name),
+                            ) {
+                                diff.diff_tables(name, prev_table, next_table);
+                            }
+                        }
+                    }
+                }
+                (None, None) => unreachable!(),
+            }
+        }
+
+        let prev_join_tables = join_table_names(prev);
+        for table in join_table_names(next) {
+            if !prev_join_tables.contains(&table) {
+                diff.changes.push(SchemaChange::NewJoinTable(table));
+            }
+        }
+
+        diff
+    }
+
+    /// Compare the columns and indexes of a table that exists in both schemas.
+    fn diff_tables(&mut self, typ: &str, prev: &Table, next: &Table) {
+        let object_col = ObjectCol::to_lowercase_string();
+
+        if prev.is_versioned() != next.is_versioned() {
+            self.breaking
+                .push(BreakingChange::ChangedVersioning(typ.to_owned()));
+        }
+
+        // Only the table layout matters here. The `onDuplicate` policy is read from
+        // the schema at runtime, so it can be changed freely.
+        if prev.is_immutable() != next.is_immutable() {
+            self.breaking
+                .push(BreakingChange::ChangedImmutability(typ.to_owned()));
+        }
+
+        if prev.search() != next.search() {
+            self.breaking
+                .push(BreakingChange::ChangedSearch(typ.to_owned()));
+        }
+
+        for prev_col in prev.columns().iter().filter(|c| c.name != object_col) {
+            let field = prev_col.name.to_owned();
+            let typ = typ.to_owned();
+
+            let next_col = match next.columns().iter().find(|c| c.name == prev_col.name) {
+                Some(c) => c,
+                // Fields that became computed are reported below.
+                None if has_column(next.computed_columns(), &field) => continue,
+                None => {
+                    self.breaking
+                        .push(BreakingChange::DroppedField { typ, field });
+                    continue;
+                }
+            };
+
+            if prev_col.graphql_type != next_col.graphql_type {
+                self.breaking.push(BreakingChange::ChangedFieldType {
+                    typ,
+                    field,
+                    from: prev_col.graphql_type.to_owned(),
+                    to: next_col.graphql_type.to_owned(),
+                });
+                continue;
+            }
+
+            if prev_col.unique != next_col.unique {
+                self.breaking.push(BreakingChange::ChangedUniqueness {
+                    typ: typ.clone(),
+                    field: field.clone(),
+                });
+            }
+
+            match (prev_col.nullable, next_col.nullable) {
+                (true, false) => self
+                    .breaking
+                    .push(BreakingChange::TightenedNullability { typ, field }),
+                (false, true) => self
+                    .changes
+                    .push(SchemaChange::RelaxedNullability { typ, field }),
+                _ => {}
+            }
+        }
+
+        for next_col in next.columns().iter() {
+            if has_column(prev.columns(), &next_col.name)
+                || has_column(prev.computed_columns(), &next_col.name)
+            {
+                continue;
+            }
+
+            let typ = typ.to_owned();
+            let field = next_col.name.to_owned();
+            // Existing rows are filled with the default value when the column is added.
+            if next_col.nullable || next_col.default.is_some() {
+                self.changes.push(SchemaChange::NewField { typ, field });
+            } else {
+                self.breaking
+                    .push(BreakingChange::NewRequiredField { typ, field });
+            }
+        }
+
+        self.diff_computed_columns(typ, prev, next);
+
+        let prev_indexes = indexed_columns(prev);
+        let next_indexes = indexed_columns(next);
+
+        for field in prev_indexes.iter() {
+            // Removing a field, or its `@unique` directive, is reported above.
+            let still_exists =
+                next.columns().iter().any(|c| &c.name == field && !c.unique);
+            if !next_indexes.contains(field) && still_exists {
+                self.breaking.push(BreakingChange::DroppedIndex {
+                    typ: typ.to_owned(),
+                    field: field.to_owned(),
+                });
+            }
+        }
+
+        for field in next_indexes.iter() {
+            // Indexes on new fields are created along with the field.
+            let existing_field = prev.columns().iter().any(|c| &c.name == field);
+            if !prev_indexes.contains(field) && existing_field {
+                self.changes.push(SchemaChange::NewIndex {
+                    typ: typ.to_owned(),
+                    field: field.to_owned(),
+                });
+            }
+        }
+
+        let prev_composites = composite_indexes(prev);
+        let next_composites = composite_indexes(next);
+
+        for (fields, unique) in prev_composites.difference(&next_composites) {
+            // A change of uniqueness is reported below.
+            if next_composites.contains(&(fields.to_owned(), !unique)) {
+                continue;
+            }
+
+            let (typ, field) = (typ.to_owned(), fields.to_owned());
+            self.breaking.push(if *unique {
+                BreakingChange::ChangedUniqueness { typ, field }
+            } else {
+                BreakingChange::DroppedIndex { typ, field }
+            });
+        }
+
+        for (fields, unique) in next_composites.difference(&prev_composites) {
+            let (typ, field) = (typ.to_owned(), fields.to_owned());
+            // Existing rows may already contain duplicate combinations of values.
+            if *unique || prev_composites.contains(&(field.clone(), !unique)) {
+                self.breaking
+                    .push(BreakingChange::ChangedUniqueness { typ, field });
+            } else {
+                self.changes.push(SchemaChange::NewIndex { typ, field });
+            }
+        }
+    }
+
+    /// Compare the `@computed` columns of a table that exists in both schemas.
+    ///
+    /// New computed columns are generated for existing rows when they're added, but any
+    /// other change to a computed column can't be applied in place.
+    fn diff_computed_columns(&mut self, typ: &str, prev: &Table, next: &Table) {
+        let definition = |c: &Column| {
+            (
+                c.computed.clone(),
+                c.graphql_type.clone(),
+                c.nullable,
+                c.unique,
+            )
+        };
+
+        for prev_col in prev.computed_columns().iter() {
+            let typ = typ.to_owned();
+            let field = prev_col.name.to_owned();
+
+            match next.computed_columns().iter().find(|c| c.name == field) {
+                Some(next_col) if definition(next_col) == definition(prev_col) => {}
+                None if !has_column(next.columns(), &field) => self
+                    .breaking
+                    .push(BreakingChange::DroppedField { typ, field }),
+                _ => self
+                    .breaking
+                    .push(BreakingChange::ChangedComputedField { typ, field }),
+            }
+        }
+
+        for next_col in next.computed_columns().iter() {
+            let typ = typ.to_owned();
+            let field = next_col.name.to_owned();
+
+            if has_column(prev.computed_columns(), &field) {
+                continue;
+            }
+
+            if has_column(prev.columns(), &field) {
+                self.breaking
+                    .push(BreakingChange::ChangedComputedField { typ, field });
+            } else {
+                self.changes.push(SchemaChange::NewField { typ, field });
+            }
+        }
+    }
+
+    /// Changes that can be applied to existing data.
+    pub fn changes(&self) -> &[SchemaChange] {
+        &self.changes
+    }
+
+    /// Changes that cannot be applied to existing data.
+    pub fn breaking(&self) -> &[BreakingChange] {
+        &self.breaking
+    }
+
+    /// Whether this diff contains any changes that cannot be applied to existing data.
+    pub fn is_breaking(&self) -> bool {
+        !self.breaking.is_empty()
+    }
+
+    /// A human-readable list of the breaking changes in this diff.
+    pub fn report(&self) -> String {
+        self.breaking
+            .iter()
+            .map(|c| format!("  - {c}"))
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Return the tables derived from a schema, keyed by table name.
+pub(crate) fn tables_by_name(parsed: &ParsedGraphQLSchema) -> HashMap<String, Table> {
+    parsed
+        .non_enum_typdefs()
+        .iter()
+        .map(|(_, t)| Table::from_typedef(t, parsed))
+        .map(|t| (t.sql_name(), t))
+        .collect()
+}
+
+/// Whether any of the given columns is named `name`.
+fn has_column(columns: &[Column], name: &str) -> bool {
+    columns.iter().any(|c| c.name == name)
+}
+
+/// Return the names of the join tables derived from a schema.
+fn join_table_names(parsed: &ParsedGraphQLSchema) -> BTreeSet<String> {
+    parsed
+        .join_table_meta()
+        .values()
+        .flat_map(|meta| meta.iter().map(|m| m.table_name()))
+        .collect()
+}
+
+/// Return the names of the columns of a table that have a single column index.
+fn indexed_columns(table: &Table) -> BTreeSet<String> {
+    table
+        .constraints()
+        .iter()
+        .filter_map(|c| match c {
+            Constraint::Index(idx) if idx.column_names.len() == 1 => {
+                Some(idx.column_names[0].to_owned())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+/// Return the columns, and uniqueness, of each multi-column index of a table.
+fn composite_indexes(table: &Table) -> BTreeSet<(String, bool)> {
+    table
+        .constraints()
+        .iter()
+        .filter_map(|c| match c {
+            Constraint::Index(idx) if idx.column_names.len() > 1 => {
+                Some((idx.column_names.join(", "), idx.unique))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+
+    fn parse(schema: &str) -> ParsedGraphQLSchema {
+        ParsedGraphQLSchema::new(
+            "test",
+            "test",
+            ExecutionSource::Wasm,
+            Some(&GraphQLSchema::new(schema.to_string())),
+        )
+        .unwrap()
+    }
+
+    const PREV: &str = r#"
+enum Status {
+    Active
+    Inactive
+}
+
+type Account @entity {
+    id: ID!
+    address: Address!
+    label: Charfield
+    status: Status!
+}
+"#;
+
+    #[test]
+    fn test_additive_changes_are_not_breaking() {
+        let next = r#"
+enum Status {
+    Active
+    Inactive
+    Frozen
+}
+
+type Account @entity {
+    id: ID!
+    address: Address! @indexed
+    label: Charfield
+    status: Status!
+    balance: UInt8
+}
+
+type Wallet @entity {
+    id: ID!
+    account: Account!
+}
+"#;
+
+        let diff = SchemaDiff::new(&parse(PREV), &parse(next));
+
+        assert!(!diff.is_breaking());
+        assert_eq!(
+            diff.changes(),
+            &[
+                SchemaChange::NewField {
+                    typ: "Account".to_string(),
+                    field: "balance".to_string(),
+                },
+                SchemaChange::NewIndex {
+                    typ: "Account".to_string(),
+                    field: "address".to_string(),
+                },
+                SchemaChange::NewEnumValue {
+                    typ: "Status".to_string(),
+                    value: "Frozen".to_string(),
+                },
+                SchemaChange::NewType("Wallet".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_breaking_changes_are_reported() {
+        let next = r#"
+enum Status {
+    Active
+}
+
+type Account @entity {
+    id: ID!
+    address: Charfield!
+    status: Status!
+    balance: UInt8!
+}
+"#;
+
+        let diff = SchemaDiff::new(&parse(PREV), &parse(next));
+
+        assert!(diff.is_breaking());
+        assert_eq!(
+            diff.report(),
+            "  - Field `Account.address` changed type from `Address` to `Charfield`.
+  - Field `Account.label` was removed.
+  - New field `Account.balance` is required, but existing rows have no value for it.
+  - Value `Inactive` was removed from enum `Status`."
+        );
+    }
+
+    #[test]
+    fn test_new_required_field_with_default_is_not_breaking() {
+        let next = PREV.replace(
+            "status: Status!",
+            "status: Status!\n    nonce: UInt8! @default(value: 0)\n    tag: Charfield! @computed(sql: \"upper(label)\")",
+        );
+
+        let diff = SchemaDiff::new(&parse(PREV), &parse(&next));
+
+        assert!(!diff.is_breaking());
+        assert_eq!(
+            diff.changes(),
+            &[
+                SchemaChange::NewField {
+                    typ: "Account".to_string(),
+                    field: "nonce".to_string(),
+                },
+                SchemaChange::NewField {
+                    typ: "Account".to_string(),
+                    field: "tag".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_changed_computed_field_is_breaking() {
+        let prev = PREV.replace(
+            "status: Status!",
+            "status: Status!\n    tag: Charfield @computed(sql: \"upper(label)\")",
+        );
+        let next = prev.replace("upper(label)", "lower(label)");
+
+        let diff = SchemaDiff::new(&parse(&prev), &parse(&next));
+
+        assert_eq!(
+            diff.breaking(),
+            &[BreakingChange::ChangedComputedField {
+                typ: "Account".to_string(),
+                field: "tag".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_tightened_nullability_is_breaking() {
+        let next = PREV.replace("label: Charfield", "label: Charfield!");
+
+        let diff = SchemaDiff::new(&parse(PREV), &parse(&next));
+
+        assert_eq!(
+            diff.breaking(),
+            &[BreakingChange::TightenedNullability {
+                typ: "Account".to_string(),
+                field: "label".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_changed_versioning_is_breaking() {
+        let next =
+            PREV.replace("type Account @entity", "type Account @entity @versioned");
+
+        let diff = SchemaDiff::new(&parse(PREV), &parse(&next));
+
+        assert_eq!(
+            diff.breaking(),
+            &[BreakingChange::ChangedVersioning("Account".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_changed_immutability_is_breaking() {
+        let next =
+            PREV.replace("type Account @entity", "type Account @entity @immutable");
+
+        let diff = SchemaDiff::new(&parse(PREV), &parse(&next));
+
+        assert_eq!(
+            diff.breaking(),
+            &[BreakingChange::ChangedImmutability("Account".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_changed_search_is_breaking() {
+        let next = PREV.replace(
+            "type Account @entity",
+            "type Account @entity @search(fields: [\"label\"])",
+        );
+
+        let diff = SchemaDiff::new(&parse(PREV), &parse(&next));
+
+        assert_eq!(
+            diff.breaking(),
+            &[BreakingChange::ChangedSearch("Account".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_composite_index_changes() {
+        let next = PREV.replace(
+            "type Account @entity",
+            "type Account @entity @index(fields: [\"address\", \"status\"]) @unique(fields: [\"address\", \"label\"])",
+        );
+
+        let diff = SchemaDiff::new(&parse(PREV), &parse(&next));
+
+        assert_eq!(
+            diff.changes(),
+            &[SchemaChange::NewIndex {
+                typ: "Account".to_string(),
+                field: "address, status".to_string(),
+            }]
+        );
+        assert_eq!(
+            diff.breaking(),
+            &[BreakingChange::ChangedUniqueness {
+                typ: "Account".to_string(),
+                field: "address, label".to_string(),
+            }]
+        );
+    }
+}
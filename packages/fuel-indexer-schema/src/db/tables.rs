@@ -5,13 +5,17 @@
 //!
 //! Also used to load tables from the database when web requests are made.
 
-use crate::db::IndexerSchemaDbResult;
+use crate::db::{
+    migration::{tables_by_name, SchemaChange, SchemaDiff},
+    IndexerSchemaDbError, IndexerSchemaDbResult,
+};
 use fuel_indexer_database::{
     queries, types::*, DbType, IndexerConnection, IndexerConnectionPool,
 };
 use fuel_indexer_lib::graphql::{GraphQLSchema, ParsedGraphQLSchema};
 use fuel_indexer_lib::{manifest::Manifest, ExecutionSource};
 use itertools::Itertools;
+use std::collections::BTreeSet;
 
 /// `IndexerSchema` is used to encapsulate most of the logic related to parsing
 /// GraphQL types, generating SQL from those types, and committing that SQL to
@@ -85,12 +89,23 @@ impl IndexerSchema {
             Some(schema),
         )?;
 
-        let mut statements = Vec::new();
         self.schema = schema.to_owned();
         self.parsed = parsed_schema;
 
+        self.register(conn).await?;
+        self.create_tables(conn).await?;
+
+        Ok(self)
+    }
+
+    /// Persist the metadata of this schema (its `GraphRoot`, `TypeId`s, and `Column`s)
+    /// to the graph registry, without creating any tables.
+    pub async fn register(
+        &mut self,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<()> {
         let root = GraphRoot {
-            version: schema.version().to_owned(),
+            version: self.schema.version().to_owned(),
             schema_name: self.namespace.to_owned(),
             schema_identifier: self.identifier.to_owned(),
             schema: self.schema.to_string(),
@@ -99,17 +114,6 @@ impl IndexerSchema {
 
         queries::new_graph_root(conn, root).await?;
 
-        // TODO: Abstract this into a SQLSchema (or named something else)?
-        match self.db_type {
-            DbType::Postgres => {
-                let create = format!(
-                    "CREATE SCHEMA IF NOT EXISTS {};",
-                    self.parsed.fully_qualified_namespace(),
-                );
-                statements.push(create);
-            }
-        }
-
         let mut type_ids = self
             .parsed
             .type_defs()
@@ -136,6 +140,74 @@ impl IndexerSchema {
 
         queries::type_id_insert(conn, type_ids).await?;
 
+        self.tables = self.derive_tables();
+
+        let columns = self
+            .tables
+            .iter()
+            .flat_map(|t| t.columns())
+            .map(|c| c.to_owned())
+            .collect::<Vec<Column>>();
+
+        queries::new_column_insert(conn, columns).await?;
+
+        Ok(())
+    }
+
+    /// Create the database schema, tables, and constraints for this schema, without
+    /// persisting any metadata to the graph registry.
+    pub async fn create_tables(
+        &mut self,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<()> {
+        let mut statements = Vec::new();
+
+        // TODO: Abstract this into a SQLSchema (or named something else)?
+        match self.db_type {
+            DbType::Postgres => {
+                let create = format!(
+                    "CREATE SCHEMA IF NOT EXISTS {};",
+                    self.parsed.fully_qualified_namespace(),
+                );
+                statements.push(create);
+            }
+        }
+
+        self.tables = self.derive_tables();
+
+        statements.extend(extension_stmnts(&self.tables));
+
+        let table_stmnts = self
+            .tables
+            .iter()
+            .filter_map(|t| {
+                let stmnt = t.create();
+                if stmnt.is_empty() {
+                    return None;
+                }
+                Some(stmnt)
+            })
+            .collect::<Vec<String>>();
+        statements.extend(table_stmnts);
+
+        let constraint_stmnts = self
+            .tables
+            .iter()
+            .flat_map(|t| t.constraints())
+            .map(|c| c.create())
+            .collect::<Vec<String>>();
+
+        statements.extend(constraint_stmnts);
+
+        for stmnt in statements.iter() {
+            queries::execute_query(conn, stmnt.to_owned()).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Return the tables, including join tables, derived from this schema.
+    pub fn derive_tables(&self) -> Vec<Table> {
         let mut tables = self
             .parsed
             .non_enum_typdefs()
@@ -155,39 +227,246 @@ impl IndexerSchema {
 
         tables.append(&mut join_tables);
 
-        let columns = tables
-            .iter()
-            .flat_map(|t| t.columns())
-            .map(|c| c.to_owned())
-            .collect::<Vec<Column>>();
+        tables
+    }
 
-        queries::new_column_insert(conn, columns).await?;
+    /// Apply the changes between a previously committed schema and a new schema
+    /// to the database, without removing any existing data.
+    ///
+    /// Only additive changes (e.g., new types, new nullable fields, new indexes, and
+    /// new enum values) are applied. If the new schema contains any breaking changes,
+    /// nothing is applied and the breaking changes are returned in the error.
+    pub async fn migrate(
+        mut self,
+        prev_schema: &GraphQLSchema,
+        schema: &GraphQLSchema,
+        exec_source: ExecutionSource,
+        conn: &mut IndexerConnection,
+    ) -> IndexerSchemaDbResult<Self> {
+        let prev_parsed = ParsedGraphQLSchema::new(
+            &self.namespace,
+            &self.identifier,
+            exec_source.clone(),
+            Some(prev_schema),
+        )?;
+        let parsed_schema = ParsedGraphQLSchema::new(
+            &self.namespace,
+            &self.identifier,
+            exec_source,
+            Some(schema),
+        )?;
 
-        let table_stmnts = tables
-            .iter()
-            .filter_map(|t| {
-                let stmnt = t.create();
-                if stmnt.is_empty() {
-                    return None;
+        let diff = SchemaDiff::new(&prev_parsed, &parsed_schema);
+        if diff.is_breaking() {
+            return Err(IndexerSchemaDbError::BreakingSchemaChange(diff.report()));
+        }
+
+        self.schema = schema.to_owned();
+        self.parsed = parsed_schema;
+
+        let root = GraphRoot {
+            version: schema.version().to_owned(),
+            schema_name: self.namespace.to_owned(),
+            schema_identifier: self.identifier.to_owned(),
+            schema: self.schema.to_string(),
+            ..GraphRoot::default()
+        };
+
+        queries::new_graph_root(conn, root).await?;
+
+        // Existing types keep their `TypeId`s, and are moved to the new version.
+        queries::type_id_update_version(
+            conn,
+            &self.namespace,
+            &self.identifier,
+            schema.version(),
+        )
+        .await?;
+
+        let fully_qualified_namespace = self.parsed.fully_qualified_namespace();
+        let tables = tables_by_name(&self.parsed);
+        let table_for = |name: &str| {
+            tables.get(name).ok_or_else(|| {
+                IndexerSchemaDbError::MigrationError(format!(
+                    "Table '{name}' not found in parsed schema."
+                ))
+            })
+        };
+
+        let mut type_ids = Vec::new();
+        let mut new_tables = Vec::new();
+        let mut join_tables = Vec::new();
+        let mut altered_tables = BTreeSet::new();
+        let mut alter_stmnts = Vec::new();
+        let mut constraint_stmnts = Vec::new();
+
+        for change in diff.changes() {
+            match change {
+                SchemaChange::NewType(typ) | SchemaChange::NewEnum(typ) => {
+                    let typdef = self.parsed.type_defs().get(typ).ok_or_else(|| {
+                        IndexerSchemaDbError::MigrationError(format!(
+                            "TypeDefinition '{typ}' not found in parsed schema."
+                        ))
+                    })?;
+                    type_ids.push(TypeId::from_typedef(typdef, &self.parsed));
+
+                    if let Some(table) = tables.get(&typ.to_lowercase()) {
+                        new_tables.push(table);
+                    }
                 }
-                Some(stmnt)
+                SchemaChange::NewJoinTable(name) => {
+                    let meta = self
+                        .parsed
+                        .join_table_meta()
+                        .values()
+                        .flatten()
+                        .find(|m| &m.table_name() == name)
+                        .ok_or_else(|| {
+                            IndexerSchemaDbError::MigrationError(format!(
+                                "JoinTableMeta '{name}' not found in parsed schema."
+                            ))
+                        })?;
+                    type_ids.push(TypeId::from_join_meta(meta.to_owned(), &self.parsed));
+                    join_tables
+                        .push(Table::from_join_meta(meta.to_owned(), &self.parsed));
+                }
+                SchemaChange::NewField { typ, field } => {
+                    let table = table_for(&typ.to_lowercase())?;
+                    altered_tables.insert(table.sql_name());
+
+                    let column = table
+                        .columns()
+                        .iter()
+                        .chain(table.computed_columns().iter())
+                        .find(|c| &c.name == field)
+                        .ok_or_else(|| {
+                            IndexerSchemaDbError::MigrationError(format!(
+                                "Column '{field}' not found in table '{}'.",
+                                table.sql_name()
+                            ))
+                        })?;
+
+                    if column.persistence == Persistence::Scalar {
+                        alter_stmnts.push(format!(
+                            "ALTER TABLE {}.{} ADD COLUMN {};",
+                            fully_qualified_namespace,
+                            table.sql_name(),
+                            column.create()
+                        ));
+                        constraint_stmnts.extend(
+                            table
+                                .constraints()
+                                .iter()
+                                .filter(|c| match c {
+                                    Constraint::Index(idx) => {
+                                        idx.column_names.len() == 1
+                                            && &idx.column_names[0] == field
+                                    }
+                                    Constraint::Fk(fk) => &fk.column_name == field,
+                                    Constraint::Pk(_) | Constraint::BlockRange(_) => {
+                                        false
+                                    }
+                                })
+                                .map(|c| c.create()),
+                        );
+                    }
+                }
+                SchemaChange::RelaxedNullability { typ, field } => {
+                    let table = table_for(&typ.to_lowercase())?;
+                    altered_tables.insert(table.sql_name());
+
+                    let scalar = table.columns().iter().any(|c| {
+                        &c.name == field && c.persistence == Persistence::Scalar
+                    });
+                    if scalar {
+                        alter_stmnts.push(format!(
+                            "ALTER TABLE {}.{} ALTER COLUMN {} DROP NOT NULL;",
+                            fully_qualified_namespace,
+                            table.sql_name(),
+                            field
+                        ));
+                    }
+                }
+                SchemaChange::NewIndex { typ, field } => {
+                    let table = table_for(&typ.to_lowercase())?;
+                    constraint_stmnts.extend(
+                        table
+                            .constraints()
+                            .iter()
+                            .filter(|c| {
+                                matches!(c, Constraint::Index(idx) if &idx.column_names.join(", ") == field)
+                            })
+                            .map(|c| c.create()),
+                    );
+                }
+                // Enum values are stored as strings, so there is nothing to alter.
+                SchemaChange::NewEnumValue { .. } => {}
+            }
+        }
+
+        if !type_ids.is_empty() {
+            queries::type_id_insert(conn, type_ids).await?;
+        }
+
+        // The columns of altered tables are re-registered, since adding a field can
+        // change the position of the fields that follow it.
+        let altered = altered_tables
+            .iter()
+            .map(|name| table_for(name))
+            .collect::<IndexerSchemaDbResult<Vec<&Table>>>()?;
+        let altered_type_ids = altered
+            .iter()
+            .map(|t| {
+                t.columns().first().map(|c| c.type_id).ok_or_else(|| {
+                    IndexerSchemaDbError::MigrationError(format!(
+                        "Table '{}' has no columns.",
+                        t.sql_name()
+                    ))
+                })
             })
-            .collect::<Vec<String>>();
-        statements.extend(table_stmnts);
+            .collect::<IndexerSchemaDbResult<Vec<i64>>>()?;
+        queries::remove_columns_by_type_id(conn, altered_type_ids).await?;
 
-        let constraint_stmnts = tables
+        let columns = new_tables
             .iter()
-            .flat_map(|t| t.constraints())
-            .map(|c| c.create())
-            .collect::<Vec<String>>();
+            .copied()
+            .chain(join_tables.iter())
+            .chain(altered.iter().copied())
+            .flat_map(|t| t.columns())
+            .map(|c| c.to_owned())
+            .collect::<Vec<Column>>();
 
+        if !columns.is_empty() {
+            queries::new_column_insert(conn, columns).await?;
+        }
+
+        // New tables are created first, so that constraints on altered tables can
+        // reference them.
+        let mut statements = extension_stmnts(tables.values());
+        statements.extend(
+            new_tables
+                .iter()
+                .copied()
+                .chain(join_tables.iter())
+                .map(|t| t.create())
+                .filter(|stmnt| !stmnt.is_empty()),
+        );
+        statements.extend(alter_stmnts);
+        statements.extend(
+            new_tables
+                .iter()
+                .copied()
+                .chain(join_tables.iter())
+                .flat_map(|t| t.constraints())
+                .map(|c| c.create()),
+        );
         statements.extend(constraint_stmnts);
 
         for stmnt in statements.iter() {
             queries::execute_query(conn, stmnt.to_owned()).await?;
         }
 
-        self.tables = tables;
+        self.tables = tables.into_values().chain(join_tables).collect();
 
         Ok(self)
     }
@@ -232,3 +511,15 @@ impl IndexerSchema {
         })
     }
 }
+
+/// Return the statements that create the database extensions required by the
+/// constraints of the given tables.
+fn extension_stmnts<'a>(tables: impl IntoIterator<Item = &'a Table>) -> Vec<String> {
+    tables
+        .into_iter()
+        .flat_map(|t| t.constraints())
+        .filter_map(|c| c.extension())
+        .unique()
+        .map(|ext| format!("CREATE EXTENSION IF NOT EXISTS {ext};"))
+        .collect()
+}
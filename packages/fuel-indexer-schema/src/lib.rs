@@ -53,6 +53,7 @@ pub enum FtColumn {
     Address(Option<Address>),
     Array(Option<Vec<FtColumn>>),
     AssetId(Option<AssetId>),
+    BigDecimal(Option<BigDecimal>),
     Blob(Option<Blob>),
     BlockHeight(Option<BlockHeight>),
     BlockId(Option<BlockId>),
@@ -82,6 +83,7 @@ pub enum FtColumn {
     UID(Option<UID>),
     UInt1(Option<UInt1>),
     UInt16(Option<UInt16>),
+    UInt32(Option<UInt32>),
     UInt4(Option<UInt4>),
     UInt8(Option<UInt8>),
     Virtual(Option<Virtual>),
@@ -185,6 +187,14 @@ impl FtColumn {
                 Some(val) => format!("{val}"),
                 None => String::from(NULL_VALUE),
             },
+            FtColumn::UInt32(value) => match value {
+                Some(val) => format!("{val}"),
+                None => String::from(NULL_VALUE),
+            },
+            FtColumn::BigDecimal(value) => match value {
+                Some(val) => format!("{val}"),
+                None => String::from(NULL_VALUE),
+            },
             FtColumn::Timestamp(value) => match value {
                 Some(val) => format!("{val}"),
                 None => String::from(NULL_VALUE),
@@ -290,6 +300,7 @@ mod tests {
     #[test]
     fn test_fragments_some_types() {
         use super::*;
+        use std::str::FromStr;
 
         let uid = FtColumn::ID(Some(
             UID::new(
@@ -347,6 +358,10 @@ mod tests {
         let blob = FtColumn::Blob(Some(Blob::from(vec![0u8, 1, 2, 3, 4, 5])));
         let r#enum = FtColumn::Enum(Some(String::from("hello")));
         let array = FtColumn::Array(Some(vec![FtColumn::Int4(Some(1))]));
+        let uint32 = FtColumn::UInt32(Some(U256::MAX));
+        let big_decimal = FtColumn::BigDecimal(Some(
+            BigDecimal::from_str("-12345.000000000000000000000001").unwrap(),
+        ));
 
         insta::assert_yaml_snapshot!(uid.query_fragment());
         insta::assert_yaml_snapshot!(addr.query_fragment());
@@ -380,6 +395,8 @@ mod tests {
         insta::assert_yaml_snapshot!(blob.query_fragment());
         insta::assert_yaml_snapshot!(r#enum.query_fragment());
         insta::assert_yaml_snapshot!(array.query_fragment());
+        insta::assert_yaml_snapshot!(uint32.query_fragment());
+        insta::assert_yaml_snapshot!(big_decimal.query_fragment());
     }
 
     #[test]
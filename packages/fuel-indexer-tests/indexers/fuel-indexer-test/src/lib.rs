@@ -435,7 +435,7 @@ mod fuel_indexer_test {
     }
 
     fn fuel_indexer_trigger_enum(
-        _first: AnotherSimpleEnum,
+        first: AnotherSimpleEnum,
         _second: NestedEnum,
         _third: AnotherSimpleEnum,
     ) {
@@ -446,6 +446,8 @@ mod fuel_indexer_test {
             one: Some(EnumEntity::One.into()),
         };
         e.save();
+
+        first.save();
     }
 
     fn fuel_indexer_trigger_non_indexable_type(_b: BlockData) {
@@ -0,0 +1,10 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+
+include!("../../parity_indexer.rs");
+
+parity_indexer!(
+    "packages/fuel-indexer-tests/indexers/parity/parity_wasm.yaml",
+    {},
+    {}
+);
@@ -0,0 +1,73 @@
+// The parity indexer is compiled once for WASM execution (see `parity-wasm`) and
+// once for native execution (see the `parity` integration tests), so that both
+// code paths generated by the `#[indexer]` macro run the same handlers.
+//
+// Native handlers are `async` and `.await` their entity operations, so each mode
+// passes in its own `async` and `.await` tokens; everything else is shared.
+macro_rules! parity_indexer {
+    ($manifest:literal, { $($async:tt)? }, { $($await:tt)* }) => {
+        #[indexer(manifest = $manifest)]
+        mod parity_indexer {
+            $($async)? fn parity_block(block: BlockData) {
+                ParityBlock::new(block.height, block.time, block.transactions.len() as u64)
+                    .save()$($await)*;
+
+                // The tip is saved again at each block, so it has a version per block.
+                let tip = ParityTip {
+                    id: uid([0]),
+                    height: block.height,
+                };
+                tip.save()$($await)*;
+            }
+
+            // Blocks are a minute apart, so a new two-minute window starts every other block.
+            #[every(minutes = 2)]
+            $($async)? fn parity_snapshot(block: BlockData) {
+                let snapshot = ParitySnapshot {
+                    id: uid(block.height.to_le_bytes()),
+                    height: block.height,
+                };
+                snapshot.save()$($await)*;
+            }
+
+            $($async)? fn parity_pung(pung: Pung, block: BlockData) {
+                let entity = ParityPung {
+                    id: uid(pung.id.to_le_bytes()),
+                    pung_id: pung.id,
+                    value: pung.value,
+                    is_pung: pung.is_pung,
+                    pung_from: pung.pung_from,
+                };
+                entity.save()$($await)*;
+
+                // Arbitrary-precision numbers are covered with values that don't fit
+                // in 64 bits.
+                let supply = ParitySupply {
+                    id: entity.id.clone(),
+                    total: (U256::from(pung.value) << 128) + U256::from(pung.id),
+                    share: BigDecimal::from(pung.value) / BigDecimal::from(1_000_000_000_000u64),
+                };
+                supply.save()$($await)*;
+
+                // Pungs are accumulated across blocks, so that loading an entity
+                // and saving its many-to-many relationships are covered too.
+                let mut batch = match ParityBatch::load(uid([0]))$($await)* {
+                    Some(batch) => batch,
+                    None => ParityBatch {
+                        id: uid([0]),
+                        height: block.height,
+                        pungs: vec![],
+                    },
+                };
+                batch.height = block.height;
+                batch.pungs.push(entity.id);
+                batch.save()$($await)*;
+            }
+
+            // `AnotherSimpleEnum` is listed under `enum_entities`, so it can be saved directly.
+            $($async)? fn parity_enum_event(event: AnotherSimpleEnum) {
+                event.save()$($await)*;
+            }
+        }
+    };
+}
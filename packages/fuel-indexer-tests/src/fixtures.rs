@@ -4,17 +4,29 @@ use crate::{
 use actix_service::Service;
 use actix_web::test;
 use axum::routing::Router;
-use fuel_indexer::IndexerService;
+use fuel_indexer::{Database, IndexerService};
 use fuel_indexer_api_server::api::WebApi;
-use fuel_indexer_database::IndexerConnectionPool;
+use fuel_indexer_database::{queries, types::DbType, IndexerConnectionPool};
+use fuel_indexer_graphql::graphql::GraphqlQueryBuilder;
 use fuel_indexer_lib::{
     config::{DatabaseConfig, IndexerConfig, WebApiConfig},
     defaults::SERVICE_REQUEST_CHANNEL_SIZE,
+    graphql::GraphQLSchema,
     manifest::Manifest,
     utils::{derive_socket_addr, ServiceRequest},
+    ExecutionSource,
 };
 use fuel_indexer_postgres;
+use fuel_indexer_schema::db::{manager::SchemaManager, tables::IndexerSchema};
+use fuel_indexer_types::{
+    fuel::{
+        BlockData, Consensus, Header, Receipt, Transaction, TransactionData,
+        TransactionStatus,
+    },
+    Identity,
+};
 use fuels::{
+    core::{codec::ABIEncoder, traits::Tokenizable},
     macros::abigen,
     prelude::{
         setup_single_asset_coins, setup_test_client, AssetId, Bech32ContractId, Contract,
@@ -440,6 +452,242 @@ pub async fn connect_to_deployed_contract(
     Ok(contract)
 }
 
+/// Register the schema of an indexer without starting an executor, so that
+/// executors can be driven directly with block fixtures.
+pub async fn register_indexer_schema(
+    pool: &IndexerConnectionPool,
+    manifest: &Manifest,
+) -> Result<String, Box<dyn std::error::Error>> {
+    let mut conn = pool.acquire().await?;
+
+    queries::register_indexer(
+        &mut conn,
+        manifest.namespace(),
+        manifest.identifier(),
+        None,
+    )
+    .await?;
+
+    let schema = manifest.graphql_schema_content()?;
+    let version = schema.version().to_string();
+
+    SchemaManager::new(pool.clone())
+        .new_schema(
+            manifest.namespace(),
+            manifest.identifier(),
+            schema,
+            manifest.execution_source(),
+            &mut conn,
+        )
+        .await?;
+
+    Ok(version)
+}
+
+/// Create the tables of a GraphQL schema for an indexer, without registering the
+/// indexer, and return a `Database` that writes to them along with the
+/// `IndexerSchema` that queries against them are built from.
+pub async fn setup_indexer(
+    pool: &IndexerConnectionPool,
+    schema: &str,
+    namespace: &str,
+    identifier: &str,
+) -> (Database, IndexerSchema) {
+    let schema = GraphQLSchema::new(schema.to_string());
+
+    let mut conn = pool.acquire().await.unwrap();
+    SchemaManager::new(pool.clone())
+        .new_schema(
+            namespace,
+            identifier,
+            schema.clone(),
+            ExecutionSource::Wasm,
+            &mut conn,
+        )
+        .await
+        .unwrap();
+
+    let manifest = Manifest::try_from(
+        format!(
+            r#"
+namespace: {namespace}
+identifier: {identifier}
+abi: ~
+contract_id: ~
+graphql_schema: schema.graphql
+module:
+  wasm: indexer.wasm
+"#
+        )
+        .as_str(),
+    )
+    .unwrap();
+
+    let mut db = Database::new(pool.clone(), &manifest, &IndexerConfig::default()).await;
+    db.load_schema(schema.version().to_string()).await.unwrap();
+
+    let indexer_schema = IndexerSchema::new(
+        namespace,
+        identifier,
+        &schema,
+        DbType::Postgres,
+        ExecutionSource::Wasm,
+    )
+    .unwrap();
+
+    (db, indexer_schema)
+}
+
+/// Run a GraphQL query against the tables of an indexer, and return the records
+/// returned by each of the SQL queries that it's made of.
+pub async fn run_graphql_query(
+    pool: &IndexerConnectionPool,
+    schema: &IndexerSchema,
+    query: &str,
+) -> serde_json::Value {
+    let sql = GraphqlQueryBuilder::new(schema, query)
+        .unwrap()
+        .build()
+        .unwrap()
+        .as_sql(schema, DbType::Postgres)
+        .unwrap();
+
+    let mut conn = pool.acquire().await.unwrap();
+    let mut records = Vec::new();
+    for query in sql {
+        let rows = queries::run_query(&mut conn, query).await.unwrap();
+        records.extend(rows.as_array().unwrap().clone());
+    }
+
+    serde_json::Value::Array(records)
+}
+
+/// Blocks used to check that the native and WASM code paths of an indexer
+/// produce the same data.
+///
+/// Blocks 2, 3 and 4 each contain a transaction that logs a `Pung`, as the
+/// `fuel-indexer-test` contract does in `trigger_multiargs`. Blocks 3 and 4 also
+/// each contain a transaction that logs the same `AnotherSimpleEnum`.
+pub fn parity_block_fixtures() -> Vec<BlockData> {
+    let contract_id = fuel_types::ContractId::from([7u8; 32]);
+
+    let pungs = [
+        (
+            2,
+            Pung {
+                id: 1,
+                value: 54321,
+                is_pung: true,
+                pung_from: Identity::Address([1u8; 32].into()),
+            },
+        ),
+        (
+            3,
+            Pung {
+                id: 2,
+                value: 8675309,
+                is_pung: false,
+                pung_from: Identity::ContractId([2u8; 32].into()),
+            },
+        ),
+        (
+            4,
+            Pung {
+                id: 3,
+                value: 123,
+                is_pung: true,
+                pung_from: Identity::Address([3u8; 32].into()),
+            },
+        ),
+    ];
+
+    (1..=4u32)
+        .map(|height| {
+            let time = 1_700_000_000 + i64::from(height) * 60;
+
+            let mut transactions = pungs
+                .iter()
+                .filter(|(h, _)| *h == height)
+                .map(|(_, pung)| {
+                    let data = ABIEncoder::encode(&[pung.clone().into_token()])
+                        .unwrap()
+                        .resolve(0);
+                    // Log ID of `Pung` in the `fuel-indexer-test` contract ABI.
+                    let log_id = 6;
+                    TransactionData {
+                        transaction: Transaction::default(),
+                        status: TransactionStatus::Success {
+                            block: [height as u8; 32].into(),
+                            time: time as u64,
+                            program_state: None,
+                        },
+                        receipts: vec![Receipt::log_data(
+                            contract_id,
+                            0,
+                            log_id,
+                            0,
+                            0,
+                            0,
+                            data,
+                        )],
+                        id: [height as u8; 32].into(),
+                    }
+                })
+                .collect::<Vec<_>>();
+
+            if height >= 3 {
+                let data =
+                    ABIEncoder::encode(&[
+                        AnotherSimpleEnum::Call(SimpleEnum::One).into_token()
+                    ])
+                    .unwrap()
+                    .resolve(0);
+                // Log ID of `AnotherSimpleEnum` in the `fuel-indexer-test` contract ABI.
+                let log_id = 0;
+                transactions.push(TransactionData {
+                    transaction: Transaction::default(),
+                    status: TransactionStatus::Success {
+                        block: [height as u8; 32].into(),
+                        time: time as u64,
+                        program_state: None,
+                    },
+                    receipts: vec![Receipt::log_data(
+                        contract_id,
+                        0,
+                        log_id,
+                        0,
+                        0,
+                        0,
+                        data,
+                    )],
+                    id: [height as u8 + 100; 32].into(),
+                });
+            }
+
+            BlockData {
+                height,
+                id: [height as u8; 32].into(),
+                header: Header {
+                    id: [height as u8; 32].into(),
+                    da_height: 1,
+                    transactions_count: transactions.len() as u64,
+                    message_receipt_count: 0,
+                    transactions_root: [0u8; 32].into(),
+                    height,
+                    prev_root: [0u8; 32].into(),
+                    time,
+                    application_hash: [0u8; 32].into(),
+                    message_receipt_root: [0u8; 32].into(),
+                },
+                producer: None,
+                time,
+                consensus: Consensus::default(),
+                transactions,
+            }
+        })
+        .collect()
+}
+
 pub mod test_web {
     use crate::{defaults, fixtures::get_test_contract_id};
     use actix_service::ServiceFactory;
@@ -34,6 +34,12 @@ pub mod assets {
 
     pub const SIMPLE_WASM_WASM: &[u8] =
         include_bytes!("./../indexers/simple-wasm/simple_wasm.wasm");
+
+    pub const PARITY_WASM_MANIFEST: &str =
+        include_str!("./../indexers/parity/parity_wasm.yaml");
+
+    pub const PARITY_NATIVE_MANIFEST: &str =
+        include_str!("./../indexers/parity/parity_native.yaml");
 }
 
 pub mod defaults {
@@ -96,6 +102,11 @@ pub mod utils {
 
         manifest.set_abi(abi);
 
+        // Native indexers are compiled into the running binary, so there is no module path.
+        if let Module::Native = manifest.module() {
+            return;
+        }
+
         let module = Module::Wasm(
             manifest_dir
                 .parent()
@@ -1,8 +1,32 @@
-use fuel_indexer_tests::fixtures::{mock_request, setup_web_test_components};
+use async_graphql::{Executor, Request};
+use fuel_indexer::{Database, FtColumn};
+use fuel_indexer_database::{queries, IndexerConnectionPool};
+use fuel_indexer_graphql::{
+    dynamic::{build_dynamic_schema, build_federated_dynamic_schema, execute_query},
+    federation::execute_federated_query,
+    graphql::{GraphqlError, GraphqlQueryBuilder, QueryLimits},
+    subscriptions::SubscriptionExecutor,
+};
+use fuel_indexer_lib::utils::publish_entity_changes;
+use fuel_indexer_schema::db::tables::IndexerSchema;
+use fuel_indexer_tests::fixtures::{
+    mock_request, run_graphql_query, setup_indexer, setup_web_test_components,
+    TestPostgresDb,
+};
+use fuel_indexer_types::{scalar::UID, type_id};
 use fuel_indexer_utils::uid;
+use fuel_types::Bytes32;
+use futures::StreamExt;
 use hyper::header::CONTENT_TYPE;
-use serde_json::{Number, Value};
-use std::collections::HashMap;
+use serde_json::{json, Number, Value};
+use sqlx::Row;
+use std::{collections::HashMap, time::Duration};
+use tokio::time::timeout;
+
+/// Return the ID of the `n`th record written by a test.
+fn test_uid(n: u64) -> UID {
+    UID::new(format!("{n:064}")).unwrap()
+}
 
 #[actix_web::test]
 async fn test_entity_with_required_and_optional_fields() {
@@ -484,3 +508,901 @@ async fn test_aliasing_and_pagination() {
 
     test.server.abort();
 }
+
+#[actix_web::test]
+async fn test_database_searches_searchable_entities() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Post @entity @search(fields: ["title", "body"]) {
+    id: ID!
+    title: Charfield!
+    body: Charfield
+}
+"#,
+        "searchable",
+        "test",
+    )
+    .await;
+
+    let post_type_id = type_id("searchable_test", "Post");
+    let posts = [
+        (1, "Indexing blocks", Some("Indexers process every block.")),
+        (2, "Querying data", Some("Queries are written in GraphQL.")),
+        (3, "Release notes", None),
+    ];
+
+    db.start_transaction().await.unwrap();
+    for (n, title, body) in posts {
+        db.put_object(
+            post_type_id,
+            vec![
+                FtColumn::ID(Some(test_uid(n))),
+                FtColumn::Charfield(Some(title.to_string())),
+                FtColumn::Charfield(body.map(|b| b.to_string())),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    let search = |query: &'static str| {
+        let (pool, schema) = (&pool, &schema);
+        async move {
+            run_graphql_query(pool, schema, query)
+                .await
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|post| post["title"].as_str().unwrap().to_string())
+                .collect::<Vec<String>>()
+        }
+    };
+
+    // Words are matched by their stem, in any of the searchable fields.
+    assert_eq!(
+        search(r#"query { post(search: "indexer") { title } }"#).await,
+        vec!["Indexing blocks".to_string()]
+    );
+    assert_eq!(
+        search(r#"query { post(search: "query -indexing") { title } }"#).await,
+        vec!["Querying data".to_string()]
+    );
+    assert_eq!(
+        search(r#"query { post(search: "release") { title } }"#).await,
+        vec!["Release notes".to_string()]
+    );
+    assert!(search(r#"query { post(search: "wallet") { title } }"#)
+        .await
+        .is_empty());
+}
+
+#[actix_web::test]
+async fn test_querying_interface_returns_every_implementor() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+interface Event {
+    id: ID!
+    block: UInt4!
+}
+
+type Swap implements Event @entity {
+    id: ID!
+    block: UInt4!
+    amount: UInt8!
+}
+
+type Deposit implements Event @entity {
+    id: ID!
+    block: UInt4!
+}
+"#,
+        "events",
+        "test",
+    )
+    .await;
+
+    db.start_transaction().await.unwrap();
+    db.put_object(
+        type_id("events_test", "Swap"),
+        vec![
+            FtColumn::ID(Some(test_uid(1))),
+            FtColumn::UInt4(Some(10)),
+            FtColumn::UInt8(Some(500)),
+        ],
+        vec![],
+    )
+    .await
+    .unwrap();
+    db.put_object(
+        type_id("events_test", "Deposit"),
+        vec![FtColumn::ID(Some(test_uid(2))), FtColumn::UInt4(Some(20))],
+        vec![],
+    )
+    .await
+    .unwrap();
+    db.commit_transaction().await.unwrap();
+
+    // Interfaces and their implementors can be introspected.
+    assert!(build_dynamic_schema(&schema).is_ok());
+
+    assert_eq!(
+        run_graphql_query(
+            &pool,
+            &schema,
+            "query { event { block ... on Swap { amount } } }"
+        )
+        .await,
+        json!([
+            { "__typename": "Deposit", "block": 20 },
+            { "__typename": "Swap", "block": 10, "amount": 500 },
+        ])
+    );
+}
+
+#[actix_web::test]
+async fn test_connection_pages_through_results_with_cursors() {
+    async fn run(
+        pool: &IndexerConnectionPool,
+        schema: &IndexerSchema,
+        query: &str,
+    ) -> Value {
+        run_graphql_query(pool, schema, query).await[0]["swapConnection"].clone()
+    }
+
+    let ids = |connection: &Value| {
+        connection["edges"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|edge| edge["node"]["id"].as_str().unwrap()[63..].to_string())
+            .collect::<Vec<String>>()
+    };
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Swap @entity {
+    id: ID!
+    amount: UInt8
+}
+"#,
+        "cursors",
+        "test",
+    )
+    .await;
+
+    db.start_transaction().await.unwrap();
+    for (n, amount) in [
+        (1, Some(20)),
+        (2, Some(10)),
+        (3, Some(20)),
+        (4, None),
+        (5, Some(30)),
+    ] {
+        db.put_object(
+            type_id("cursors_test", "Swap"),
+            vec![FtColumn::ID(Some(test_uid(n))), FtColumn::UInt8(amount)],
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    // Connections can be introspected.
+    assert!(build_dynamic_schema(&schema).is_ok());
+
+    // Ties are broken by `ID`, and `NULL`s come last in ascending order.
+    let mut pages = Vec::new();
+    let mut after = String::new();
+    loop {
+        let page = run(
+            &pool,
+            &schema,
+            &format!(
+                r#"query {{ swapConnection(order: {{ amount: asc }}, first: 2{after}) {{ edges {{ node {{ id }} cursor }} pageInfo {{ hasNextPage endCursor }} }} }}"#
+            ),
+        )
+        .await;
+        pages.push(ids(&page));
+
+        if !page["pageInfo"]["hasNextPage"].as_bool().unwrap() {
+            assert!(page["pageInfo"]["hasPreviousPage"].as_bool().unwrap());
+            break;
+        }
+        after = format!(
+            r#", after: "{}""#,
+            page["pageInfo"]["endCursor"].as_str().unwrap()
+        );
+    }
+    assert_eq!(pages, vec![vec!["2", "1"], vec!["3", "5"], vec!["4"]]);
+
+    // Pages can also be counted back from a cursor.
+    let page = run(
+        &pool,
+        &schema,
+        r#"query { swapConnection(order: { amount: asc }) { edges { node { id } cursor } } }"#,
+    )
+    .await;
+    let cursor = page["edges"][3]["cursor"].as_str().unwrap();
+
+    let page = run(
+        &pool,
+        &schema,
+        &format!(
+            r#"query {{ swapConnection(order: {{ amount: asc }}, last: 2, before: "{cursor}") {{ edges {{ node {{ id }} }} pageInfo {{ hasPreviousPage }} }} }}"#
+        ),
+    )
+    .await;
+    assert_eq!(ids(&page), vec!["1", "3"]);
+    assert!(page["pageInfo"]["hasPreviousPage"].as_bool().unwrap());
+}
+
+#[actix_web::test]
+async fn test_aggregates_are_computed_per_group() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Swap @entity {
+    id: ID!
+    pool: Charfield!
+    amount: UInt8!
+}
+"#,
+        "aggregates",
+        "test",
+    )
+    .await;
+
+    db.start_transaction().await.unwrap();
+    for (n, pool, amount) in
+        [(1, "eth", 10), (2, "eth", 30), (3, "btc", 5), (4, "eth", 2)]
+    {
+        db.put_object(
+            type_id("aggregates_test", "Swap"),
+            vec![
+                FtColumn::ID(Some(test_uid(n))),
+                FtColumn::Charfield(Some(pool.to_string())),
+                FtColumn::UInt8(Some(amount)),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    // Aggregates can be introspected.
+    assert!(build_dynamic_schema(&schema).is_ok());
+
+    assert_eq!(
+        run_graphql_query(
+            &pool,
+            &schema,
+            r#"query { swapAggregate(filter: { amount: { gt: 2 } }, groupBy: [pool]) { keys { pool } count sum { amount } min { amount } max { amount } } }"#,
+        )
+        .await,
+        json!([
+            {
+                "keys": { "pool": "btc" },
+                "count": 1,
+                "sum": { "amount": 5 },
+                "min": { "amount": 5 },
+                "max": { "amount": 5 }
+            },
+            {
+                "keys": { "pool": "eth" },
+                "count": 2,
+                "sum": { "amount": 40 },
+                "min": { "amount": 10 },
+                "max": { "amount": 30 }
+            }
+        ])
+    );
+}
+
+#[actix_web::test]
+async fn test_subscriptions_receive_committed_changes() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Swap @entity {
+    id: ID!
+    amount: UInt8!
+}
+"#,
+        "subscriptions",
+        "test",
+    )
+    .await;
+
+    let executor = SubscriptionExecutor::new(schema, pool.clone());
+    let mut stream = executor.execute_stream(
+        Request::new(
+            "subscription { swap(filter: { amount: { gt: 10 } }) { id amount } }",
+        ),
+        None,
+    );
+
+    // Each commit is published in the same way as by an executor.
+    async fn commit(db: &mut Database, swaps: Vec<(u64, u64)>) {
+        db.start_transaction().await.unwrap();
+        for (n, amount) in swaps {
+            db.put_object(
+                type_id("subscriptions_test", "Swap"),
+                vec![
+                    FtColumn::ID(Some(test_uid(n))),
+                    FtColumn::UInt8(Some(amount)),
+                ],
+                vec![],
+            )
+            .await
+            .unwrap();
+        }
+        db.commit_transaction().await.unwrap();
+        publish_entity_changes(db.take_entity_changes().unwrap());
+    }
+
+    // Changes that don't match the subscription's filter aren't sent.
+    commit(&mut db, vec![(1, 5)]).await;
+    commit(&mut db, vec![(2, 20), (3, 1)]).await;
+    let response = timeout(Duration::from_secs(10), stream.next())
+        .await
+        .unwrap();
+    assert_eq!(
+        response.unwrap().data.into_json().unwrap(),
+        json!({ "swap": [{ "id": format!("{:064}", 2), "amount": 20 }] })
+    );
+
+    // Updated records are sent with their new values.
+    commit(&mut db, vec![(1, 50)]).await;
+    let response = timeout(Duration::from_secs(10), stream.next())
+        .await
+        .unwrap();
+    assert_eq!(
+        response.unwrap().data.into_json().unwrap(),
+        json!({ "swap": [{ "id": format!("{:064}", 1), "amount": 50 }] })
+    );
+}
+
+#[actix_web::test]
+async fn test_filters_apply_to_related_entities() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Token @entity {
+    id: ID!
+    symbol: Charfield!
+}
+
+type Swap @entity {
+    id: ID!
+    token: Token!
+}
+
+type Pool @entity {
+    id: ID!
+    tokens: [Token!]
+}
+"#,
+        "related",
+        "test",
+    )
+    .await;
+
+    db.start_transaction().await.unwrap();
+    for (n, symbol) in [(1, "ETH"), (2, "BTC")] {
+        db.put_object(
+            type_id("related_test", "Token"),
+            vec![
+                FtColumn::ID(Some(test_uid(n))),
+                FtColumn::Charfield(Some(symbol.to_string())),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+    for (n, token) in [(3, 1), (4, 2)] {
+        db.put_object(
+            type_id("related_test", "Swap"),
+            vec![
+                FtColumn::ID(Some(test_uid(n))),
+                FtColumn::UID(Some(test_uid(token))),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+    for (n, tokens) in [(5, vec![1]), (6, vec![1, 2]), (7, vec![])] {
+        db.put_object(
+            type_id("related_test", "Pool"),
+            vec![
+                FtColumn::ID(Some(test_uid(n))),
+                FtColumn::Array(Some(
+                    tokens
+                        .iter()
+                        .map(|t| FtColumn::ID(Some(test_uid(*t))))
+                        .collect(),
+                )),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+        db.put_many_to_many_record(
+            tokens
+                .iter()
+                .map(|t| {
+                    format!(
+                        "INSERT INTO related_test.pools_tokens (pool_id, token_id) VALUES ('{}', '{}')",
+                        test_uid(n),
+                        test_uid(*t)
+                    )
+                })
+                .collect(),
+        )
+        .await
+        .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    // Filters on related entities can be introspected.
+    assert!(build_dynamic_schema(&schema).is_ok());
+
+    let ids = |rows: Value| {
+        rows.as_array()
+            .unwrap()
+            .iter()
+            .map(|row| row["id"].as_str().unwrap().to_string())
+            .collect::<Vec<String>>()
+    };
+
+    for (query, expected) in [
+        (
+            r#"query { swap(filter: { token: { symbol: { equals: "ETH" } } }, order: { id: asc }) { id } }"#,
+            vec![3],
+        ),
+        (
+            r#"query { swap(filter: { not: { token: { symbol: { equals: "ETH" } } } }, order: { id: asc }) { id } }"#,
+            vec![4],
+        ),
+        (
+            r#"query { pool(filter: { tokens: { some: { symbol: { equals: "ETH" } } } }, order: { id: asc }) { id } }"#,
+            vec![5, 6],
+        ),
+        (
+            r#"query { pool(filter: { tokens: { every: { symbol: { equals: "ETH" } } } }, order: { id: asc }) { id } }"#,
+            vec![5, 7],
+        ),
+        (
+            r#"query { pool(filter: { tokens: { none: { symbol: { equals: "ETH" } } } }, order: { id: asc }) { id } }"#,
+            vec![7],
+        ),
+    ] {
+        assert_eq!(
+            ids(run_graphql_query(&pool, &schema, query).await),
+            expected
+                .into_iter()
+                .map(|n| test_uid(n).to_string())
+                .collect::<Vec<String>>()
+        );
+    }
+}
+
+#[actix_web::test]
+async fn test_string_patterns_match_text_fields() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Token @entity {
+    id: ID!
+    symbol: Charfield! @indexed(type: Trigram)
+    supply: UInt32
+}
+"#,
+        "patterns",
+        "test",
+    )
+    .await;
+
+    // Pattern matches on the field can use a trigram index.
+    let index = sqlx::query(
+        "SELECT indexdef FROM pg_indexes WHERE schemaname = 'patterns_test' AND indexname = 'token_symbol_idx'",
+    )
+    .fetch_one(&test_db.pool)
+    .await
+    .unwrap();
+    assert!(index.get::<String, _>(0).contains("gin_trgm_ops"));
+
+    db.start_transaction().await.unwrap();
+    for (n, symbol) in [(1, "ETH"), (2, "WETH"), (3, "eth_2"), (4, "50%_off")] {
+        db.put_object(
+            type_id("patterns_test", "Token"),
+            vec![
+                FtColumn::ID(Some(test_uid(n))),
+                FtColumn::Charfield(Some(symbol.to_string())),
+                FtColumn::UInt32(None),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    // Pattern operators can be introspected.
+    assert!(build_dynamic_schema(&schema).is_ok());
+
+    // Numeric fields passed as strings can't be matched against patterns.
+    let e = GraphqlQueryBuilder::new(
+        &schema,
+        r#"query { token(filter: { supply: { startsWith: "1" } }) { id } }"#,
+    )
+    .and_then(|builder| builder.build())
+    .unwrap_err();
+    assert!(matches!(e, GraphqlError::UnsupportedFilterOperation(_)));
+
+    for (filter, expected) in [
+        (r#"{ symbol: { startsWith: "W" } }"#, vec!["WETH"]),
+        (r#"{ symbol: { contains: "ETH" } }"#, vec!["ETH", "WETH"]),
+        (
+            r#"{ symbol: { ilike: "%eth%" } }"#,
+            vec!["ETH", "WETH", "eth_2"],
+        ),
+        (r#"{ symbol: { like: "_ETH" } }"#, vec!["WETH"]),
+        // Wildcards and quotes in values are matched literally.
+        (r#"{ symbol: { contains: "%" } }"#, vec!["50%_off"]),
+        (r#"{ symbol: { endsWith: "_2" } }"#, vec!["eth_2"]),
+        (r#"{ symbol: { startsWith: "ETH' OR 'a' = 'a" } }"#, vec![]),
+        (
+            r#"{ not: { symbol: { contains: "ETH" } } }"#,
+            vec!["eth_2", "50%_off"],
+        ),
+    ] {
+        let symbols = run_graphql_query(
+            &pool,
+            &schema,
+            &format!(
+                "query {{ token(filter: {filter}, order: {{ id: asc }}) {{ symbol }} }}"
+            ),
+        )
+        .await;
+        assert_eq!(
+            symbols
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|row| row["symbol"].as_str().unwrap())
+                .collect::<Vec<&str>>(),
+            expected,
+            "{filter}"
+        );
+    }
+}
+
+#[actix_web::test]
+async fn test_query_limits_bound_graphql_queries() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Token @entity {
+    id: ID!
+    symbol: Charfield!
+}
+"#,
+        "limits",
+        "test",
+    )
+    .await;
+
+    db.start_transaction().await.unwrap();
+    for n in 1..=5 {
+        db.put_object(
+            type_id("limits_test", "Token"),
+            vec![
+                FtColumn::ID(Some(test_uid(n))),
+                FtColumn::Charfield(Some(format!("TKN{n}"))),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    let dynamic_schema = build_dynamic_schema(&schema).unwrap();
+
+    let limits = QueryLimits {
+        default_limit: Some(2),
+        max_limit: Some(3),
+        timeout: Some(1000),
+        ..QueryLimits::default()
+    };
+    let run = |query: &str| {
+        execute_query(
+            Request::new(query),
+            dynamic_schema.clone(),
+            query.to_string(),
+            pool.clone(),
+            &schema,
+            limits.clone(),
+        )
+    };
+
+    // Queries that don't request a number of records return the default number.
+    let tokens = run("query { token { id } }").await.unwrap();
+    assert_eq!(tokens.as_array().unwrap().len(), 2);
+
+    let e = run("query { token(order: { id: asc }, first: 5) { id } }")
+        .await
+        .unwrap_err();
+    assert!(matches!(e, GraphqlError::MaxLimitExceeded(5, 3)));
+    assert_eq!(e.code(), Some("MAX_LIMIT_EXCEEDED"));
+
+    // A query that's blocked for longer than the timeout is cancelled.
+    let mut lock_conn = pool.acquire().await.unwrap();
+    queries::start_transaction(&mut lock_conn).await.unwrap();
+    queries::execute_query(
+        &mut lock_conn,
+        "LOCK TABLE limits_test.token IN ACCESS EXCLUSIVE MODE".to_string(),
+    )
+    .await
+    .unwrap();
+
+    let e = run("query { token { id } }").await.unwrap_err();
+    assert!(matches!(e, GraphqlError::QueryTimeout(1000)));
+
+    queries::revert_transaction(&mut lock_conn).await.unwrap();
+
+    let tokens = run("query { token { id } }").await.unwrap();
+    assert_eq!(tokens.as_array().unwrap().len(), 2);
+}
+
+#[actix_web::test]
+async fn test_nullable_relations_are_returned_as_null() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Memo @entity @versioned {
+    id: ID!
+    text: Charfield!
+}
+
+type Payment @entity {
+    id: ID!
+    memo: Memo
+}
+"#,
+        "nullable",
+        "test",
+    )
+    .await;
+
+    // The memo has a version per block, only the latest of which is returned.
+    for (height, text) in [(1, "draft"), (2, "rent")] {
+        db.set_block_height(height);
+        db.start_transaction().await.unwrap();
+        db.put_object(
+            type_id("nullable_test", "Memo"),
+            vec![
+                FtColumn::ID(Some(test_uid(1))),
+                FtColumn::Charfield(Some(text.to_string())),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+        db.commit_transaction().await.unwrap();
+    }
+
+    db.start_transaction().await.unwrap();
+    for (n, memo) in [(2, Some(test_uid(1))), (3, None)] {
+        db.put_object(
+            type_id("nullable_test", "Payment"),
+            vec![FtColumn::ID(Some(test_uid(n))), FtColumn::UID(memo)],
+            vec![],
+        )
+        .await
+        .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    // Payments without a memo are kept, with a null memo.
+    assert_eq!(
+        run_graphql_query(
+            &pool,
+            &schema,
+            "query { payment(order: { id: asc }) { id memo { text } } }"
+        )
+        .await,
+        json!([
+            { "id": test_uid(2).to_string(), "memo": { "text": "rent" } },
+            { "id": test_uid(3).to_string(), "memo": null },
+        ])
+    );
+}
+
+#[actix_web::test]
+async fn test_meta_query_reports_indexer_status() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Account @entity {
+    id: ID!
+    balance: UInt8!
+}
+"#,
+        "meta",
+        "test",
+    )
+    .await;
+
+    let mut conn = pool.acquire().await.unwrap();
+    queries::register_indexer(&mut conn, "meta", "test", None)
+        .await
+        .unwrap();
+
+    let query = "query { _meta { block { height } indexer { namespace version errors: hasIndexingErrors } } }";
+
+    // No block has been committed yet.
+    assert_eq!(
+        run_graphql_query(&pool, &schema, query).await,
+        json!([{
+            "block": null,
+            "indexer": {
+                "namespace": "meta",
+                "version": schema.parsed().version(),
+                "errors": false,
+            },
+        }])
+    );
+
+    for height in [1, 2] {
+        db.start_transaction().await.unwrap();
+        db.put_object(
+            type_id("meta_test", "IndexMetadataEntity"),
+            vec![
+                FtColumn::ID(Some(test_uid(height as u64))),
+                FtColumn::UInt8(Some(1_700_000_000 + height as u64)),
+                FtColumn::UInt4(Some(height)),
+                FtColumn::Bytes32(Some(Bytes32::from([height as u8; 32]))),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+        db.commit_transaction().await.unwrap();
+    }
+    queries::record_indexing_error(&mut conn, "meta", "test", "Indexer failed.")
+        .await
+        .unwrap();
+
+    assert_eq!(
+        run_graphql_query(&pool, &schema, query).await,
+        json!([{
+            "block": { "height": 2 },
+            "indexer": {
+                "namespace": "meta",
+                "version": schema.parsed().version(),
+                "errors": true,
+            },
+        }])
+    );
+}
+
+#[actix_web::test]
+async fn test_federated_query_selects_from_each_indexer() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+
+    // Both indexers have an entity of the same name, each in its own schema.
+    let mut schemas = Vec::new();
+    for (identifier, balance) in [("left", 1), ("right", 2)] {
+        let (mut db, schema) = setup_indexer(
+            &pool,
+            r#"
+type Account @entity {
+    id: ID!
+    balance: UInt8!
+}
+"#,
+            "fed",
+            identifier,
+        )
+        .await;
+
+        db.start_transaction().await.unwrap();
+        db.put_object(
+            type_id(&format!("fed_{identifier}"), "Account"),
+            vec![
+                FtColumn::ID(Some(test_uid(balance))),
+                FtColumn::UInt8(Some(balance)),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+        db.commit_transaction().await.unwrap();
+
+        schemas.push(schema);
+    }
+
+    let dynamic_schema = build_federated_dynamic_schema([
+        ("fed_left", &schemas[0]),
+        ("fed_right", &schemas[1]),
+    ])
+    .unwrap();
+    let schemas = HashMap::from([
+        ("fed_left".to_string(), &schemas[0]),
+        ("fed_right".to_string(), &schemas[1]),
+    ]);
+    let execute = |query: &str| {
+        execute_federated_query(
+            Request::new(query),
+            &dynamic_schema,
+            &schemas,
+            &pool,
+            QueryLimits::default(),
+        )
+    };
+
+    // The types of each indexer are prefixed with the name of its root field.
+    let result = execute(
+        r#"
+query {
+    fed_left { account { ...leftBalance } }
+    other: fed_right { account { ... on fed_right_Account { balance } } }
+    __type(name: "fed_left_Account") { name }
+}
+
+fragment leftBalance on fed_left_Account { balance }
+"#,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(
+        result,
+        json!({
+            "fed_left": [{ "balance": 1 }],
+            "other": [{ "balance": 2 }],
+            "__type": { "name": "fed_left_Account" },
+        })
+    );
+
+    // Queries are validated against the federated schema.
+    assert!(matches!(
+        execute("query { fed_left { account { ...balance } } } fragment balance on Account { balance }").await,
+        Err(GraphqlError::InvalidQuery(_))
+    ));
+    assert!(matches!(
+        execute("query { fed_other { account { balance } } }").await,
+        Err(GraphqlError::InvalidQuery(_))
+    ));
+}
@@ -4,6 +4,9 @@ mod graphql_server;
 #[cfg(all(feature = "postgres", not(feature = "trybuild")))]
 mod indexing;
 
+#[cfg(all(feature = "postgres", not(feature = "trybuild")))]
+mod parity;
+
 #[cfg(not(feature = "trybuild"))]
 mod service;
 
@@ -0,0 +1,271 @@
+use fuel_indexer::{Executor, IndexerConfig, NativeIndexExecutor, WasmIndexExecutor};
+use fuel_indexer_database::IndexerConnectionPool;
+use fuel_indexer_lib::{
+    config::DatabaseConfig,
+    manifest::{Manifest, Module},
+};
+use fuel_indexer_tests::{
+    assets,
+    fixtures::{parity_block_fixtures, register_indexer_schema, TestPostgresDb},
+    utils::update_test_manifest_asset_paths,
+};
+use sqlx::{PgConnection, Row};
+use std::str::FromStr;
+
+// The native build of the parity indexer. The WASM build of the same definition
+// is the `parity-wasm` module.
+#[allow(dead_code)]
+mod native {
+    extern crate alloc;
+    use fuel_indexer_utils::prelude::*;
+
+    include!("../indexers/parity/parity_indexer.rs");
+
+    parity_indexer!(
+        "packages/fuel-indexer-tests/indexers/parity/parity_native.yaml",
+        { async },
+        { .await }
+    );
+
+    pub(super) async fn handle_blocks(
+        blocks: Vec<BlockData>,
+        db_conn: Arc<Mutex<Database>>,
+    ) -> IndexerResult<()> {
+        handle_events(blocks, db_conn).await
+    }
+}
+
+/// Return the names of the tables in a schema, in order.
+async fn table_names(conn: &mut PgConnection, schema: &str) -> Vec<String> {
+    sqlx::query(
+        "SELECT table_name FROM information_schema.tables WHERE table_schema = $1 ORDER BY table_name",
+    )
+    .bind(schema)
+    .fetch_all(conn)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|row| row.get::<String, usize>(0))
+    .collect()
+}
+
+/// Return the rows of a table as JSON, in order.
+async fn table_rows(conn: &mut PgConnection, schema: &str, table: &str) -> Vec<String> {
+    sqlx::query(&format!(
+        "SELECT to_jsonb(t)::text FROM {schema}.{table} t ORDER BY 1"
+    ))
+    .fetch_all(conn)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|row| row.get::<String, usize>(0))
+    .collect()
+}
+
+#[actix_web::test]
+async fn test_native_and_wasm_executors_produce_identical_tables() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let config = IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        ..Default::default()
+    };
+
+    let mut wasm_manifest = Manifest::try_from(assets::PARITY_WASM_MANIFEST).unwrap();
+    update_test_manifest_asset_paths(&mut wasm_manifest);
+
+    let mut native_manifest = Manifest::try_from(assets::PARITY_NATIVE_MANIFEST).unwrap();
+    update_test_manifest_asset_paths(&mut native_manifest);
+
+    let schema_version = register_indexer_schema(&pool, &wasm_manifest)
+        .await
+        .unwrap();
+    register_indexer_schema(&pool, &native_manifest)
+        .await
+        .unwrap();
+
+    let wasm_bytes = match wasm_manifest.module() {
+        Module::Wasm(module) => std::fs::read(module).unwrap(),
+        Module::Native => unreachable!("The parity WASM manifest uses a WASM module."),
+    };
+
+    let mut wasm_executor = WasmIndexExecutor::create(
+        &config,
+        &wasm_manifest,
+        pool.clone(),
+        schema_version,
+        wasm_bytes,
+    )
+    .await
+    .unwrap();
+
+    let mut native_executor = NativeIndexExecutor::create(
+        &config,
+        &native_manifest,
+        pool.clone(),
+        native::handle_blocks,
+    )
+    .await
+    .unwrap();
+
+    // Blocks are handled in two batches, so that entities saved by one call
+    // to `handle_events` are loaded by the next.
+    let blocks = parity_block_fixtures();
+    for batch in blocks.chunks(2) {
+        wasm_executor.handle_events(batch.to_vec()).await.unwrap();
+        native_executor.handle_events(batch.to_vec()).await.unwrap();
+    }
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+
+    let wasm_tables = table_names(&mut conn, "parity_wasm").await;
+    let native_tables = table_names(&mut conn, "parity_native").await;
+    assert!(!wasm_tables.is_empty());
+    assert_eq!(wasm_tables, native_tables);
+
+    for table in wasm_tables {
+        let wasm_rows = table_rows(&mut conn, "parity_wasm", &table).await;
+        let native_rows = table_rows(&mut conn, "parity_native", &table).await;
+        assert_eq!(wasm_rows, native_rows, "Table `{table}` differs.");
+    }
+
+    let rows = table_rows(&mut conn, "parity_wasm", "paritypung").await;
+    assert_eq!(rows.len(), 3);
+
+    // Blocks are a minute apart, so the first and third blocks each start a new window.
+    let rows = table_rows(&mut conn, "parity_wasm", "paritysnapshot").await;
+    assert_eq!(rows.len(), 2);
+}
+
+#[actix_web::test]
+async fn test_enum_events_with_the_same_value_are_saved_separately() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let config = IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        ..Default::default()
+    };
+
+    let mut manifest = Manifest::try_from(assets::PARITY_NATIVE_MANIFEST).unwrap();
+    update_test_manifest_asset_paths(&mut manifest);
+    register_indexer_schema(&pool, &manifest).await.unwrap();
+
+    let mut executor =
+        NativeIndexExecutor::create(&config, &manifest, pool, native::handle_blocks)
+            .await
+            .unwrap();
+    executor
+        .handle_events(parity_block_fixtures())
+        .await
+        .unwrap();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let rows = sqlx::query(
+        "SELECT COUNT(*), COUNT(DISTINCT id) FROM parity_native.anothersimpleenumentity",
+    )
+    .fetch_one(&mut *conn)
+    .await
+    .unwrap();
+
+    // The same event is logged in blocks 3 and 4.
+    assert_eq!(rows.get::<i64, usize>(0), 2);
+    assert_eq!(rows.get::<i64, usize>(1), 2);
+}
+
+#[actix_web::test]
+async fn test_versioned_entities_are_versioned_at_the_height_of_each_block() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let config = IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        ..Default::default()
+    };
+
+    let mut manifest = Manifest::try_from(assets::PARITY_NATIVE_MANIFEST).unwrap();
+    update_test_manifest_asset_paths(&mut manifest);
+    register_indexer_schema(&pool, &manifest).await.unwrap();
+
+    // All blocks are handled in a single batch.
+    let mut executor =
+        NativeIndexExecutor::create(&config, &manifest, pool, native::handle_blocks)
+            .await
+            .unwrap();
+    executor
+        .handle_events(parity_block_fixtures())
+        .await
+        .unwrap();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let versions = sqlx::query(
+        "SELECT height, lower(_block_range), upper(_block_range)
+        FROM parity_native.paritytip ORDER BY lower(_block_range)",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|row| (row.get(0), row.get(1), row.get(2)))
+    .collect::<Vec<(i32, i64, Option<i64>)>>();
+
+    assert_eq!(
+        versions,
+        vec![
+            (1, 1, Some(2)),
+            (2, 2, Some(3)),
+            (3, 3, Some(4)),
+            (4, 4, None)
+        ]
+    );
+}
+
+#[actix_web::test]
+async fn test_arbitrary_precision_numbers_are_saved_exactly() {
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let config = IndexerConfig {
+        database: DatabaseConfig::from_str(&test_db.url).unwrap(),
+        ..Default::default()
+    };
+
+    let mut manifest = Manifest::try_from(assets::PARITY_NATIVE_MANIFEST).unwrap();
+    update_test_manifest_asset_paths(&mut manifest);
+    register_indexer_schema(&pool, &manifest).await.unwrap();
+
+    let mut executor =
+        NativeIndexExecutor::create(&config, &manifest, pool, native::handle_blocks)
+            .await
+            .unwrap();
+    executor
+        .handle_events(parity_block_fixtures())
+        .await
+        .unwrap();
+
+    let mut conn = test_db.pool.acquire().await.unwrap();
+    let supplies = sqlx::query(
+        "SELECT s.total::text, s.share::text FROM parity_native.paritysupply AS s ORDER BY s.total",
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|row| (row.get(0), row.get(1)))
+    .collect::<Vec<(String, String)>>();
+
+    assert_eq!(
+        supplies,
+        vec![
+            (
+                "41854731131275431005995076714107490009091".to_string(),
+                "0.000000000123".to_string()
+            ),
+            (
+                "18484478453512298273793972050301081014501377".to_string(),
+                "0.000000054321".to_string()
+            ),
+            (
+                "2952054680290519740529984902224285650758139906".to_string(),
+                "0.000008675309".to_string()
+            ),
+        ]
+    );
+}
@@ -210,3 +210,529 @@ async fn test_wasm_executor_error_codes() {
         ),
     }
 }
+
+#[tokio::test]
+async fn test_schema_manager_applies_additive_schema_changes_on_redeploy() {
+    use fuel_indexer_database::{queries, IndexerConnectionPool};
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+    use fuel_indexer_schema::db::{manager::SchemaManager, IndexerSchemaDbError};
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let manager = SchemaManager::new(pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+
+    let prev = GraphQLSchema::new(
+        r#"
+type Account @entity {
+    id: ID!
+    address: Address!
+}
+"#
+        .to_string(),
+    );
+
+    manager
+        .new_schema("migration", "test", prev, ExecutionSource::Wasm, &mut conn)
+        .await
+        .unwrap();
+
+    let next = GraphQLSchema::new(
+        r#"
+type Account @entity {
+    id: ID!
+    label: Charfield
+    address: Address! @indexed
+}
+
+type Wallet @entity {
+    id: ID!
+    account: Account!
+}
+"#
+        .to_string(),
+    );
+    let version = next.version().to_string();
+
+    manager
+        .new_schema("migration", "test", next, ExecutionSource::Wasm, &mut conn)
+        .await
+        .unwrap();
+
+    let columns = sqlx::query(
+        "SELECT column_name FROM information_schema.columns
+        WHERE table_schema = 'migration_test' AND table_name = 'account'
+        ORDER BY column_name",
+    )
+    .fetch_all(&test_db.pool)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|row| row.get::<String, usize>(0))
+    .collect::<Vec<String>>();
+    assert_eq!(columns, vec!["address", "id", "label", "object"]);
+
+    // The registered columns follow the order of the fields in the new schema.
+    let registered =
+        queries::columns_get_schema(&mut conn, "migration", "test", &version)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| format!("{}.{}", c.table_name, c.column_name))
+            .collect::<Vec<String>>();
+    assert!(registered.windows(4).any(|w| w
+        == [
+            "account.id",
+            "account.label",
+            "account.address",
+            "account.object"
+        ]));
+    assert!(registered.contains(&"wallet.account".to_string()));
+
+    let breaking = GraphQLSchema::new(
+        r#"
+type Account @entity {
+    id: ID!
+    label: Charfield
+}
+"#
+        .to_string(),
+    );
+
+    match manager
+        .new_schema(
+            "migration",
+            "test",
+            breaking,
+            ExecutionSource::Wasm,
+            &mut conn,
+        )
+        .await
+    {
+        Err(IndexerSchemaDbError::BreakingSchemaChange(report)) => {
+            assert!(report.contains("Field `Account.address` was removed."));
+            assert!(report.contains("Type `Wallet` was removed."));
+        }
+        _ => panic!("Expected breaking schema changes to be refused."),
+    }
+}
+
+#[tokio::test]
+async fn test_schema_manager_promotes_shadow_schema() {
+    use fuel_indexer_database::{queries, IndexerConnectionPool};
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+    use fuel_indexer_schema::db::manager::SchemaManager;
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let manager = SchemaManager::new(pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+
+    let table_columns = |schema: &'static str| {
+        let pool = test_db.pool.clone();
+        async move {
+            sqlx::query(
+                "SELECT column_name FROM information_schema.columns
+                WHERE table_schema = $1 AND table_name = 'account'
+                ORDER BY column_name",
+            )
+            .bind(schema)
+            .fetch_all(&pool)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|row| row.get::<String, usize>(0))
+            .collect::<Vec<String>>()
+        }
+    };
+
+    let prev = GraphQLSchema::new(
+        r#"
+type Account @entity {
+    id: ID!
+    address: Address!
+}
+"#
+        .to_string(),
+    );
+
+    manager
+        .new_schema("shadow", "test", prev, ExecutionSource::Wasm, &mut conn)
+        .await
+        .unwrap();
+
+    let next = GraphQLSchema::new(
+        r#"
+type Account @entity {
+    id: ID!
+    label: Charfield!
+}
+"#
+        .to_string(),
+    );
+
+    let diff = manager
+        .schema_diff("shadow", "test", &next, ExecutionSource::Wasm, &mut conn)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(diff.is_breaking());
+
+    manager
+        .new_shadow_schema("shadow", "test", &next, ExecutionSource::Wasm, &mut conn)
+        .await
+        .unwrap();
+
+    // The live schema is untouched until the shadow schema is promoted.
+    assert_eq!(
+        table_columns("shadow_test").await,
+        vec!["address", "id", "object"]
+    );
+    assert_eq!(
+        table_columns("shadow_test__shadow").await,
+        vec!["id", "label", "object"]
+    );
+
+    let retired = manager
+        .promote_shadow_schema("shadow", "test", &next, ExecutionSource::Wasm, &mut conn)
+        .await
+        .unwrap();
+
+    assert_eq!(
+        table_columns("shadow_test").await,
+        vec!["id", "label", "object"]
+    );
+    assert!(table_columns("shadow_test__shadow").await.is_empty());
+
+    let root = queries::graph_root_latest(&mut conn, "shadow", "test")
+        .await
+        .unwrap();
+    assert_eq!(root.version, next.version().to_string());
+
+    let registered =
+        queries::columns_get_schema(&mut conn, "shadow", "test", next.version())
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|c| format!("{}.{}", c.table_name, c.column_name))
+            .collect::<Vec<String>>();
+    assert!(registered.contains(&"account.label".to_string()));
+    assert!(!registered.contains(&"account.address".to_string()));
+
+    queries::retire_schema(&mut conn, &retired, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        queries::expired_retired_schemas(&mut conn).await.unwrap(),
+        vec![retired.clone()]
+    );
+
+    queries::drop_retired_schema(&mut conn, &retired)
+        .await
+        .unwrap();
+    assert!(queries::expired_retired_schemas(&mut conn)
+        .await
+        .unwrap()
+        .is_empty());
+
+    let remaining = sqlx::query(
+        "SELECT COUNT(*) FROM information_schema.schemata WHERE schema_name = $1",
+    )
+    .bind(&retired)
+    .fetch_one(&test_db.pool)
+    .await
+    .unwrap()
+    .get::<i64, usize>(0);
+    assert_eq!(remaining, 0);
+}
+
+#[tokio::test]
+async fn test_shadow_indexer_has_no_height_until_a_block_is_committed() {
+    use fuel_indexer::FtColumn;
+    use fuel_indexer_database::{queries, IndexerConnectionPool};
+    use fuel_indexer_tests::fixtures::setup_indexer;
+    use fuel_indexer_types::{scalar::UID, type_id};
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, _) = setup_indexer(
+        &pool,
+        r#"
+type Account @entity {
+    id: ID!
+    balance: UInt8!
+}
+"#,
+        "heights",
+        "test",
+    )
+    .await;
+
+    // An indexer that hasn't indexed anything can't be promoted over another.
+    let mut conn = pool.acquire().await.unwrap();
+    assert_eq!(
+        queries::last_committed_block_height(&mut conn, "heights", "test")
+            .await
+            .unwrap(),
+        None
+    );
+    assert_eq!(
+        queries::last_block_height_for_indexer(&mut conn, "heights", "test")
+            .await
+            .unwrap(),
+        1
+    );
+
+    db.start_transaction().await.unwrap();
+    db.put_object(
+        type_id("heights_test", "IndexMetadataEntity"),
+        vec![
+            FtColumn::ID(Some(UID::new(format!("{:064}", 1)).unwrap())),
+            FtColumn::UInt8(Some(1_700_000_000)),
+            FtColumn::UInt4(Some(1)),
+            FtColumn::Bytes32(Some(Bytes32::default())),
+        ],
+        vec![],
+    )
+    .await
+    .unwrap();
+    db.commit_transaction().await.unwrap();
+
+    assert_eq!(
+        queries::last_committed_block_height(&mut conn, "heights", "test")
+            .await
+            .unwrap(),
+        Some(1)
+    );
+}
+
+#[tokio::test]
+async fn test_database_keeps_versions_of_versioned_entities() {
+    use fuel_indexer::FtColumn;
+    use fuel_indexer_database::IndexerConnectionPool;
+    use fuel_indexer_tests::fixtures::{run_graphql_query, setup_indexer};
+    use fuel_indexer_types::{scalar::UID, type_id};
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, schema) = setup_indexer(
+        &pool,
+        r#"
+type Wallet @entity @versioned {
+    id: ID!
+    balance: UInt8!
+}
+"#,
+        "versioned",
+        "test",
+    )
+    .await;
+
+    let wallet_type_id = type_id("versioned_test", "Wallet");
+    let id = UID::new(
+        "0000000000000000000000000000000000000000000000000000000000000001".to_string(),
+    )
+    .unwrap();
+
+    // The second write at block 4 overwrites the first, since both are in the same block.
+    for (height, balance) in [(1, 100), (2, 200), (4, 300), (4, 400)] {
+        db.set_block_height(height);
+        db.start_transaction().await.unwrap();
+        db.put_object(
+            wallet_type_id,
+            vec![
+                FtColumn::ID(Some(id.clone())),
+                FtColumn::UInt8(Some(balance)),
+            ],
+            vec![],
+        )
+        .await
+        .unwrap();
+        db.commit_transaction().await.unwrap();
+    }
+
+    let versions = |pool: sqlx::PgPool| async move {
+        sqlx::query(
+            "SELECT balance::bigint, lower(_block_range), upper(_block_range)
+            FROM versioned_test.wallet ORDER BY lower(_block_range)",
+        )
+        .fetch_all(&pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|row| (row.get(0), row.get(1), row.get(2)))
+        .collect::<Vec<(i64, i64, Option<i64>)>>()
+    };
+
+    assert_eq!(
+        versions(test_db.pool.clone()).await,
+        vec![(100, 1, Some(2)), (200, 2, Some(4)), (400, 4, None)]
+    );
+
+    let balance_as_of = |query: &'static str| {
+        let (pool, schema) = (&pool, &schema);
+        async move { run_graphql_query(pool, schema, query).await[0]["balance"].clone() }
+    };
+
+    assert_eq!(
+        balance_as_of("query { wallet(block: { number: 3 }) { balance } }").await,
+        200
+    );
+    assert_eq!(balance_as_of("query { wallet { balance } }").await, 400);
+}
+
+#[tokio::test]
+async fn test_schema_manager_creates_composite_indexes() {
+    use fuel_indexer_database::IndexerConnectionPool;
+    use fuel_indexer_lib::{graphql::GraphQLSchema, ExecutionSource};
+    use fuel_indexer_schema::db::manager::SchemaManager;
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let manager = SchemaManager::new(pool.clone());
+    let mut conn = pool.acquire().await.unwrap();
+
+    let schema = GraphQLSchema::new(
+        r#"
+type Position @entity @index(fields: ["owner", "pool"]) @unique(fields: ["pool", "nonce"]) {
+    id: ID!
+    owner: Charfield!
+    pool: Charfield!
+    nonce: UInt8!
+}
+"#
+        .to_string(),
+    );
+
+    manager
+        .new_schema(
+            "composite",
+            "test",
+            schema,
+            ExecutionSource::Wasm,
+            &mut conn,
+        )
+        .await
+        .unwrap();
+
+    let indexes = sqlx::query(
+        "SELECT indexdef FROM pg_indexes
+        WHERE schemaname = 'composite_test' AND indexname LIKE '%_idx'
+        ORDER BY indexname",
+    )
+    .fetch_all(&test_db.pool)
+    .await
+    .unwrap()
+    .into_iter()
+    .map(|row| row.get::<String, usize>(0))
+    .collect::<Vec<String>>();
+
+    assert_eq!(
+        indexes,
+        vec![
+            "CREATE INDEX position_owner_pool_idx ON composite_test.\"position\" USING btree (owner, pool)",
+            "CREATE UNIQUE INDEX position_pool_nonce_idx ON composite_test.\"position\" USING btree (pool, nonce)",
+        ]
+    );
+
+    let insert = "INSERT INTO composite_test.position (id, owner, pool, nonce, object)
+        VALUES ($1, 'alice', 'pool', 1, ''::bytea)";
+    sqlx::query(insert)
+        .bind("1")
+        .execute(&test_db.pool)
+        .await
+        .unwrap();
+    assert!(sqlx::query(insert)
+        .bind("2")
+        .execute(&test_db.pool)
+        .await
+        .is_err());
+}
+
+#[tokio::test]
+async fn test_immutable_entities_are_only_inserted() {
+    use fuel_indexer::{FtColumn, IndexerError};
+    use fuel_indexer_database::IndexerConnectionPool;
+    use fuel_indexer_tests::fixtures::setup_indexer;
+    use fuel_indexer_types::{scalar::UID, type_id};
+    use sqlx::Row;
+
+    let test_db = TestPostgresDb::new().await.unwrap();
+    let pool = IndexerConnectionPool::Postgres(test_db.pool.clone());
+    let (mut db, _) = setup_indexer(
+        &pool,
+        r#"
+type Swap @entity @immutable {
+    id: ID!
+    amount: UInt8!
+}
+
+type Deposit @entity @immutable(onDuplicate: Ignore) {
+    id: ID!
+    amount: UInt8!
+}
+"#,
+        "immutable",
+        "test",
+    )
+    .await;
+
+    let id = UID::new(format!("{:064}", 1)).unwrap();
+    let row = |amount: u64| {
+        vec![
+            FtColumn::ID(Some(id.clone())),
+            FtColumn::UInt8(Some(amount)),
+        ]
+    };
+
+    // Duplicate deposits are skipped, keeping the first record.
+    db.set_block_height(7);
+    db.start_transaction().await.unwrap();
+    for amount in [100, 200] {
+        db.put_object(type_id("immutable_test", "Deposit"), row(amount), vec![])
+            .await
+            .unwrap();
+    }
+    db.commit_transaction().await.unwrap();
+
+    let deposit =
+        sqlx::query("SELECT amount::int8, _block_height FROM immutable_test.deposit")
+            .fetch_all(&test_db.pool)
+            .await
+            .unwrap();
+    assert_eq!(deposit.len(), 1);
+    assert_eq!(deposit[0].get::<i64, _>(0), 100);
+    assert_eq!(deposit[0].get::<i32, _>(1), 7);
+
+    // Saved records can be loaded before they're committed.
+    db.start_transaction().await.unwrap();
+    db.put_object(type_id("immutable_test", "Swap"), row(300), vec![1])
+        .await
+        .unwrap();
+    assert_eq!(
+        db.get_object(type_id("immutable_test", "Swap"), id.to_string())
+            .await
+            .unwrap(),
+        Some(vec![1])
+    );
+
+    // A duplicate swap fails the whole transaction, and is reported with its table.
+    db.put_object(type_id("immutable_test", "Swap"), row(400), vec![])
+        .await
+        .unwrap();
+    assert!(matches!(
+        db.commit_transaction().await,
+        Err(IndexerError::DuplicateRecordError(table)) if table == "immutable_test.swap"
+    ));
+
+    let swaps: i64 = sqlx::query("SELECT count(*) FROM immutable_test.swap")
+        .fetch_one(&test_db.pool)
+        .await
+        .unwrap()
+        .get(0);
+    assert_eq!(swaps, 0);
+}
@@ -101,6 +101,34 @@ module:
             // Using a custom manifest here
             manifest_content.clone(),
         ),
+        (
+            "pass_if_indexer_is_valid_script_and_predicate.rs",
+            "simple_wasm_script_predicate.yaml",
+            TestKind::Pass,
+            // Using a custom manifest here
+            format!(
+                r#"
+        namespace: test_namespace
+        identifier: simple_wasm_executor
+        abi: {tests_root_str}/contracts/simple-wasm/out/debug/contracts-abi.json
+        graphql_schema: {tests_root_str}/indexers/simple-wasm/schema/simple_wasm.graphql
+        contract_id: ~
+        script:
+            abi: {tests_root_str}/contracts/simple-wasm/out/debug/script-abi.json
+            bytecode_root: "0xe04d10aa974f5ae14299e2083124f7b42e13ef7146863415cf27b42c3680c111"
+        predicate:
+            abi: {tests_root_str}/contracts/simple-wasm/out/debug/predicate-abi.json
+            address: "0x0101010101010101010101010101010101010101010101010101010101010101"
+        module:
+            wasm: {project_root_str}/target/wasm32-unknown-unknown/release/simple_wasm.wasm"#
+            ),
+        ),
+        (
+            "pass_if_indexer_uses_scheduled_handlers.rs",
+            "simple_wasm.yaml",
+            TestKind::Pass,
+            manifest_content.clone(),
+        ),
         (
             "fail_if_abi_contains_reserved_fuel_type.rs",
             "invalid_abi_type_simple_wasm.yaml",
@@ -135,6 +163,35 @@ module:
             TestKind::Fail,
             manifest_content.clone(),
         ),
+        (
+            "fail_if_handler_arg_type_is_misspelled.rs",
+            "simple_wasm.yaml",
+            TestKind::Fail,
+            manifest_content.clone(),
+        ),
+        (
+            "fail_if_handler_schedule_is_invalid.rs",
+            "simple_wasm.yaml",
+            TestKind::Fail,
+            manifest_content.clone(),
+        ),
+        (
+            "fail_if_schema_field_type_is_not_defined.rs",
+            "undefined_field_type_simple_wasm.yaml",
+            TestKind::Fail,
+            // Using a custom manifest here
+            format!(
+                r#"
+        namespace: test_namespace
+        identifier: simple_wasm_executor
+        abi: {tests_root_str}/contracts/simple-wasm/out/debug/contracts-abi.json
+        # This schema references a type that isn't defined
+        graphql_schema: {tests_root_str}/trybuild/undefined_field_type.graphql
+        contract_id: ~
+        module:
+            wasm: {project_root_str}/target/wasm32-unknown-unknown/release/simple_wasm.wasm"#
+            ),
+        ),
     ];
 
     for (name, manifest_name, kind, manifest_content) in tests {
@@ -133,6 +133,102 @@ async fn test_asset_upload_endpoint_properly_adds_assets_to_database_postgres()
     assert!(is_indexer_registered.is_some());
 }
 
+#[tokio::test]
+async fn test_live_deployment_supersedes_pending_shadow_indexer_postgres() {
+    use sqlx::Row;
+
+    let config = IndexerConfig {
+        replace_indexer: true,
+        ..IndexerConfig::default()
+    };
+
+    let WebTestComponents {
+        server,
+        db,
+        rx: _rx,
+        client,
+        ..
+    } = setup_web_test_components(Some(config)).await;
+
+    let deploy = |schema: String, replace_indexer: bool| {
+        let client = client.clone();
+        async move {
+            let form = multipart::Form::new()
+                .text("replace_indexer", replace_indexer.to_string())
+                .part(
+                    "manifest",
+                    multipart::Part::stream(assets::SIMPLE_WASM_MANIFEST)
+                        .file_name("simple_wasm.yaml"),
+                )
+                .part(
+                    "schema",
+                    multipart::Part::stream(schema).file_name("simple_wasm.graphql"),
+                )
+                .part(
+                    "wasm",
+                    multipart::Part::stream(assets::SIMPLE_WASM_WASM)
+                        .file_name("simple_wasm.wasm"),
+                );
+
+            client
+                .post("http://localhost:29987/api/index/test_namespace/simple_wasm_executor")
+                .multipart(form)
+                .header(CONTENT_TYPE, "multipart/form-data".to_owned())
+                .send()
+                .await
+                .unwrap()
+        }
+    };
+
+    let shadow_schema_exists = || async {
+        sqlx::query(
+            "SELECT COUNT(*) FROM information_schema.schemata WHERE schema_name = $1",
+        )
+        .bind("test_namespace_simple_wasm_executor__shadow")
+        .fetch_one(&db.pool)
+        .await
+        .unwrap()
+        .get::<i64, usize>(0)
+            > 0
+    };
+
+    let resp = deploy(assets::SIMPLE_WASM_SCHEMA.to_string(), false).await;
+    assert!(resp.status().is_success());
+
+    // Removing a field is a breaking change, so it is deployed as a shadow indexer.
+    let breaking = assets::SIMPLE_WASM_SCHEMA.replace("  hash: Bytes32! @indexed\n", "");
+    let resp = deploy(breaking, true).await;
+    assert!(resp.status().is_success());
+
+    let mut conn = db.pool.acquire().await.unwrap();
+    assert_eq!(
+        postgres::all_shadow_indexers(&mut conn)
+            .await
+            .unwrap()
+            .len(),
+        1
+    );
+    assert!(shadow_schema_exists().await);
+
+    // Adding a nullable field is not, so it replaces the live indexer, and the
+    // pending shadow indexer is dropped.
+    let non_breaking = assets::SIMPLE_WASM_SCHEMA.replace(
+        "  account: Address!\n}",
+        "  account: Address!\n  label: Charfield\n}",
+    );
+    assert_ne!(non_breaking, assets::SIMPLE_WASM_SCHEMA);
+    let resp = deploy(non_breaking, true).await;
+    assert!(resp.status().is_success());
+
+    server.abort();
+
+    assert!(postgres::all_shadow_indexers(&mut conn)
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(!shadow_schema_exists().await);
+}
+
 #[derive(Serialize, Debug)]
 struct SignatureRequest {
     signature: String,
@@ -0,0 +1,10 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm.yaml")]
+mod indexer {
+    #[every(days = 1)]
+    fn function_one(block: BlockData) {
+        let _ = block;
+    }
+}
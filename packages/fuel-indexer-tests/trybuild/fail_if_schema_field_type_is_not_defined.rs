@@ -0,0 +1,12 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/undefined_field_type_simple_wasm.yaml")]
+mod indexer {
+    fn function_one(event: SomeEvent) {
+        let SomeEvent { id, account } = event;
+
+        let t1 = Thing1 { id, account };
+        t1.save();
+    }
+}
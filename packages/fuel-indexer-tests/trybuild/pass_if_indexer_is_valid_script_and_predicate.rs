@@ -0,0 +1,112 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+
+#[no_mangle]
+fn ff_log_data(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_object(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_many_to_many_record(_inp: ()) {}
+
+#[no_mangle]
+fn ff_set_block_height(_inp: ()) {}
+
+#[no_mangle]
+fn ff_early_exit(_inp: ()) {}
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm_script_predicate.yaml")]
+mod indexer {
+    fn function_one(event: ScriptEvent) {
+        let ScriptEvent { id, amount } = event;
+
+        assert_eq!(id, 7);
+        assert_eq!(amount, 100);
+    }
+
+    fn function_two(coin: InputCoin, input: PredicateInput) {
+        let PredicateInput { owner, nonce } = input;
+
+        assert_eq!(coin.amount, 100);
+        assert_eq!(owner, Bits256([1u8; 32]));
+        assert_eq!(nonce, 3);
+    }
+}
+
+fn main() {
+    use fuels::core::codec::ABIEncoder;
+
+    let event = ScriptEvent { id: 7, amount: 100 };
+    let log_data = ABIEncoder::encode(&[event.into_token()])
+        .expect("Failed compile test")
+        .resolve(0);
+
+    let input = PredicateInput {
+        owner: Bits256([1u8; 32]),
+        nonce: 3,
+    };
+    let predicate_data = ABIEncoder::encode(&[input.into_token()])
+        .expect("Failed compile test")
+        .resolve(0);
+
+    let data: Vec<BlockData> = vec![BlockData {
+        id: [0u8; 32].into(),
+        time: 1,
+        producer: None,
+        height: 0,
+        consensus: fuel::Consensus::default(),
+        header: fuel::Header {
+            id: [0u8; 32].into(),
+            da_height: 1,
+            transactions_count: 1,
+            message_receipt_count: 1,
+            transactions_root: [0u8; 32].into(),
+            height: 1,
+            prev_root: [0u8; 32].into(),
+            time: 1,
+            application_hash: [0u8; 32].into(),
+            message_receipt_root: [0u8; 32].into(),
+        },
+        transactions: vec![fuel::TransactionData {
+            status: fuel::TransactionStatus::default(),
+            id: [0u8; 32].into(),
+            receipts: vec![fuel::Receipt::LogData {
+                id: [0u8; 32].into(),
+                ra: 0,
+                rb: 0,
+                ptr: 0,
+                len: log_data.len() as u64,
+                digest: [0u8; 32].into(),
+                data: Some(log_data),
+                pc: 0,
+                is: 0,
+            }],
+            transaction: fuel::Transaction::Script(fuel::Script {
+                script: vec![0x24, 0x04, 0x00, 0x00],
+                inputs: vec![fuel::Input::Coin(fuel::InputCoin {
+                    utxo_id: fuel::UtxoId::default(),
+                    owner: [1u8; 32].into(),
+                    amount: 100,
+                    asset_id: [0u8; 32].into(),
+                    tx_pointer: fuel::TxPointer {
+                        block_height: 0u32.into(),
+                        tx_index: 0,
+                    },
+                    witness_index: 0,
+                    maturity: 0u32.into(),
+                    predicate: vec![0x24, 0x04, 0x00, 0x00].into(),
+                    predicate_data: predicate_data.into(),
+                })],
+                ..fuel::Script::default()
+            }),
+        }],
+    }];
+
+    let mut bytes = serialize(&data);
+
+    let ptr = bytes.as_mut_ptr();
+    let len = bytes.len();
+
+    handle_events(ptr, len);
+}
@@ -10,6 +10,9 @@ fn ff_put_object(_inp: ()) {}
 #[no_mangle]
 fn ff_put_many_to_many_record(_inp: ()) {}
 
+#[no_mangle]
+fn ff_set_block_height(_inp: ()) {}
+
 #[no_mangle]
 fn ff_early_exit(_inp: ()) {}
 
@@ -0,0 +1,79 @@
+extern crate alloc;
+use fuel_indexer_utils::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[no_mangle]
+fn ff_log_data(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_object(_inp: ()) {}
+
+#[no_mangle]
+fn ff_put_many_to_many_record(_inp: ()) {}
+
+#[no_mangle]
+fn ff_set_block_height(_inp: ()) {}
+
+#[no_mangle]
+fn ff_early_exit(_inp: ()) {}
+
+static EVERY_TWO_BLOCKS: AtomicUsize = AtomicUsize::new(0);
+static EVERY_HOUR: AtomicUsize = AtomicUsize::new(0);
+
+#[indexer(manifest = "packages/fuel-indexer-tests/trybuild/simple_wasm.yaml")]
+mod indexer {
+    #[every_n_blocks(2)]
+    fn function_one(block: BlockData) {
+        assert_eq!(block.height % 2, 0);
+        EVERY_TWO_BLOCKS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    #[every(hours = 1)]
+    fn function_two(block: BlockData) {
+        assert_ne!(block.header.time, 1800);
+        EVERY_HOUR.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+fn block(height: u32, time: i64) -> BlockData {
+    BlockData {
+        id: [0u8; 32].into(),
+        time,
+        producer: None,
+        height,
+        consensus: fuel::Consensus::default(),
+        header: fuel::Header {
+            id: [0u8; 32].into(),
+            da_height: 1,
+            transactions_count: 0,
+            message_receipt_count: 0,
+            transactions_root: [0u8; 32].into(),
+            height,
+            prev_root: [0u8; 32].into(),
+            time,
+            application_hash: [0u8; 32].into(),
+            message_receipt_root: [0u8; 32].into(),
+        },
+        transactions: vec![],
+    }
+}
+
+fn main() {
+    let data: Vec<BlockData> = vec![
+        block(1, 0),
+        block(2, 1800),
+        block(3, 3600),
+        block(4, 7300),
+    ];
+
+    let mut bytes = serialize(&data);
+
+    let ptr = bytes.as_mut_ptr();
+    let len = bytes.len();
+
+    handle_events(ptr, len);
+
+    // Blocks 2 and 4 are multiples of two, and blocks 1, 3 and 4 each start a new hour.
+    assert_eq!(EVERY_TWO_BLOCKS.load(Ordering::SeqCst), 2);
+    assert_eq!(EVERY_HOUR.load(Ordering::SeqCst), 3);
+}
@@ -5,8 +5,9 @@
 use crate::{scalar::*, type_id, TypeId, FUEL_TYPES_NAMESPACE};
 pub use fuel_tx::ScriptExecutionResult;
 pub use fuel_tx::{
-    Input as ClientInput, Output as ClientOutput, PanicReason as ClientPanicReason,
-    Transaction as ClientTransaction, TxPointer as ClientTxPointer,
+    Contract as ClientContract, Input as ClientInput, Output as ClientOutput,
+    PanicReason as ClientPanicReason, Transaction as ClientTransaction,
+    TxPointer as ClientTxPointer,
 };
 pub use fuel_tx::{Receipt, TxId, UtxoId, Witness, Word};
 use serde::{Deserialize, Serialize};
@@ -309,6 +310,12 @@ pub struct InputCoin {
     pub predicate_data: HexString,
 }
 
+impl TypeId for InputCoin {
+    fn type_id() -> usize {
+        type_id(FUEL_TYPES_NAMESPACE, "InputCoin") as usize
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct InputContract {
     pub utxo_id: UtxoId,
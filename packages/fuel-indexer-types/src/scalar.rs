@@ -1,9 +1,11 @@
+pub use bigdecimal::BigDecimal;
 use bytes::Bytes;
 pub use fuel_types::{
     Address, AssetId, BlockHeight, Bytes32, Bytes4, Bytes64, Bytes8, ContractId,
     MessageId, Nonce, Salt, Word,
 };
 use fuels::types::SizedAsciiString;
+pub use fuels::types::U256;
 use serde::{Deserialize, Serialize};
 use tai64::Tai64;
 
@@ -31,6 +33,9 @@ pub type UInt8 = u64;
 /// Scalar for 16-byte unsigned integers.
 pub type UInt16 = u128;
 
+/// Scalar for 32-byte unsigned integers.
+pub type UInt32 = U256;
+
 /// Scalar for 8-byte integers aliased as `Timestamp`s.
 pub type Timestamp = u64;
 
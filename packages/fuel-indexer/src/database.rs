@@ -1,14 +1,41 @@
 use crate::{IndexerConfig, IndexerError, IndexerResult, Manifest};
 use fuel_indexer_database::{
-    queries, IndexerConnection, IndexerConnectionPool, IndexerDatabaseError,
+    queries, types::SqlNamed, DbType, IndexerConnection, IndexerConnectionPool,
+    IndexerDatabaseError,
 };
 use fuel_indexer_lib::{
-    fully_qualified_namespace, graphql::types::IdCol, utils::format_sql_query,
+    fully_qualified_namespace,
+    graphql::{
+        types::{BlockHeightCol, BlockRangeCol, IdCol},
+        GraphQLSchema, OnDuplicate,
+    },
+    shadow_identifier,
+    utils::{format_sql_query, EntityChanges},
+    ExecutionSource,
 };
-use fuel_indexer_schema::FtColumn;
-use std::collections::HashMap;
+use fuel_indexer_schema::{db::tables::IndexerSchema, FtColumn};
+use std::collections::{HashMap, HashSet};
 use tracing::{debug, error, info};
 
+/// Maximum number of rows buffered for an insert-only table before they're written.
+const MAX_PENDING_INSERTS: usize = 1_000;
+
+/// Postgres error code for a unique constraint violation.
+const UNIQUE_VIOLATION: &str = "23505";
+
+/// A row saved to an insert-only table that hasn't been written to the database yet.
+#[derive(Debug)]
+struct PendingInsert {
+    /// SQL fragments for the values of each column, except the object column.
+    values: Vec<String>,
+
+    /// Height of the block in which the row was saved.
+    block_height: u32,
+
+    /// Serialized object.
+    bytes: Vec<u8>,
+}
+
 /// Database for an executor instance, with schema info.
 #[derive(Debug)]
 pub struct Database {
@@ -27,12 +54,31 @@ pub struct Database {
     /// Version of the indexer.
     version: String,
 
+    /// Name of the database schema that holds the indexer's tables.
+    schema_name: String,
+
     /// Table schema for the indexer.
     schema: HashMap<String, Vec<String>>,
 
     /// Mapping of `TypeId`s to tables.
     tables: HashMap<i64, String>,
 
+    /// Tables that keep a row for each version of a record.
+    versioned: HashSet<String>,
+
+    /// Insert-only tables, and what happens when a duplicate record is saved to them.
+    immutable: HashMap<String, OnDuplicate>,
+
+    /// Rows saved to insert-only tables in the current transaction that haven't been
+    /// written yet, by table.
+    pending_inserts: HashMap<String, Vec<PendingInsert>>,
+
+    /// Height of the block being indexed.
+    block_height: Option<u32>,
+
+    /// IDs of the records saved in the current transaction, by `TypeId`.
+    changes: HashMap<i64, HashSet<String>>,
+
     /// Indexer configuration.
     config: IndexerConfig,
 }
@@ -55,14 +101,25 @@ impl Database {
             namespace: manifest.namespace().to_string(),
             identifier: manifest.identifier().to_string(),
             version: Default::default(),
+            schema_name: fully_qualified_namespace(
+                manifest.namespace(),
+                manifest.identifier(),
+            ),
             schema: Default::default(),
             tables: Default::default(),
+            versioned: Default::default(),
+            immutable: Default::default(),
+            pending_inserts: Default::default(),
+            block_height: None,
+            changes: Default::default(),
             config: config.clone(),
         }
     }
 
     /// Open a database transaction.
     pub async fn start_transaction(&mut self) -> IndexerResult<usize> {
+        self.pending_inserts.clear();
+        self.changes.clear();
         let conn = self.pool.acquire().await?;
         self.stashed = Some(conn);
         debug!("Connection stashed as: {:?}", self.stashed);
@@ -78,6 +135,12 @@ impl Database {
 
     /// Commit transaction to database.
     pub async fn commit_transaction(&mut self) -> IndexerResult<usize> {
+        // Buffered rows can still fail to be written, e.g. on a duplicate record, in
+        // which case nothing from this transaction is committed.
+        if let Err(e) = self.flush_inserts().await {
+            self.revert_transaction().await?;
+            return Err(e);
+        }
         let conn =
             self.stashed
                 .as_mut()
@@ -90,6 +153,8 @@ impl Database {
 
     /// Revert open transaction.
     pub async fn revert_transaction(&mut self) -> IndexerResult<usize> {
+        self.pending_inserts.clear();
+        self.changes.clear();
         let conn =
             self.stashed
                 .as_mut()
@@ -100,27 +165,61 @@ impl Database {
         Ok(res)
     }
 
+    /// Take the records saved in the last committed transaction, if there are any,
+    /// so that they can be published to subscribers.
+    pub fn take_entity_changes(&mut self) -> Option<EntityChanges> {
+        if self.changes.is_empty() {
+            return None;
+        }
+
+        Some(EntityChanges {
+            namespace: self.namespace.clone(),
+            identifier: self.identifier.clone(),
+            changes: std::mem::take(&mut self.changes),
+            block_height: self.block_height,
+        })
+    }
+
     /// Build an upsert query using a set of columns, insert values, update values, and a table name.
+    ///
+    /// For versioned tables, the new row is the current version of the record, starting at the
+    /// given block height.
     fn upsert_query(
         &self,
         table: &str,
         columns: &[String],
         inserts: Vec<String>,
         updates: Vec<String>,
+        block_height: Option<u32>,
     ) -> String {
-        if is_id_only_upsert(columns) {
-            format!(
-                "INSERT INTO {} ({}) VALUES ({}, $1::bytea) ON CONFLICT(id) DO NOTHING",
-                table,
+        let id_only = is_id_only_upsert(columns);
+
+        let (columns, values, conflict) = match block_height {
+            Some(height) => {
+                let block_range = BlockRangeCol::to_lowercase_str();
+                (
+                    format!("{}, {block_range}", columns.join(", ")),
+                    format!(
+                        "{}, $1::bytea, int8range({height}, NULL)",
+                        inserts.join(", ")
+                    ),
+                    format!("(id) WHERE upper_inf({block_range})"),
+                )
+            }
+            None => (
                 columns.join(", "),
-                inserts.join(", "),
+                format!("{}, $1::bytea", inserts.join(", ")),
+                "(id)".to_string(),
+            ),
+        };
+
+        if id_only {
+            format!(
+                "INSERT INTO {table} ({columns}) VALUES ({values}) ON CONFLICT{conflict} DO NOTHING",
             )
         } else {
             format!(
-                "INSERT INTO {} ({}) VALUES ({}, $1::bytea) ON CONFLICT(id) DO UPDATE SET {}",
-                table,
-                columns.join(", "),
-                inserts.join(", "),
+                "INSERT INTO {table} ({columns}) VALUES ({values}) ON CONFLICT{conflict} DO UPDATE SET {}",
                 updates.join(", "),
             )
         }
@@ -128,7 +227,13 @@ impl Database {
 
     /// Return a query to get an object from the database.
     fn get_query(&self, table: &str, object_id: &str) -> String {
-        let q = format!("SELECT object from {table} where id = '{object_id}'");
+        let mut q = format!("SELECT object from {table} where id = '{object_id}'");
+        if self.versioned.contains(table) {
+            q.push_str(&format!(
+                " AND upper_inf({})",
+                BlockRangeCol::to_lowercase_str()
+            ));
+        }
         if self.config.verbose {
             info!("{q}");
         }
@@ -158,23 +263,91 @@ Do your WASM modules need to be rebuilt?
             }
         };
 
+        if let Some(FtColumn::ID(Some(id))) = columns.first() {
+            self.changes
+                .entry(type_id)
+                .or_default()
+                .insert(id.to_string());
+        }
+
         let inserts: Vec<_> = columns.iter().map(|col| col.query_fragment()).collect();
+
+        // Rows of insert-only tables are buffered, and written together later on.
+        if self.immutable.contains_key(table) {
+            let block_height = self.block_height.ok_or_else(|| {
+                IndexerError::Unknown(format!(
+                    "No block height available for immutable table {table}."
+                ))
+            })?;
+
+            let table = table.clone();
+            let pending = self.pending_inserts.entry(table).or_default();
+            pending.push(PendingInsert {
+                values: inserts,
+                block_height,
+                bytes,
+            });
+
+            if pending.len() >= MAX_PENDING_INSERTS {
+                self.flush_inserts().await?;
+            }
+
+            return Ok(());
+        }
+
         let updates: Vec<_> = self.schema[table]
             .iter()
             .zip(columns.iter())
             .map(|(colname, value)| format!("{colname} = {}", value.query_fragment()))
             .collect();
 
+        let block_height = if self.versioned.contains(table) {
+            Some(self.block_height.ok_or_else(|| {
+                IndexerError::Unknown(format!(
+                    "No block height available for versioned table {table}."
+                ))
+            })?)
+        } else {
+            None
+        };
+
+        // Close the range of the current version of the record. A version that was written
+        // in the same block is overwritten by the upsert instead.
+        let close_query_text = block_height.map(|height| {
+            let block_range = BlockRangeCol::to_lowercase_str();
+            let id = self.schema[table]
+                .iter()
+                .position(|colname| colname == IdCol::to_lowercase_str())
+                .map(|i| inserts[i].clone())
+                .unwrap_or_default();
+            format_sql_query(format!(
+                "UPDATE {table} SET {block_range} = int8range(lower({block_range}), {height}) WHERE id = {id} AND upper_inf({block_range}) AND lower({block_range}) < {height}"
+            ))
+        });
+
         let columns = self.schema[table].clone();
 
-        let query_text =
-            format_sql_query(self.upsert_query(table, &columns, inserts, updates));
+        let query_text = format_sql_query(self.upsert_query(
+            table,
+            &columns,
+            inserts,
+            updates,
+            block_height,
+        ));
 
         let conn = self
             .stashed
             .as_mut()
             .ok_or(IndexerError::NoTransactionError("put_object".to_string()))?;
 
+        if let Some(close_query_text) = close_query_text {
+            if self.config.verbose {
+                info!("{close_query_text}");
+            }
+
+            queries::execute_query(conn, close_query_text).await?;
+        }
+
         if self.config.verbose {
             info!("{query_text}");
         }
@@ -184,6 +357,83 @@ Do your WASM modules need to be rebuilt?
         Ok(())
     }
 
+    /// Write the rows saved to insert-only tables that haven't been written yet.
+    ///
+    /// The rows of each table are written with a single `INSERT`. Since these tables are
+    /// never updated, a duplicate record either fails the insert, or is skipped, depending
+    /// on the table's `@immutable` directive. A failed insert is reported along with the
+    /// table that the duplicate record was saved to.
+    pub async fn flush_inserts(&mut self) -> IndexerResult<()> {
+        if self.pending_inserts.is_empty() {
+            return Ok(());
+        }
+
+        let conn = self
+            .stashed
+            .as_mut()
+            .ok_or(IndexerError::NoTransactionError(
+                "flush_inserts".to_string(),
+            ))?;
+
+        for (table, rows) in self.pending_inserts.drain() {
+            let values = rows
+                .iter()
+                .enumerate()
+                .map(|(i, row)| {
+                    format!(
+                        "({}, ${}::bytea, {})",
+                        row.values.join(", "),
+                        i + 1,
+                        row.block_height
+                    )
+                })
+                .collect::<Vec<String>>()
+                .join(", ");
+
+            let mut query_text = format!(
+                "INSERT INTO {table} ({}, {}) VALUES {values}",
+                self.schema[&table].join(", "),
+                BlockHeightCol::to_lowercase_str(),
+            );
+
+            if self.immutable.get(&table) == Some(&OnDuplicate::Ignore) {
+                query_text.push_str(" ON CONFLICT (id) DO NOTHING");
+            }
+
+            let query_text = format_sql_query(query_text);
+
+            if self.config.verbose {
+                info!("{query_text}");
+            }
+
+            let result = queries::put_objects(
+                conn,
+                query_text,
+                rows.into_iter().map(|row| row.bytes).collect(),
+            )
+            .await;
+
+            match result {
+                Err(sqlx::Error::Database(e))
+                    if e.code().as_deref() == Some(UNIQUE_VIOLATION) =>
+                {
+                    return Err(IndexerError::DuplicateRecordError(table));
+                }
+                Err(e) => return Err(e.into()),
+                Ok(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Set the height of the block that is about to be indexed.
+    ///
+    /// Records saved to versioned and insert-only tables are saved at this height.
+    pub fn set_block_height(&mut self, height: u32) {
+        self.block_height = Some(height);
+    }
+
     /// Get an object from the database.
     pub async fn get_object(
         &mut self,
@@ -195,6 +445,11 @@ Do your WASM modules need to be rebuilt?
             .get(&type_id)
             .ok_or(IndexerDatabaseError::TableMappingDoesNotExist(type_id))?;
         let query = self.get_query(table, &object_id);
+
+        // Records that were saved earlier in this transaction have to be written before
+        // they can be found.
+        self.flush_inserts().await?;
+
         let conn = self
             .stashed
             .as_mut()
@@ -231,11 +486,7 @@ Do your WASM modules need to be rebuilt?
         .await?;
 
         for column in columns {
-            let table = &format!(
-                "{}.{}",
-                fully_qualified_namespace(&self.namespace, &self.identifier),
-                &column.table_name
-            );
+            let table = &format!("{}.{}", self.schema_name, &column.table_name);
 
             self.tables
                 .entry(column.type_id)
@@ -246,6 +497,91 @@ Do your WASM modules need to be rebuilt?
             columns.push(column.column_name);
         }
 
+        let versioned =
+            queries::versioned_table_names(&mut conn, &self.schema_name).await?;
+        for table in versioned {
+            self.versioned
+                .insert(format!("{}.{}", self.schema_name, table));
+        }
+
+        // Whether a table is insert-only, and how it treats duplicates, is declared
+        // in the GraphQL schema rather than in the table itself.
+        let root =
+            queries::graph_root_latest(&mut conn, &self.namespace, &self.identifier)
+                .await?;
+        let tables = IndexerSchema::new(
+            &self.namespace,
+            &self.identifier,
+            &GraphQLSchema::new(root.schema),
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )?
+        .derive_tables();
+
+        for table in tables {
+            if let Some(on_duplicate) = table.on_duplicate() {
+                self.immutable.insert(
+                    format!("{}.{}", self.schema_name, table.sql_name()),
+                    on_duplicate,
+                );
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Load the schema for a shadow deployment of this indexer, and build a mapping of
+    /// `TypeId`s to the tables in the shadow schema.
+    ///
+    /// Shadow deployments aren't added to the graph registry until they're promoted, so
+    /// the mapping is derived from the GraphQL schema itself. `TypeId`s are derived from
+    /// the live namespace and identifier, since those are what the indexer module uses.
+    pub async fn load_shadow_schema(
+        &mut self,
+        schema: &GraphQLSchema,
+    ) -> IndexerResult<()> {
+        self.version = schema.version().to_string();
+        self.schema_name = fully_qualified_namespace(
+            &self.namespace,
+            &shadow_identifier(&self.identifier),
+        );
+
+        info!(
+            "Database loading shadow schema for Indexer({}.{}) with Version({}).",
+            self.namespace, self.identifier, self.version
+        );
+
+        let tables = IndexerSchema::new(
+            &self.namespace,
+            &self.identifier,
+            schema,
+            DbType::Postgres,
+            ExecutionSource::Wasm,
+        )?
+        .derive_tables();
+
+        for table in tables {
+            let name = format!("{}.{}", self.schema_name, table.sql_name());
+            if table.is_versioned() {
+                self.versioned.insert(name.clone());
+            }
+
+            if let Some(on_duplicate) = table.on_duplicate() {
+                self.immutable.insert(name.clone(), on_duplicate);
+            }
+
+            for column in table.columns() {
+                self.tables
+                    .entry(column.type_id)
+                    .or_insert_with(|| name.clone());
+
+                self.schema
+                    .entry(name.clone())
+                    .or_default()
+                    .push(column.name.clone());
+            }
+        }
+
         Ok(())
     }
 
@@ -282,6 +618,21 @@ Do your WASM modules need to be rebuilt?
                 "put_many_to_many_record".to_string(),
             ))?;
 
+        // Join table queries are built by the indexer module, which only knows
+        // about the live schema.
+        let live = fully_qualified_namespace(&self.namespace, &self.identifier);
+        let queries = queries.into_iter().map(|q| {
+            if live == self.schema_name {
+                q
+            } else {
+                q.replacen(
+                    &format!("INSERT INTO {live}."),
+                    &format!("INSERT INTO {}.", self.schema_name),
+                    1,
+                )
+            }
+        });
+
         for query in queries {
             if self.config.verbose {
                 info!("{query}");
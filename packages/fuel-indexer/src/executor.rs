@@ -13,7 +13,11 @@ use fuel_core_client::client::{
 };
 use fuel_indexer_database::IndexerConnectionPool;
 use fuel_indexer_lib::{
-    defaults::*, manifest::Manifest, utils::serialize, WasmIndexerError,
+    defaults::*,
+    graphql::GraphQLSchema,
+    manifest::Manifest,
+    utils::{publish_entity_changes, serialize},
+    WasmIndexerError,
 };
 use fuel_indexer_types::{
     fuel::{field::*, *},
@@ -70,14 +74,14 @@ impl From<ExecutorSource> for Vec<u8> {
 }
 
 /// Run the executor task until the kill switch is flipped, or until some other
-/// stop criteria is met.
+/// stop criteria is met. Returns the error that stopped the executor, if any.
 //
 // In general the logic in this function isn't very idiomatic, but that's because
 // types in `fuel_core_client` don't compile to WASM.
 pub fn run_executor<T: 'static + Executor + Send + Sync>(
     config: &IndexerConfig,
     mut executor: T,
-) -> impl Future<Output = ()> {
+) -> impl Future<Output = IndexerResult<()>> {
     // TODO: https://github.com/FuelLabs/fuel-indexer/issues/286
 
     let end_block = executor.manifest().end_block();
@@ -200,7 +204,7 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                 // Run time metering is deterministic. There is no point in retrying.
                 if let IndexerError::RunTimeLimitExceededError = e {
                     error!("Indexer({indexer_uid}) executor run time limit exceeded. Giving up. <('.')>. Consider increasing metering points");
-                    break;
+                    return Err(e);
                 }
 
                 // We don't want to retry forever as that eats resources.
@@ -208,7 +212,7 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
                     error!(
                         "Indexer({indexer_uid}) failed after too many retries, giving up. <('.')>"
                     );
-                    break;
+                    return Err(e);
                 }
 
                 if let IndexerError::SqlxError(sqlx::Error::Database(inner)) = e {
@@ -246,6 +250,8 @@ pub fn run_executor<T: 'static + Executor + Send + Sync>(
             // Since we had successful call, we reset the retry count.
             consecutive_retries = 0;
         }
+
+        Ok(())
     }
 }
 
@@ -623,7 +629,22 @@ where
             if self.kill_switch.load(Ordering::SeqCst) {
                 self.db.lock().await.revert_transaction().await?;
             } else {
-                self.db.lock().await.commit_transaction().await?;
+                let mut db = self.db.lock().await;
+
+                // Buffered rows are written before committing, so that a duplicate
+                // record fails this batch, and is reported with its table.
+                if let Err(e) = db.flush_inserts().await {
+                    error!("NativeIndexExecutor({uid}) failed to write records: {e}.");
+                    db.revert_transaction().await?;
+                    return Err(e);
+                }
+
+                db.commit_transaction().await?;
+
+                // Let subscribers know about the records that were saved.
+                if let Some(changes) = db.take_entity_changes() {
+                    publish_entity_changes(changes);
+                }
             }
         }
         Ok(())
@@ -788,6 +809,37 @@ impl WasmIndexExecutor {
         }
     }
 
+    /// Create a new `WasmIndexExecutor` that writes to the shadow schema of an indexer.
+    pub async fn create_shadow(
+        config: &IndexerConfig,
+        manifest: &Manifest,
+        pool: IndexerConnectionPool,
+        schema: &GraphQLSchema,
+        wasm_bytes: impl AsRef<[u8]>,
+    ) -> IndexerResult<Self> {
+        let uid = manifest.uid();
+
+        let executor = match WasmIndexExecutor::new(
+            config,
+            manifest,
+            wasm_bytes,
+            pool,
+            schema.version().to_string(),
+        )
+        .await
+        {
+            Ok(executor) => executor,
+            Err(e) => {
+                error!("Could not instantiate shadow WasmIndexExecutor({uid}): {e:?}.");
+                return Err(IndexerError::WasmExecutionInstantiationError);
+            }
+        };
+
+        executor.db.lock().await.load_shadow_schema(schema).await?;
+
+        Ok(executor)
+    }
+
     /// Returns true if metering is enabled.
     pub fn metering_enabled(&self) -> bool {
         self.metering_points.is_some()
@@ -906,7 +958,22 @@ impl Executor for WasmIndexExecutor {
             if self.kill_switch.load(Ordering::SeqCst) {
                 self.db.lock().await.revert_transaction().await?;
             } else {
-                self.db.lock().await.commit_transaction().await?;
+                let mut db = self.db.lock().await;
+
+                // Buffered rows are written before committing, so that a duplicate
+                // record fails this batch, and is reported with its table.
+                if let Err(e) = db.flush_inserts().await {
+                    error!("Indexer({uid}) failed to write records: {e}.");
+                    db.revert_transaction().await?;
+                    return Err(e);
+                }
+
+                db.commit_transaction().await?;
+
+                // Let subscribers know about the records that were saved.
+                if let Some(changes) = db.take_entity_changes() {
+                    publish_entity_changes(changes);
+                }
             }
         }
 
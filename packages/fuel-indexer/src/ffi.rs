@@ -291,6 +291,30 @@ fn put_many_to_many_record(
     Ok(())
 }
 
+/// Set the height of the block that the indexer module is about to process.
+///
+/// Records saved to versioned and insert-only tables are saved at this height.
+fn set_block_height(
+    env: FunctionEnvMut<IndexEnv>,
+    height: u32,
+) -> Result<(), WasmIndexerError> {
+    let idx_env = env.data();
+
+    if idx_env
+        .kill_switch
+        .load(std::sync::atomic::Ordering::SeqCst)
+    {
+        // If the kill switch has been flipped, returning an error will cause an
+        // early termination of WASM execution.
+        return Err(WasmIndexerError::KillSwitch);
+    }
+
+    let rt = tokio::runtime::Handle::current();
+    rt.block_on(async { idx_env.db.lock().await.set_block_height(height) });
+
+    Ok(())
+}
+
 /// When called from WASM it will terminate the execution and return the error
 /// code.
 pub fn early_exit(err_code: u32) -> Result<(), WasmIndexerError> {
@@ -306,6 +330,7 @@ pub fn get_exports(store: &mut Store, env: &wasmer::FunctionEnv<IndexEnv>) -> Ex
     let f_log_data = Function::new_typed_with_env(store, env, log_data);
     let f_put_many_to_many_record =
         Function::new_typed_with_env(store, env, put_many_to_many_record);
+    let f_set_block_height = Function::new_typed_with_env(store, env, set_block_height);
     let f_early_exit = Function::new_typed(store, early_exit);
 
     exports.insert("ff_early_exit".to_string(), f_early_exit);
@@ -316,6 +341,7 @@ pub fn get_exports(store: &mut Store, env: &wasmer::FunctionEnv<IndexEnv>) -> Ex
         f_put_many_to_many_record,
     );
     exports.insert("ff_log_data".to_string(), f_log_data);
+    exports.insert("ff_set_block_height".to_string(), f_set_block_height);
 
     exports
 }
@@ -88,4 +88,6 @@ pub enum IndexerError {
     EndBlockMet,
     #[error("Invalid schema: {0:?}")]
     SchemaVersionMismatch(String),
+    #[error("Duplicate record saved to immutable table {0}")]
+    DuplicateRecordError(String),
 }
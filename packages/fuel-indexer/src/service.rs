@@ -5,16 +5,27 @@ use crate::{
 use async_std::sync::{Arc, Mutex};
 use async_std::{fs::File, io::ReadExt};
 use fuel_indexer_database::{
-    queries, types::IndexerAssetType, IndexerConnection, IndexerConnectionPool,
+    queries,
+    types::{IndexerAssetType, ShadowIndexer},
+    IndexerConnection, IndexerConnectionPool,
+};
+use fuel_indexer_lib::{
+    defaults,
+    graphql::GraphQLSchema,
+    shadow_identifier,
+    utils::{publish_schema_change, SchemaChange, ServiceRequest},
+    ExecutionSource,
 };
-use fuel_indexer_lib::utils::ServiceRequest;
 use fuel_indexer_schema::db::manager::SchemaManager;
 use fuel_indexer_types::fuel::BlockData;
 use futures::Future;
 use std::collections::HashMap;
 use std::marker::Send;
 use std::sync::atomic::{AtomicBool, Ordering};
-use tokio::sync::mpsc::Receiver;
+use tokio::{
+    sync::mpsc::Receiver,
+    time::{interval, sleep, timeout, Duration},
+};
 use tracing::{error, info, warn};
 
 /// Primary service used to run one or many indexers.
@@ -36,6 +47,9 @@ pub struct IndexerService {
 
     /// Killers used to stop the spawned indexers.
     killers: HashMap<String, Arc<AtomicBool>>,
+
+    /// Flags set once the spawned indexers have stopped running.
+    finished: HashMap<String, Arc<AtomicBool>>,
 }
 
 impl IndexerService {
@@ -52,6 +66,7 @@ impl IndexerService {
             pool,
             manager,
             killers: HashMap::default(),
+            finished: HashMap::default(),
             tasks: tokio::task::JoinSet::new(),
             rx,
         })
@@ -209,6 +224,18 @@ impl IndexerService {
             }
         }
 
+        for shadow in queries::all_shadow_indexers(&mut conn).await? {
+            if let Err(e) = self
+                .start_shadow_executor(&shadow.namespace, &shadow.identifier)
+                .await
+            {
+                error!(
+                    "Failed to register shadow Indexer({}) from registry: {e:?}",
+                    shadow.uid()
+                );
+            }
+        }
+
         Ok(())
     }
 
@@ -260,12 +287,20 @@ impl IndexerService {
 
     /// Kick it off! Run the indexer service loop, listening to service messages primarily coming from the web API.
     pub async fn run(mut self) -> IndexerResult<()> {
+        let mut shadow_check =
+            interval(Duration::from_secs(defaults::SHADOW_INDEXER_CHECK_SECS));
+
         loop {
             tokio::select! {
                 // Calling join_next will remove finished tasks from the set.
                 Some(Err(e)) = self.tasks.join_next() => {
                     error!("Error retiring indexer task {e}");
                 }
+                _ = shadow_check.tick() => {
+                    if let Err(e) = self.check_shadow_indexers().await {
+                        error!("Failed to check shadow indexers: {e:?}");
+                    }
+                }
                 Some(service_request) = self.rx.recv() => {
                     match service_request {
                         ServiceRequest::Reload(request) => {
@@ -289,13 +324,21 @@ impl IndexerService {
                                         get_start_block(&mut conn, &manifest).await?;
                                     manifest.set_start_block(start_block);
 
-                                    if let Some(killer_for_prev_executor) =
-                                        self.killers.remove(&manifest.uid())
-                                    {
-                                        let uid = manifest.uid();
+                                    let uid = manifest.uid();
+                                    if self.killers.contains_key(&uid) {
                                         info!("Indexer({uid}) is being replaced. Stopping previous version of Indexer({uid}).");
-                                        killer_for_prev_executor
-                                            .store(true, Ordering::SeqCst);
+                                    }
+
+                                    // A live deployment supersedes any pending shadow
+                                    // version of the indexer.
+                                    let shadow_uid = shadow_uid(
+                                        &request.namespace,
+                                        &request.identifier,
+                                    );
+                                    for key in [&uid, &shadow_uid] {
+                                        if let Err(e) = self.stop_executor(key).await {
+                                            error!("Failed to stop Indexer({key}): {e:?}");
+                                        }
                                     }
 
                                     match WasmIndexExecutor::create(
@@ -330,33 +373,382 @@ impl IndexerService {
                         ServiceRequest::Stop(request) => {
                             let uid = format!("{}.{}", request.namespace, request.identifier);
 
-                            if let Some(killer) = self.killers.remove(&uid) {
-                                killer.store(true, Ordering::SeqCst);
-                            } else {
+                            if !self.killers.contains_key(&uid) {
                                 warn!(
                                     "Stop Indexer: No indexer with the name Indexer({uid})"
                                 );
                             }
+
+                            let shadow_uid =
+                                shadow_uid(&request.namespace, &request.identifier);
+                            for key in [&uid, &shadow_uid] {
+                                if let Err(e) = self.stop_executor(key).await {
+                                    error!("Failed to stop Indexer({key}): {e:?}");
+                                }
+                            }
+                        }
+                        ServiceRequest::Shadow(request) => {
+                            if let Err(e) = self
+                                .start_shadow_executor(
+                                    &request.namespace,
+                                    &request.identifier,
+                                )
+                                .await
+                            {
+                                error!(
+                                    "Failed to start shadow Indexer({}.{}): {e:?}",
+                                    &request.namespace, &request.identifier
+                                );
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    /// Start indexing the shadow version of an indexer into its shadow schema,
+    /// replacing any shadow executor that is already running for it.
+    async fn start_shadow_executor(
+        &mut self,
+        namespace: &str,
+        identifier: &str,
+    ) -> IndexerResult<()> {
+        let uid = shadow_uid(namespace, identifier);
+        self.stop_executor(&uid).await?;
+
+        let mut conn = self.pool.acquire().await?;
+        let shadow = queries::shadow_indexer(&mut conn, namespace, identifier).await?;
+
+        let mut manifest = Manifest::try_from(&shadow.manifest)?;
+        let schema = GraphQLSchema::from(shadow.schema);
+
+        // Shadow indexers always resume from their own progress.
+        let last = queries::last_block_height_for_indexer(
+            &mut conn,
+            namespace,
+            &shadow_identifier(identifier),
+        )
+        .await?;
+        let start_block = std::cmp::max(manifest.start_block().unwrap_or(1), last);
+        manifest.set_start_block(start_block);
+
+        let executor = WasmIndexExecutor::create_shadow(
+            &self.config,
+            &manifest,
+            self.pool.clone(),
+            &schema,
+            shadow.wasm,
+        )
+        .await?;
+
+        info!("Starting shadow Indexer({uid}) from block {start_block}");
+
+        self.spawn_executor(uid, executor);
+
+        Ok(())
+    }
+
+    /// Promote the shadow indexers that have caught up with their live versions,
+    /// and drop the retired schemas whose grace period has passed.
+    async fn check_shadow_indexers(&mut self) -> IndexerResult<()> {
+        let mut conn = self.pool.acquire().await?;
+
+        for shadow in queries::all_shadow_indexers(&mut conn).await? {
+            let live = queries::last_block_height_for_indexer(
+                &mut conn,
+                &shadow.namespace,
+                &shadow.identifier,
+            )
+            .await?;
+            // A shadow indexer that hasn't committed a block yet can't have caught
+            // up, even with a live indexer that hasn't either.
+            let synced = match queries::last_committed_block_height(
+                &mut conn,
+                &shadow.namespace,
+                &shadow_identifier(&shadow.identifier),
+            )
+            .await?
+            {
+                Some(synced) if synced >= live => synced,
+                _ => {
+                    // The shadow executor is left stopped by a promotion that timed
+                    // out while stopping it, so it's restarted to keep syncing.
+                    let key = shadow_uid(&shadow.namespace, &shadow.identifier);
+                    if !self.killers.contains_key(&key)
+                        && self.killers.contains_key(&shadow.uid())
+                    {
+                        if let Err(e) = self
+                            .start_shadow_executor(&shadow.namespace, &shadow.identifier)
+                            .await
+                        {
+                            error!("Failed to restart shadow Indexer({key}): {e:?}");
                         }
                     }
+                    continue;
                 }
+            };
+
+            let uid = shadow.uid();
+            info!("Shadow Indexer({uid}) has caught up at block {synced}. Promoting it.");
+
+            if let Err(e) = self.promote_shadow_indexer(&mut conn, shadow).await {
+                error!("Failed to promote shadow Indexer({uid}): {e:?}");
             }
         }
+
+        for schema_name in queries::expired_retired_schemas(&mut conn).await? {
+            info!("Dropping retired schema {schema_name}.");
+            queries::drop_retired_schema(&mut conn, &schema_name).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Replace the live version of an indexer with its shadow version.
+    ///
+    /// Both executors are stopped, the shadow schema takes the place of the live
+    /// schema in a single transaction, and the new version is started from where
+    /// the shadow version left off.
+    ///
+    /// If either executor doesn't stop in time, the promotion is abandoned, to be
+    /// retried at the next check, since it could still write to the schemas.
+    async fn promote_shadow_indexer(
+        &mut self,
+        conn: &mut IndexerConnection,
+        shadow: ShadowIndexer,
+    ) -> IndexerResult<()> {
+        let namespace = shadow.namespace.clone();
+        let identifier = shadow.identifier.clone();
+
+        self.stop_executor(&shadow_uid(&namespace, &identifier))
+            .await?;
+        if let Err(e) = self.stop_executor(&shadow.uid()).await {
+            self.start_shadow_executor(&namespace, &identifier).await?;
+            return Err(e);
+        }
+
+        queries::start_transaction(conn).await?;
+
+        if let Err(e) = self.promote_shadow_schema(conn, shadow).await {
+            queries::revert_transaction(conn).await?;
+            self.start_shadow_executor(&namespace, &identifier).await?;
+            self.start_registered_executor(conn, &namespace, &identifier)
+                .await?;
+            return Err(e);
+        }
+
+        queries::commit_transaction(conn).await?;
+
+        info!("Promoted shadow Indexer({namespace}.{identifier}).");
+
+        // The web API serves the indexer with its previous schema until told otherwise.
+        publish_schema_change(SchemaChange {
+            namespace: namespace.clone(),
+            identifier: identifier.clone(),
+        });
+
+        self.start_registered_executor(conn, &namespace, &identifier)
+            .await
+    }
+
+    /// Swap the shadow schema in for the live schema, register the shadow version's
+    /// assets, and retire the previous schema.
+    async fn promote_shadow_schema(
+        &self,
+        conn: &mut IndexerConnection,
+        shadow: ShadowIndexer,
+    ) -> IndexerResult<()> {
+        let namespace = &shadow.namespace;
+        let identifier = &shadow.identifier;
+        let schema = GraphQLSchema::from(shadow.schema.clone());
+
+        let retired = self
+            .manager
+            .promote_shadow_schema(
+                namespace,
+                identifier,
+                &schema,
+                // Only WASM indexers can be shadowed.
+                ExecutionSource::Wasm,
+                conn,
+            )
+            .await?;
+
+        let items = vec![
+            (IndexerAssetType::Wasm, shadow.wasm),
+            (IndexerAssetType::Manifest, shadow.manifest),
+            (IndexerAssetType::Schema, (&schema).into()),
+        ];
+
+        for (asset_type, bytes) in items {
+            queries::register_indexer_asset(
+                conn,
+                namespace,
+                identifier,
+                bytes,
+                asset_type,
+                shadow.pubkey.as_deref(),
+            )
+            .await?;
+        }
+
+        queries::remove_shadow_indexer(conn, namespace, identifier).await?;
+        queries::retire_schema(
+            conn,
+            &retired,
+            defaults::RETIRED_SCHEMA_GRACE_PERIOD_SECS,
+        )
+        .await?;
+
+        Ok(())
+    }
+
+    /// Start an executor for a registered indexer, using its latest assets.
+    async fn start_registered_executor(
+        &mut self,
+        conn: &mut IndexerConnection,
+        namespace: &str,
+        identifier: &str,
+    ) -> IndexerResult<()> {
+        let id = queries::get_indexer_id(conn, namespace, identifier).await?;
+        let assets = queries::indexer_assets(conn, &id).await?;
+        let mut manifest = Manifest::try_from(&assets.manifest.bytes)?;
+
+        let start_block = get_start_block(conn, &manifest).await?;
+        manifest.set_start_block(start_block);
+
+        let executor = WasmIndexExecutor::create(
+            &self.config,
+            &manifest,
+            self.pool.clone(),
+            assets.schema.digest,
+            assets.wasm.bytes,
+        )
+        .await?;
+
+        self.start_executor(executor);
+
+        Ok(())
     }
 
     // Spawn and register a tokio::task running the Executor loop, as well as
     // the kill switch and the abort handle.
     fn start_executor<T: 'static + Executor + Send + Sync>(&mut self, executor: T) {
         let uid = executor.manifest().uid();
+        self.spawn_executor(uid, executor);
+    }
+
+    // Spawn a tokio::task running the Executor loop, registering its kill switch
+    // and finished flag under the given key.
+    fn spawn_executor<T: 'static + Executor + Send + Sync>(
+        &mut self,
+        key: String,
+        executor: T,
+    ) {
+        let finished = Arc::new(AtomicBool::new(false));
+
+        // Errors are only recorded for live indexers, since a shadow indexer runs
+        // under the manifest of the live indexer that it will replace.
+        let manifest = executor.manifest().clone();
+        let pool = (key == manifest.uid()).then(|| self.pool.clone());
 
         self.killers
-            .insert(uid.clone(), executor.kill_switch().clone());
+            .insert(key.clone(), executor.kill_switch().clone());
+        self.finished.insert(key, finished.clone());
+
+        let run = crate::executor::run_executor(&self.config, executor);
+        self.tasks.spawn(async move {
+            if let Some(pool) = &pool {
+                if let Err(e) = clear_indexing_errors(pool, &manifest).await {
+                    error!(
+                        "Failed to clear errors of Indexer({}): {e:?}",
+                        manifest.uid()
+                    );
+                }
+            }
+
+            if let (Some(pool), Err(e)) = (&pool, run.await) {
+                if let Err(e) = record_indexing_error(pool, &manifest, &e).await {
+                    error!(
+                        "Failed to record error of Indexer({}): {e:?}",
+                        manifest.uid()
+                    );
+                }
+            }
+
+            finished.store(true, Ordering::SeqCst);
+        });
+    }
+
+    // Flip the kill switch of a spawned executor, and wait for it to finish
+    // the batch of blocks it is working on. An executor that doesn't finish in
+    // time is kept track of, so that stopping it again waits for it.
+    async fn stop_executor(&mut self, key: &str) -> IndexerResult<()> {
+        if let Some(killer) = self.killers.remove(key) {
+            killer.store(true, Ordering::SeqCst);
+        }
 
-        self.tasks
-            .spawn(crate::executor::run_executor(&self.config, executor));
+        if let Some(finished) = self.finished.remove(key) {
+            let wait = async {
+                while !finished.load(Ordering::SeqCst) {
+                    sleep(Duration::from_millis(100)).await;
+                }
+            };
+
+            if let Err(e) = timeout(
+                Duration::from_secs(defaults::EXECUTOR_STOP_TIMEOUT_SECS),
+                wait,
+            )
+            .await
+            {
+                warn!("Timed out waiting for Indexer({key}) to stop.");
+                self.finished.insert(key.to_string(), finished);
+                return Err(e.into());
+            }
+        }
+
+        Ok(())
     }
 }
 
+/// Key used to register the executor of a shadow indexer.
+fn shadow_uid(namespace: &str, identifier: &str) -> String {
+    format!("{namespace}.{}", shadow_identifier(identifier))
+}
+
+/// Remove the errors recorded for an indexer, as it starts running again.
+async fn clear_indexing_errors(
+    pool: &IndexerConnectionPool,
+    manifest: &Manifest,
+) -> IndexerResult<()> {
+    let mut conn = pool.acquire().await?;
+    queries::clear_indexing_errors(
+        &mut conn,
+        manifest.namespace(),
+        manifest.identifier(),
+    )
+    .await?;
+    Ok(())
+}
+
+/// Record the error that stopped an indexer, so that it can be reported by the web API.
+async fn record_indexing_error(
+    pool: &IndexerConnectionPool,
+    manifest: &Manifest,
+    error: &IndexerError,
+) -> IndexerResult<()> {
+    let mut conn = pool.acquire().await?;
+    queries::record_indexing_error(
+        &mut conn,
+        manifest.namespace(),
+        manifest.identifier(),
+        &error.to_string(),
+    )
+    .await?;
+    Ok(())
+}
+
 /// Determine the starting block for this indexer.
 pub async fn get_start_block(
     conn: &mut IndexerConnection,
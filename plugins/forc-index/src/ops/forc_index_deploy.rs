@@ -3,11 +3,13 @@ use crate::{
     commands::{build, remove},
     utils::{file_part, project_dir_info},
 };
-use fuel_indexer_lib::manifest::Manifest;
+use fuel_indexer_lib::{
+    graphql::enum_entities::schema_with_enum_entities, manifest::Manifest,
+};
 use indicatif::{ProgressBar, ProgressStyle};
 use reqwest::{
     header::{HeaderMap, AUTHORIZATION, CONNECTION},
-    multipart::Form,
+    multipart::{Form, Part},
     Client, StatusCode,
 };
 use serde_json::{to_string_pretty, value::Value, Map};
@@ -75,10 +77,27 @@ pub async fn init(command: DeployCommand) -> anyhow::Result<()> {
 
     let manifest_module_file = workspace_root.join(manifest.module());
 
+    // Enum entities are appended to the schema when the indexer is built, so
+    // the uploaded schema must include them for the schema versions to match.
+    let schema_part = match manifest.abi() {
+        Some(abi) if manifest.enum_entities().is_some() => {
+            let mut rooted = manifest.clone();
+            rooted.set_abi(workspace_root.join(abi).to_str().unwrap().to_string());
+            let entities = rooted.derive_enum_entities()?;
+            let schema = std::fs::read_to_string(&manifest_schema_file)?;
+            let file_name = Path::new(&manifest_schema_file)
+                .file_name()
+                .map(|name| name.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            Part::text(schema_with_enum_entities(&schema, &entities)).file_name(file_name)
+        }
+        _ => file_part(manifest_schema_file).await?,
+    };
+
     let form = Form::new()
         .text("replace_indexer", replace_indexer.to_string())
         .part("manifest", file_part(&manifest_path).await?)
-        .part("schema", file_part(manifest_schema_file).await?)
+        .part("schema", schema_part)
         .part("wasm", file_part(manifest_module_file).await?);
 
     let target = format!(
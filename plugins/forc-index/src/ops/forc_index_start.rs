@@ -26,6 +26,12 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
         jwt_secret,
         jwt_issuer,
         jwt_expiry,
+        max_query_depth,
+        max_query_joins,
+        default_query_limit,
+        max_query_limit,
+        query_timeout,
+        response_cache_size,
         verbose,
         ..
     } = command;
@@ -67,6 +73,18 @@ pub async fn init(command: StartCommand) -> anyhow::Result<()> {
             ("--jwt-secret", jwt_secret),
             ("--jwt-issuer", jwt_issuer),
             ("--jwt-expiry", jwt_expiry.map(|x| x.to_string())),
+            ("--max-query-depth", max_query_depth.map(|x| x.to_string())),
+            ("--max-query-joins", max_query_joins.map(|x| x.to_string())),
+            (
+                "--default-query-limit",
+                default_query_limit.map(|x| x.to_string()),
+            ),
+            ("--max-query-limit", max_query_limit.map(|x| x.to_string())),
+            ("--query-timeout", query_timeout.map(|x| x.to_string())),
+            (
+                "--response-cache-size",
+                response_cache_size.map(|x| x.to_string()),
+            ),
         ];
         for (opt, value) in options.iter() {
             if let Some(value) = value {
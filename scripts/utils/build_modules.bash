@@ -18,6 +18,12 @@ bash scripts/stripper.bash simple_wasm.wasm
 cp simple_wasm.wasm target/wasm32-unknown-unknown/release/
 rm -fv simple_wasm.wasm
 
+# This is a test index; its native build lives in the parity integration tests
+cargo build -p parity-wasm --release --target wasm32-unknown-unknown
+bash scripts/stripper.bash parity_wasm.wasm
+cp parity_wasm.wasm target/wasm32-unknown-unknown/release/
+rm -fv parity_wasm.wasm
+
 cargo build -p fuel_explorer --release --target wasm32-unknown-unknown
 bash scripts/stripper.bash fuel_explorer.wasm
 cp fuel_explorer.wasm target/wasm32-unknown-unknown/release/